@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use chia_protocol::Bytes32;
+use tokio::time::Instant;
+
+use crate::{ClientError, Peer};
+
+/// How often the coin state is requested while waiting for a spend to be confirmed.
+pub const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for the peer to return the transaction from its mempool.
+/// Full nodes don't respond if they don't have the transaction, so there is no other way to tell.
+pub const MEMPOOL_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Waits for a coin to be spent on-chain after its transaction was sent to the mempool,
+/// and returns the height of the block it was spent in.
+///
+/// If the coin is still unspent when the timeout elapses, the transaction may still be pending,
+/// and [`ClientError::ConfirmationTimeout`] is returned. If the coin is unspent and the peer no longer
+/// has the transaction in its mempool, for example because it was evicted or replaced, or if the peer
+/// no longer knows about the coin because the block it was created in was reorged out, the spend
+/// can't be confirmed and [`ClientError::TransactionDropped`] is returned.
+///
+/// The genesis challenge of the network is required to request the coin state from the peer.
+pub async fn wait_for_confirmation(
+    peer: &Peer,
+    transaction_id: Bytes32,
+    coin_id: Bytes32,
+    genesis_challenge: Bytes32,
+    timeout: Duration,
+) -> Result<u32, ClientError> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(spent_height) = spent_height(peer, coin_id, genesis_challenge).await? {
+            return Ok(spent_height);
+        }
+
+        let now = Instant::now();
+
+        if now >= deadline {
+            return Err(ClientError::ConfirmationTimeout(coin_id));
+        }
+
+        let in_mempool = match tokio::time::timeout(
+            MEMPOOL_RESPONSE_TIMEOUT.min(deadline - now),
+            peer.request_transaction(transaction_id),
+        )
+        .await
+        {
+            Ok(response) => {
+                response?;
+                true
+            }
+            Err(_elapsed) => false,
+        };
+
+        if !in_mempool {
+            // The transaction may have been removed from the mempool because it was just confirmed.
+            if let Some(spent_height) = spent_height(peer, coin_id, genesis_challenge).await? {
+                return Ok(spent_height);
+            }
+
+            if Instant::now() >= deadline {
+                return Err(ClientError::ConfirmationTimeout(coin_id));
+            }
+
+            return Err(ClientError::TransactionDropped(coin_id));
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL.min(remaining)).await;
+    }
+}
+
+/// Looks up the height the coin was spent at, if it has been spent.
+async fn spent_height(
+    peer: &Peer,
+    coin_id: Bytes32,
+    genesis_challenge: Bytes32,
+) -> Result<Option<u32>, ClientError> {
+    let Some(coin_state) = peer
+        .get_coin_record_by_name(coin_id, genesis_challenge)
+        .await?
+    else {
+        return Err(ClientError::TransactionDropped(coin_id));
+    };
+
+    Ok(coin_state.spent_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::Signature;
+    use chia_protocol::{
+        ChiaProtocolMessage, Coin, CoinState, Message, RequestCoinState, RequestTransaction,
+        RespondCoinState, RespondTransaction, SpendBundle,
+    };
+    use chia_traits::Streamable;
+    use futures_util::{SinkExt, StreamExt};
+
+    use crate::mock_peer::mock_peer;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_confirmed_while_in_mempool() -> anyhow::Result<()> {
+        let coin = Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 1);
+        let spend_bundle = SpendBundle::new(Vec::new(), Signature::default());
+        let transaction_id = spend_bundle.name();
+
+        // The coin is spent the second time its state is requested.
+        let peer = mock_peer(move |mut ws| async move {
+            let mut lookups = 0;
+
+            while let Some(Ok(tungstenite::Message::Binary(bytes))) = ws.next().await {
+                let request = Message::from_bytes(&bytes).unwrap();
+
+                let (msg_type, data) = if request.msg_type == RequestCoinState::msg_type() {
+                    lookups += 1;
+                    let spent_height = (lookups > 1).then_some(7);
+                    let response = RespondCoinState::new(
+                        vec![coin.coin_id()],
+                        vec![CoinState::new(coin, spent_height, Some(1))],
+                    );
+                    (RespondCoinState::msg_type(), response.to_bytes().unwrap())
+                } else {
+                    assert_eq!(request.msg_type, RequestTransaction::msg_type());
+                    let response = RespondTransaction::new(spend_bundle.clone());
+                    (RespondTransaction::msg_type(), response.to_bytes().unwrap())
+                };
+
+                let response = Message {
+                    msg_type,
+                    id: request.id,
+                    data: data.into(),
+                };
+
+                ws.send(tungstenite::Message::Binary(response.to_bytes().unwrap()))
+                    .await
+                    .unwrap();
+            }
+        })
+        .await?;
+
+        let height = wait_for_confirmation(
+            &peer,
+            transaction_id,
+            coin.coin_id(),
+            Bytes32::default(),
+            CONFIRMATION_POLL_INTERVAL * 2,
+        )
+        .await?;
+        assert_eq!(height, 7);
+
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use tokio::sync::mpsc;
 use tokio_tungstenite::Connector;
 use tracing::instrument;
 
-use crate::{ClientError, Peer};
+use crate::{retry_with_backoff, ClientError, Peer, RetryPolicy};
 
 #[instrument(skip(connector))]
 pub async fn connect_peer(
@@ -59,3 +59,17 @@ pub async fn connect_peer(
 
     Ok((peer, receiver))
 }
+
+/// Connects to a peer with [`connect_peer`], retrying transient failures according to the policy.
+#[instrument(skip(connector))]
+pub async fn connect_peer_with_retry(
+    network_id: String,
+    connector: Connector,
+    socket_addr: SocketAddr,
+    policy: RetryPolicy,
+) -> Result<(Peer, mpsc::Receiver<Message>), ClientError> {
+    retry_with_backoff(policy, || {
+        connect_peer(network_id.clone(), connector.clone(), socket_addr)
+    })
+    .await
+}
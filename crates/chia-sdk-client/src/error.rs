@@ -1,4 +1,4 @@
-use chia_protocol::{NodeType, ProtocolMessageTypes};
+use chia_protocol::{Bytes32, NodeType, ProtocolMessageTypes, RejectStateReason};
 use thiserror::Error;
 use tokio::sync::oneshot::error::RecvError;
 
@@ -55,4 +55,59 @@ pub enum ClientError {
 
     #[error("The peer is banned")]
     BannedPeer,
+
+    #[error("Fee estimate error: {0}")]
+    FeeEstimate(String),
+
+    #[error("Coin state request rejected: {0:?}")]
+    CoinStateRejected(RejectStateReason),
+
+    #[error("Coin {0} was not spent before the timeout")]
+    ConfirmationTimeout(Bytes32),
+
+    #[error("The transaction spending coin {0} was dropped")]
+    TransactionDropped(Bytes32),
+
+    #[error("The peer hasn't announced a peak, so it may not be synced")]
+    NotSynced,
+
+    #[error("The peer doesn't have a block at height {0}")]
+    MissingBlock(u32),
+
+    #[error("Timed out waiting for a response from the peer")]
+    TimedOut,
+
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+
+    #[error("Missing genesis challenge for network {0}")]
+    MissingGenesisChallenge(String),
+
+    #[error("Invalid config value for {0}")]
+    InvalidConfig(String),
+}
+
+impl ClientError {
+    /// Whether the error may go away by retrying, such as a dropped connection.
+    /// Configuration errors, TLS failures and rejections by the peer are not considered transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            Self::WebSocket(tungstenite::Error::Tls(..)) => false,
+            #[cfg(feature = "native-tls")]
+            Self::NativeTls(..) => false,
+            #[cfg(feature = "rustls")]
+            Self::Rustls(..) => false,
+            Self::WebSocket(error) => !matches!(
+                error,
+                tungstenite::Error::Url(..) | tungstenite::Error::HttpFormat(..)
+            ),
+            Self::Io(..)
+            | Self::Recv(..)
+            | Self::MissingHandshake
+            | Self::NotSynced
+            | Self::TimedOut => true,
+            _ => false,
+        }
+    }
 }
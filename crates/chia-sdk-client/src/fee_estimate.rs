@@ -0,0 +1,124 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chia_protocol::{RequestFeeEstimates, RespondFeeEstimates};
+
+use crate::{ClientError, Peer};
+
+/// The fee rate in mojos per cost which is used when the mempool is empty or the estimate is lower.
+pub const MIN_FEE_RATE: u64 = 5;
+
+/// Blocks are made every 18.75 seconds on average, which is represented here as a fraction.
+const BLOCK_TIME_NUMERATOR: u64 = 75;
+const BLOCK_TIME_DENOMINATOR: u64 = 4;
+
+/// Requests a fee estimate from the peer, and returns the fee rate in mojos per cost that's
+/// expected to be included within the given number of blocks. The result is at least [`MIN_FEE_RATE`].
+pub async fn estimate_fee(peer: &Peer, target_blocks: u32) -> Result<u64, ClientError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let offset = u64::from(target_blocks) * BLOCK_TIME_NUMERATOR / BLOCK_TIME_DENOMINATOR;
+
+    let response: RespondFeeEstimates = peer
+        .request_infallible(RequestFeeEstimates::new(vec![now + offset]))
+        .await?;
+
+    let group = response.estimates;
+
+    if let Some(error) = group.error {
+        return Err(ClientError::FeeEstimate(error));
+    }
+
+    let Some(estimate) = group.estimates.into_iter().next() else {
+        return Ok(MIN_FEE_RATE);
+    };
+
+    if let Some(error) = estimate.error {
+        return Err(ClientError::FeeEstimate(error));
+    }
+
+    Ok(estimate
+        .estimated_fee_rate
+        .mojos_per_clvm_cost
+        .max(MIN_FEE_RATE))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::{ChiaProtocolMessage, FeeEstimate, FeeEstimateGroup, FeeRate, Message};
+    use chia_traits::Streamable;
+    use futures_util::{SinkExt, StreamExt};
+
+    use crate::mock_peer::mock_peer;
+
+    use super::*;
+
+    /// Connects to a mock peer, which answers a single fee estimate request.
+    async fn fee_peer(estimates: FeeEstimateGroup) -> anyhow::Result<Peer> {
+        mock_peer(|mut ws| async move {
+            let Some(Ok(tungstenite::Message::Binary(bytes))) = ws.next().await else {
+                panic!("expected a binary message");
+            };
+
+            let request = Message::from_bytes(&bytes).unwrap();
+            assert_eq!(request.msg_type, RequestFeeEstimates::msg_type());
+
+            let response = Message {
+                msg_type: RespondFeeEstimates::msg_type(),
+                id: request.id,
+                data: RespondFeeEstimates::new(estimates)
+                    .to_bytes()
+                    .unwrap()
+                    .into(),
+            };
+
+            ws.send(tungstenite::Message::Binary(response.to_bytes().unwrap()))
+                .await
+                .unwrap();
+
+            std::future::pending::<()>().await;
+        })
+        .await
+    }
+
+    fn estimate(mojos_per_clvm_cost: u64) -> FeeEstimateGroup {
+        FeeEstimateGroup::new(
+            None,
+            vec![FeeEstimate::new(None, 0, FeeRate::new(mojos_per_clvm_cost))],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee() -> anyhow::Result<()> {
+        let peer = fee_peer(estimate(42)).await?;
+        assert_eq!(estimate_fee(&peer, 10).await?, 42);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_mempool_fee() -> anyhow::Result<()> {
+        let peer = fee_peer(estimate(0)).await?;
+        assert_eq!(estimate_fee(&peer, 1).await?, MIN_FEE_RATE);
+
+        let peer = fee_peer(FeeEstimateGroup::new(None, Vec::new())).await?;
+        assert_eq!(estimate_fee(&peer, 1).await?, MIN_FEE_RATE);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_fee_estimate_error() -> anyhow::Result<()> {
+        let peer = fee_peer(FeeEstimateGroup::new(
+            Some("Fee estimator is not ready".to_string()),
+            Vec::new(),
+        ))
+        .await?;
+
+        assert!(matches!(
+            estimate_fee(&peer, 1).await,
+            Err(ClientError::FeeEstimate(..))
+        ));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,29 @@
+mod confirmation;
 mod error;
+mod fee_estimate;
+mod message_router;
+#[cfg(test)]
+mod mock_peer;
 mod network;
+mod network_config;
+mod network_registry;
 mod peer;
+mod peer_pool;
 mod request_map;
+mod retry;
+mod subscription_manager;
 mod tls;
 
+pub use confirmation::*;
 pub use error::*;
+pub use fee_estimate::*;
+pub use message_router::*;
 pub use network::*;
+pub use network_registry::*;
 pub use peer::*;
+pub use peer_pool::*;
+pub use retry::*;
+pub use subscription_manager::*;
 pub use tls::*;
 
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
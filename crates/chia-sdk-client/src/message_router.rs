@@ -0,0 +1,154 @@
+use chia_protocol::{ChiaProtocolMessage, CoinStateUpdate, Message, NewPeakWallet};
+use chia_traits::Streamable;
+use tokio::{
+    sync::mpsc::{self, error::TrySendError},
+    task::JoinHandle,
+};
+use tracing::warn;
+
+/// Splits the messages received from a peer into a typed channel for each kind of message that wallets handle.
+///
+/// Messages of any other type are sent to [`MessageRouter::other`]. If one of the receivers is dropped or full,
+/// messages for it are discarded, so that a receiver which is never read doesn't stop the routing of every other message.
+#[derive(Debug)]
+pub struct MessageRouter {
+    pub coin_state_updates: mpsc::Receiver<CoinStateUpdate>,
+    pub new_peaks: mpsc::Receiver<NewPeakWallet>,
+    pub other: mpsc::Receiver<Message>,
+    route_task: JoinHandle<()>,
+}
+
+impl MessageRouter {
+    /// Routes the messages from the receiver returned when the peer was connected.
+    pub fn new(receiver: mpsc::Receiver<Message>) -> Self {
+        let (coin_state_sender, coin_state_updates) = mpsc::channel(32);
+        let (new_peak_sender, new_peaks) = mpsc::channel(32);
+        let (other_sender, other) = mpsc::channel(32);
+
+        let route_task = tokio::spawn(route_messages(
+            receiver,
+            coin_state_sender,
+            new_peak_sender,
+            other_sender,
+        ));
+
+        Self {
+            coin_state_updates,
+            new_peaks,
+            other,
+            route_task,
+        }
+    }
+}
+
+impl Drop for MessageRouter {
+    fn drop(&mut self) {
+        self.route_task.abort();
+    }
+}
+
+async fn route_messages(
+    mut receiver: mpsc::Receiver<Message>,
+    coin_state_sender: mpsc::Sender<CoinStateUpdate>,
+    new_peak_sender: mpsc::Sender<NewPeakWallet>,
+    other_sender: mpsc::Sender<Message>,
+) {
+    while let Some(message) = receiver.recv().await {
+        if message.msg_type == CoinStateUpdate::msg_type() {
+            match CoinStateUpdate::from_bytes(&message.data) {
+                Ok(update) => forward(&coin_state_sender, update),
+                Err(error) => warn!("Failed to parse coin state update: {error}"),
+            }
+        } else if message.msg_type == NewPeakWallet::msg_type() {
+            match NewPeakWallet::from_bytes(&message.data) {
+                Ok(peak) => forward(&new_peak_sender, peak),
+                Err(error) => warn!("Failed to parse new peak: {error}"),
+            }
+        } else {
+            forward(&other_sender, message);
+        }
+    }
+}
+
+fn forward<T>(sender: &mpsc::Sender<T>, value: T) {
+    if let Err(TrySendError::Full(..)) = sender.try_send(value) {
+        warn!(
+            "Dropping {} message, since the receiver is full",
+            std::any::type_name::<T>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::{Bytes32, RespondPeers};
+
+    use super::*;
+
+    fn message<T>(body: &T) -> anyhow::Result<Message>
+    where
+        T: Streamable + ChiaProtocolMessage,
+    {
+        Ok(Message::new(T::msg_type(), None, body.to_bytes()?.into()))
+    }
+
+    #[tokio::test]
+    async fn test_route_mixed_messages() -> anyhow::Result<()> {
+        let (sender, receiver) = mpsc::channel(32);
+        let mut router = MessageRouter::new(receiver);
+
+        let update = CoinStateUpdate::new(10, 9, Bytes32::new([1; 32]), Vec::new());
+        let peak = NewPeakWallet::new(Bytes32::new([2; 32]), 10, 1000, 9);
+        let peers = RespondPeers::new(Vec::new());
+
+        sender.send(message(&peak)?).await?;
+        sender.send(message(&peers)?).await?;
+        sender.send(message(&update)?).await?;
+
+        // A message which can't be parsed is skipped, rather than stopping the router.
+        sender
+            .send(Message::new(
+                CoinStateUpdate::msg_type(),
+                None,
+                vec![1, 2, 3].into(),
+            ))
+            .await?;
+        sender.send(message(&update)?).await?;
+        drop(sender);
+
+        assert_eq!(router.new_peaks.recv().await, Some(peak));
+        assert_eq!(router.new_peaks.recv().await, None);
+
+        assert_eq!(router.coin_state_updates.recv().await, Some(update.clone()));
+        assert_eq!(router.coin_state_updates.recv().await, Some(update));
+        assert_eq!(router.coin_state_updates.recv().await, None);
+
+        let other = router.other.recv().await.expect("missing message");
+        assert_eq!(other.msg_type, RespondPeers::msg_type());
+        assert_eq!(RespondPeers::from_bytes(&other.data)?, peers);
+        assert!(router.other.recv().await.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unread_receiver() -> anyhow::Result<()> {
+        let (sender, receiver) = mpsc::channel(32);
+        let mut router = MessageRouter::new(receiver);
+
+        let update = CoinStateUpdate::new(10, 9, Bytes32::new([1; 32]), Vec::new());
+        let peak = NewPeakWallet::new(Bytes32::new([2; 32]), 10, 1000, 9);
+
+        // The coin state updates are never read, so the extra ones are dropped once the channel is full.
+        for _ in 0..100 {
+            sender.send(message(&update)?).await?;
+        }
+        sender.send(message(&peak)?).await?;
+        drop(sender);
+
+        assert_eq!(router.new_peaks.recv().await, Some(peak));
+        assert_eq!(router.new_peaks.recv().await, None);
+
+        Ok(())
+    }
+}
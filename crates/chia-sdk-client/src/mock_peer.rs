@@ -0,0 +1,49 @@
+use std::{future::Future, net::SocketAddr};
+
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::WebSocketStream;
+
+use crate::{ClientError, Peer};
+
+/// The server side of a connection to a mock peer.
+pub(crate) type MockSocket = WebSocketStream<TcpStream>;
+
+/// Listens on a local port, drops the first `refused` connections before the websocket handshake,
+/// and passes the next websocket connection to the handler.
+pub(crate) async fn mock_server<F, Fut>(refused: usize, handler: F) -> anyhow::Result<SocketAddr>
+where
+    F: FnOnce(MockSocket) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        for _ in 0..refused {
+            drop(listener.accept().await.unwrap());
+        }
+
+        let (stream, _) = listener.accept().await.unwrap();
+        let ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        handler(ws).await;
+    });
+
+    Ok(addr)
+}
+
+/// Connects a [`Peer`] to a local websocket server, such as one started by [`mock_server`].
+pub(crate) async fn connect_mock_peer(addr: SocketAddr) -> Result<Peer, ClientError> {
+    let (ws, _) = tokio_tungstenite::connect_async(format!("ws://{addr}")).await?;
+    let (peer, _receiver) = Peer::from_websocket(ws)?;
+    Ok(peer)
+}
+
+/// Starts a local websocket server with the handler, and connects a [`Peer`] to it.
+pub(crate) async fn mock_peer<F, Fut>(handler: F) -> anyhow::Result<Peer>
+where
+    F: FnOnce(MockSocket) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let addr = mock_server(0, handler).await?;
+    Ok(connect_mock_peer(addr).await?)
+}
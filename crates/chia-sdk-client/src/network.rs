@@ -1,4 +1,4 @@
-use std::{net::SocketAddr, time::Duration};
+use std::{collections::HashSet, future::Future, net::SocketAddr, time::Duration};
 
 use chia_protocol::Bytes32;
 use chia_sdk_types::{MAINNET_CONSTANTS, TESTNET11_CONSTANTS};
@@ -11,6 +11,8 @@ use crate::ClientError;
 pub struct Network {
     pub default_port: u16,
     pub genesis_challenge: Bytes32,
+    /// Overrides the additional data used for `AGG_SIG_ME` signatures, which is the genesis challenge if unset.
+    pub agg_sig_me: Option<Bytes32>,
     pub dns_introducers: Vec<String>,
 }
 
@@ -19,6 +21,7 @@ impl Network {
         Self {
             default_port: 8444,
             genesis_challenge: MAINNET_CONSTANTS.genesis_challenge,
+            agg_sig_me: None,
             dns_introducers: vec![
                 "dns-introducer.chia.net".to_string(),
                 "chia.ctrlaltdel.ch".to_string(),
@@ -32,48 +35,197 @@ impl Network {
         Self {
             default_port: 58444,
             genesis_challenge: TESTNET11_CONSTANTS.genesis_challenge,
+            agg_sig_me: None,
             dns_introducers: vec!["dns-introducer-testnet11.chia.net".to_string()],
         }
     }
 
+    /// The additional data that is appended to `AGG_SIG_ME` messages, the same as consensus uses.
+    /// This should be used when signing, rather than the genesis challenge directly.
+    pub fn agg_sig_me_additional_data(&self) -> Bytes32 {
+        self.agg_sig_me.unwrap_or(self.genesis_challenge)
+    }
+
+    /// Looks up every DNS introducer concurrently (in batches), ignoring the ones that fail or
+    /// take longer than the timeout. The resulting addresses are deduplicated.
     #[instrument]
     pub async fn lookup_all(&self, timeout: Duration, batch_size: usize) -> Vec<SocketAddr> {
-        let mut result = Vec::new();
-
-        for batch in self.dns_introducers.chunks(batch_size) {
-            let mut futures = FuturesUnordered::new();
-
-            for dns_introducer in batch {
-                futures.push(async move {
-                    match tokio::time::timeout(timeout, self.lookup_host(dns_introducer)).await {
-                        Ok(Ok(addrs)) => addrs,
-                        Ok(Err(error)) => {
-                            warn!("Failed to lookup DNS introducer {dns_introducer}: {error}");
-                            Vec::new()
-                        }
-                        Err(_timeout) => {
-                            warn!("Timeout looking up DNS introducer {dns_introducer}");
-                            Vec::new()
-                        }
-                    }
-                });
+        lookup_concurrently(
+            &self.dns_introducers,
+            timeout,
+            batch_size,
+            |dns_introducer| self.lookup_host(dns_introducer),
+        )
+        .await
+    }
+
+    /// Resolves the IP addresses of a DNS introducer, using the default port for each of them.
+    /// If the introducer is given as `host:port`, that port is used instead.
+    #[instrument]
+    pub async fn lookup_host(&self, dns_introducer: &str) -> Result<Vec<SocketAddr>, ClientError> {
+        info!("Looking up DNS introducer {dns_introducer}");
+        let (host, port) = split_host_port(dns_introducer, self.default_port);
+        Ok(tokio::net::lookup_host((host, port)).await?.collect())
+    }
+}
+
+/// Splits a trailing port off of the host, if there is one. Bare IPv6 addresses are left intact.
+fn split_host_port(dns_introducer: &str, default_port: u16) -> (&str, u16) {
+    if let Some((host, port)) = dns_introducer.rsplit_once(':') {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        if !host.contains(':') || dns_introducer.starts_with('[') {
+            if let Ok(port) = port.parse() {
+                return (host, port);
             }
+        }
+    }
+
+    (dns_introducer, default_port)
+}
+
+async fn lookup_concurrently<'a, F, Fut>(
+    dns_introducers: &'a [String],
+    timeout: Duration,
+    batch_size: usize,
+    lookup: F,
+) -> Vec<SocketAddr>
+where
+    F: Fn(&'a str) -> Fut,
+    Fut: Future<Output = Result<Vec<SocketAddr>, ClientError>>,
+{
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+
+    for batch in dns_introducers.chunks(batch_size.max(1)) {
+        let mut futures = FuturesUnordered::new();
 
-            while let Some(addrs) = futures.next().await {
-                result.extend(addrs);
+        for dns_introducer in batch {
+            let lookup = &lookup;
+
+            futures.push(async move {
+                match tokio::time::timeout(timeout, lookup(dns_introducer)).await {
+                    Ok(Ok(addrs)) => addrs,
+                    Ok(Err(error)) => {
+                        warn!("Failed to lookup DNS introducer {dns_introducer}: {error}");
+                        Vec::new()
+                    }
+                    Err(_timeout) => {
+                        warn!("Timeout looking up DNS introducer {dns_introducer}");
+                        Vec::new()
+                    }
+                }
+            });
+        }
+
+        while let Some(addrs) = futures.next().await {
+            for addr in addrs {
+                if seen.insert(addr) {
+                    result.push(addr);
+                }
             }
         }
+    }
 
-        result
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{IpAddr, Ipv4Addr},
+        time::Instant,
+    };
+
+    use super::*;
+
+    fn addr(last: u8) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, last)), 8444)
     }
 
-    #[instrument]
-    pub async fn lookup_host(&self, dns_introducer: &str) -> Result<Vec<SocketAddr>, ClientError> {
-        info!("Looking up DNS introducer {dns_introducer}");
-        let mut result = Vec::new();
-        for addr in tokio::net::lookup_host(format!("{dns_introducer}:80")).await? {
-            result.push(SocketAddr::new(addr.ip(), self.default_port));
+    async fn mock_lookup(dns_introducer: &str) -> Result<Vec<SocketAddr>, ClientError> {
+        match dns_introducer {
+            "fast" => Ok(vec![addr(1), addr(2), addr(1)]),
+            "also-fast" => Ok(vec![addr(2), addr(3)]),
+            "slow" => {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok(vec![addr(4)])
+            }
+            _ => Err(ClientError::MissingHandshake),
         }
-        Ok(result)
+    }
+
+    #[test]
+    fn test_agg_sig_me_additional_data() {
+        let mainnet = Network::default_mainnet();
+        assert_eq!(
+            mainnet.agg_sig_me_additional_data(),
+            MAINNET_CONSTANTS.agg_sig_me_additional_data
+        );
+
+        let testnet11 = Network::default_testnet11();
+        assert_eq!(
+            testnet11.agg_sig_me_additional_data(),
+            TESTNET11_CONSTANTS.agg_sig_me_additional_data
+        );
+
+        let custom = Network {
+            agg_sig_me: Some(Bytes32::new([1; 32])),
+            ..Network::default_mainnet()
+        };
+        assert_eq!(custom.agg_sig_me_additional_data(), Bytes32::new([1; 32]));
+    }
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(
+            split_host_port("dns-introducer.chia.net", 8444),
+            ("dns-introducer.chia.net", 8444)
+        );
+        assert_eq!(
+            split_host_port("dns-introducer.chia.net:58444", 8444),
+            ("dns-introducer.chia.net", 58444)
+        );
+        assert_eq!(split_host_port("127.0.0.1:1234", 8444), ("127.0.0.1", 1234));
+        assert_eq!(split_host_port("::1", 8444), ("::1", 8444));
+        assert_eq!(split_host_port("[::1]:1234", 8444), ("::1", 1234));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_host_port() -> anyhow::Result<()> {
+        let network = Network::default_mainnet();
+
+        let addrs = network.lookup_host("127.0.0.1").await?;
+        assert_eq!(
+            addrs,
+            vec![SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 8444)]
+        );
+
+        let addrs = network.lookup_host("127.0.0.1:1234").await?;
+        assert_eq!(
+            addrs,
+            vec![SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 1234)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_slow_introducer_does_not_block() {
+        let dns_introducers = ["slow", "fast", "broken", "also-fast"].map(str::to_string);
+
+        let start = Instant::now();
+        let mut addrs = lookup_concurrently(
+            &dns_introducers,
+            Duration::from_millis(200),
+            10,
+            mock_lookup,
+        )
+        .await;
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        addrs.sort();
+        assert_eq!(addrs, vec![addr(1), addr(2), addr(3)]);
     }
 }
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use chia_protocol::Bytes32;
+use serde::Deserialize;
+
+use crate::{ClientError, Network};
+
+#[derive(Debug, Deserialize)]
+struct Config {
+    selected_network: Option<String>,
+    #[serde(default)]
+    network_overrides: NetworkOverrides,
+    #[serde(default)]
+    full_node: FullNodeConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NetworkOverrides {
+    #[serde(default)]
+    constants: HashMap<String, ConstantsOverrides>,
+    #[serde(default)]
+    config: HashMap<String, ConfigOverrides>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct ConstantsOverrides {
+    genesis_challenge: Option<String>,
+    agg_sig_me_additional_data: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigOverrides {
+    default_full_node_port: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FullNodeConfig {
+    dns_servers: Option<Vec<String>>,
+}
+
+impl Network {
+    /// Reads the selected network from a full node's `config.yaml`.
+    /// See [`Network::from_config_str`] for more details.
+    pub fn from_config_yaml(path: impl AsRef<Path>) -> Result<Self, ClientError> {
+        Self::from_config_str(&fs::read_to_string(path)?)
+    }
+
+    /// Parses the network named by `selected_network` (or mainnet, if unset) from the contents of a `config.yaml`.
+    ///
+    /// The genesis challenge and port are read from `network_overrides`, and the introducers from
+    /// `full_node.dns_servers`. Fields that are missing fall back to the defaults for mainnet and testnet11,
+    /// but a custom network must at least specify its genesis challenge.
+    pub fn from_config_str(text: &str) -> Result<Self, ClientError> {
+        let config: Config = serde_yaml_ng::from_str(text)?;
+        let name = config
+            .selected_network
+            .unwrap_or_else(|| "mainnet".to_string());
+
+        let defaults = match name.as_str() {
+            "mainnet" => Some(Self::default_mainnet()),
+            "testnet11" => Some(Self::default_testnet11()),
+            _ => None,
+        };
+
+        let constants = config.network_overrides.constants.get(&name);
+        let overrides = config.network_overrides.config.get(&name);
+
+        let genesis_challenge = match constants.and_then(|c| c.genesis_challenge.as_deref()) {
+            Some(hex) => parse_bytes32("GENESIS_CHALLENGE", hex)?,
+            None => defaults
+                .as_ref()
+                .map(|network| network.genesis_challenge)
+                .ok_or_else(|| ClientError::MissingGenesisChallenge(name.clone()))?,
+        };
+
+        let agg_sig_me = match constants.and_then(|c| c.agg_sig_me_additional_data.as_deref()) {
+            Some(hex) => Some(parse_bytes32("AGG_SIG_ME_ADDITIONAL_DATA", hex)?),
+            None => defaults.as_ref().and_then(|network| network.agg_sig_me),
+        };
+
+        let default_port = overrides
+            .and_then(|o| o.default_full_node_port)
+            .or_else(|| defaults.as_ref().map(|network| network.default_port))
+            .unwrap_or(8444);
+
+        let dns_introducers = config
+            .full_node
+            .dns_servers
+            .or_else(|| defaults.map(|network| network.dns_introducers))
+            .unwrap_or_default();
+
+        Ok(Self {
+            default_port,
+            genesis_challenge,
+            agg_sig_me,
+            dns_introducers,
+        })
+    }
+}
+
+fn parse_bytes32(field: &str, text: &str) -> Result<Bytes32, ClientError> {
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    let bytes: [u8; 32] = hex::decode(text)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ClientError::InvalidConfig(field.to_string()))?;
+    Ok(bytes.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_sdk_types::MAINNET_CONSTANTS;
+
+    use super::*;
+
+    const CUSTOM_CONFIG: &str = r#"
+selected_network: &selected_network "customnet"
+network_overrides: &network_overrides
+  constants:
+    mainnet:
+      GENESIS_CHALLENGE: ccd5bb71183532bff220ba46c268991a3ff07eb358e8255a65c30a2dce0e5fbb
+    customnet:
+      MIN_PLOT_SIZE: 18
+      GENESIS_CHALLENGE: 0707070707070707070707070707070707070707070707070707070707070707
+  config:
+    mainnet:
+      address_prefix: "xch"
+      default_full_node_port: 8444
+    customnet:
+      address_prefix: "txch"
+      default_full_node_port: 9444
+full_node:
+  selected_network: *selected_network
+  port: 9444
+  dns_servers:
+    - "introducer.example.com"
+"#;
+
+    #[test]
+    fn test_custom_network_config() -> anyhow::Result<()> {
+        let network = Network::from_config_str(CUSTOM_CONFIG)?;
+
+        assert_eq!(network.default_port, 9444);
+        assert_eq!(network.genesis_challenge, Bytes32::new([7; 32]));
+        assert_eq!(network.agg_sig_me, None);
+        assert_eq!(network.dns_introducers, ["introducer.example.com"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() -> anyhow::Result<()> {
+        let network = Network::from_config_str("full_node:\n  port: 8444\n")?;
+        let mainnet = Network::default_mainnet();
+
+        assert_eq!(network.default_port, mainnet.default_port);
+        assert_eq!(
+            network.genesis_challenge,
+            MAINNET_CONSTANTS.genesis_challenge
+        );
+        assert_eq!(network.dns_introducers, mainnet.dns_introducers);
+
+        let network = Network::from_config_str("selected_network: testnet11\n")?;
+        assert_eq!(network.default_port, 58444);
+
+        // A custom network can't be used without knowing its genesis challenge.
+        assert!(matches!(
+            Network::from_config_str("selected_network: customnet\n"),
+            Err(ClientError::MissingGenesisChallenge(name)) if name == "customnet"
+        ));
+
+        Ok(())
+    }
+}
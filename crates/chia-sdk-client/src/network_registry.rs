@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use crate::Network;
+
+/// A set of [`Network`] configurations which can be looked up by name, such as `testnet11`.
+/// The default registry contains `mainnet` and `testnet11`.
+#[derive(Debug, Clone)]
+pub struct NetworkRegistry {
+    networks: HashMap<String, Network>,
+}
+
+impl Default for NetworkRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("mainnet", Network::default_mainnet());
+        registry.register("testnet11", Network::default_testnet11());
+        registry
+    }
+}
+
+impl NetworkRegistry {
+    /// Creates a registry without the built-in networks.
+    pub fn empty() -> Self {
+        Self {
+            networks: HashMap::new(),
+        }
+    }
+
+    /// Registers a network under a name, returning the network it replaced if there was one.
+    pub fn register(&mut self, name: impl Into<String>, network: Network) -> Option<Network> {
+        self.networks.insert(name.into(), network)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Network> {
+        self.networks.get(name).cloned()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.networks.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Bytes32;
+
+    use super::*;
+
+    #[test]
+    fn test_builtin_networks() {
+        let registry = NetworkRegistry::default();
+
+        let mainnet = registry.get("mainnet").expect("missing mainnet");
+        assert_eq!(mainnet.default_port, 8444);
+
+        let testnet11 = registry.get("testnet11").expect("missing testnet11");
+        assert_eq!(testnet11.default_port, 58444);
+
+        assert!(registry.get("simulator0").is_none());
+    }
+
+    #[test]
+    fn test_custom_network() {
+        let mut registry = NetworkRegistry::default();
+
+        let custom = Network {
+            default_port: 1234,
+            genesis_challenge: Bytes32::new([7; 32]),
+            agg_sig_me: None,
+            dns_introducers: vec!["introducer.example.com".to_string()],
+        };
+
+        assert!(registry.register("custom", custom.clone()).is_none());
+
+        let network = registry.get("custom").expect("missing custom network");
+        assert_eq!(network.default_port, custom.default_port);
+        assert_eq!(network.genesis_challenge, custom.genesis_challenge);
+        assert_eq!(network.dns_introducers, custom.dns_introducers);
+
+        let mut names: Vec<&str> = registry.names().collect();
+        names.sort_unstable();
+        assert_eq!(names, ["custom", "mainnet", "testnet11"]);
+    }
+}
@@ -1,14 +1,15 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use chia_protocol::{
-    Bytes32, ChiaProtocolMessage, CoinStateFilters, Message, PuzzleSolutionResponse,
-    RegisterForCoinUpdates, RegisterForPhUpdates, RejectCoinState, RejectPuzzleSolution,
-    RejectPuzzleState, RequestChildren, RequestCoinState, RequestPeers, RequestPuzzleSolution,
+    Bytes32, ChiaProtocolMessage, CoinState, CoinStateFilters, HeaderBlock, Message, NewPeakWallet,
+    ProtocolMessageTypes, PuzzleSolutionResponse, RegisterForCoinUpdates, RegisterForPhUpdates,
+    RejectCoinState, RejectHeaderRequest, RejectPuzzleSolution, RejectPuzzleState,
+    RequestBlockHeader, RequestChildren, RequestCoinState, RequestPeers, RequestPuzzleSolution,
     RequestPuzzleState, RequestRemoveCoinSubscriptions, RequestRemovePuzzleSubscriptions,
-    RequestTransaction, RespondChildren, RespondCoinState, RespondPeers, RespondPuzzleSolution,
-    RespondPuzzleState, RespondRemoveCoinSubscriptions, RespondRemovePuzzleSubscriptions,
-    RespondToCoinUpdates, RespondToPhUpdates, RespondTransaction, SendTransaction, SpendBundle,
-    TransactionAck,
+    RequestTransaction, RespondBlockHeader, RespondChildren, RespondCoinState, RespondPeers,
+    RespondPuzzleSolution, RespondPuzzleState, RespondRemoveCoinSubscriptions,
+    RespondRemovePuzzleSubscriptions, RespondToCoinUpdates, RespondToPhUpdates, RespondTransaction,
+    SendTransaction, SpendBundle, TransactionAck,
 };
 use chia_traits::Streamable;
 use futures_util::{
@@ -38,12 +39,22 @@ pub struct Peer(Arc<PeerInner>);
 
 #[derive(Debug)]
 struct PeerInner {
-    sink: Mutex<Sink>,
+    sink: Arc<Mutex<Sink>>,
     inbound_handle: JoinHandle<()>,
     requests: Arc<RequestMap>,
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
     socket_addr: SocketAddr,
 }
 
+/// The information about a block returned by [`Peer::get_block_record_by_height`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRecord {
+    pub height: u32,
+    pub header_hash: Bytes32,
+    /// The timestamp of the block in seconds, which is only set for transaction blocks.
+    pub timestamp: Option<u64>,
+}
+
 impl Peer {
     /// Connects to a peer using its IP address and port.
     #[cfg(any(feature = "native-tls", feature = "rustls"))]
@@ -92,16 +103,22 @@ impl Peer {
         let requests = Arc::new(RequestMap::new());
         let requests_clone = requests.clone();
 
+        let peak = Arc::new(Mutex::new(None));
+        let peak_clone = peak.clone();
+
         let inbound_handle = tokio::spawn(async move {
-            if let Err(error) = handle_inbound_messages(stream, sender, requests_clone).await {
+            if let Err(error) =
+                handle_inbound_messages(stream, sender, requests_clone, peak_clone).await
+            {
                 debug!("Error handling message: {error}");
             }
         });
 
         let peer = Self(Arc::new(PeerInner {
-            sink: Mutex::new(sink),
+            sink: Arc::new(Mutex::new(sink)),
             inbound_handle,
             requests,
+            peak,
             socket_addr,
         }));
 
@@ -113,6 +130,31 @@ impl Peer {
         self.0.socket_addr
     }
 
+    /// Whether the connection is still open, based on whether inbound messages are being handled.
+    pub fn is_connected(&self) -> bool {
+        !self.0.inbound_handle.is_finished()
+    }
+
+    /// Sends a websocket ping frame, which fails if the connection has been closed.
+    pub async fn ping(&self) -> Result<(), ClientError> {
+        self.0
+            .sink
+            .lock()
+            .await
+            .send(tungstenite::Message::Ping(Vec::new()))
+            .await?;
+        Ok(())
+    }
+
+    /// Closes the connection by sending a websocket close frame.
+    /// The peer is expected to respond with its own close frame, after which inbound messages stop being handled.
+    ///
+    /// Dropping the last clone of the peer also attempts to close it, but can't report whether that succeeded.
+    pub async fn close(&self) -> Result<(), ClientError> {
+        self.0.sink.lock().await.close().await?;
+        Ok(())
+    }
+
     pub async fn send_transaction(
         &self,
         spend_bundle: SpendBundle,
@@ -121,6 +163,18 @@ impl Peer {
             .await
     }
 
+    /// Sends a transaction, but gives up with [`ClientError::TimedOut`] if the peer doesn't acknowledge it in time.
+    /// The transaction may still have been received by the peer when this happens.
+    pub async fn send_transaction_with_timeout(
+        &self,
+        spend_bundle: SpendBundle,
+        timeout: Duration,
+    ) -> Result<TransactionAck, ClientError> {
+        tokio::time::timeout(timeout, self.send_transaction(spend_bundle))
+            .await
+            .map_err(|_| ClientError::TimedOut)?
+    }
+
     pub async fn request_puzzle_state(
         &self,
         puzzle_hashes: Vec<Bytes32>,
@@ -221,6 +275,122 @@ impl Peer {
         self.request_infallible(RequestPeers::new()).await
     }
 
+    /// Requests the current state of a coin, or [`None`] if the peer doesn't know about it.
+    ///
+    /// The genesis challenge of the network is required to request the coin state from the peer.
+    pub async fn get_coin_record_by_name(
+        &self,
+        coin_id: Bytes32,
+        genesis_challenge: Bytes32,
+    ) -> Result<Option<CoinState>, ClientError> {
+        let response = self
+            .request_coin_state(vec![coin_id], None, genesis_challenge, false)
+            .await?
+            .map_err(|rejection| ClientError::CoinStateRejected(rejection.reason))?;
+
+        Ok(response
+            .coin_states
+            .into_iter()
+            .find(|coin_state| coin_state.coin.coin_id() == coin_id))
+    }
+
+    /// Requests the state of every coin with the given puzzle hash, excluding coins which are only hinted.
+    /// The response is paginated by the peer, so multiple requests may be made until every page has been received.
+    ///
+    /// The genesis challenge of the network is required to request the puzzle state from the peer.
+    pub async fn get_coin_records_by_puzzle_hash(
+        &self,
+        puzzle_hash: Bytes32,
+        include_spent: bool,
+        genesis_challenge: Bytes32,
+    ) -> Result<Vec<CoinState>, ClientError> {
+        let mut coin_states = Vec::new();
+        let mut previous_height = None;
+        let mut header_hash = genesis_challenge;
+
+        loop {
+            let response = self
+                .request_puzzle_state(
+                    vec![puzzle_hash],
+                    previous_height,
+                    header_hash,
+                    CoinStateFilters::new(include_spent, true, false, 0),
+                    false,
+                )
+                .await?
+                .map_err(|rejection| ClientError::CoinStateRejected(rejection.reason))?;
+
+            coin_states.extend(response.coin_states);
+
+            if response.is_finished {
+                return Ok(coin_states);
+            }
+
+            previous_height = Some(response.height);
+            header_hash = response.header_hash;
+        }
+    }
+
+    /// Requests the state of every coin created by any of the given parent coins.
+    pub async fn get_coin_records_by_parent_ids(
+        &self,
+        parent_ids: Vec<Bytes32>,
+    ) -> Result<Vec<CoinState>, ClientError> {
+        let mut coin_states = Vec::new();
+
+        for parent_id in parent_ids {
+            coin_states.extend(self.request_children(parent_id).await?.coin_states);
+        }
+
+        Ok(coin_states)
+    }
+
+    /// Returns the latest peak announced by the peer.
+    ///
+    /// Full nodes only announce their peak once they are synced, so [`ClientError::NotSynced`]
+    /// is returned if no peak has been received yet.
+    pub async fn get_blockchain_state(&self) -> Result<NewPeakWallet, ClientError> {
+        self.0
+            .peak
+            .lock()
+            .await
+            .clone()
+            .ok_or(ClientError::NotSynced)
+    }
+
+    pub async fn request_block_header(
+        &self,
+        height: u32,
+    ) -> Result<Response<HeaderBlock, RejectHeaderRequest>, ClientError> {
+        match self
+            .request_fallible::<RespondBlockHeader, _, _>(RequestBlockHeader::new(height))
+            .await?
+        {
+            Ok(response) => Ok(Ok(response.header_block)),
+            Err(rejection) => Ok(Err(rejection)),
+        }
+    }
+
+    /// Requests the header of the block at the given height, and returns its header hash and timestamp.
+    /// If the peer hasn't synced up to the height yet, [`ClientError::MissingBlock`] is returned.
+    pub async fn get_block_record_by_height(
+        &self,
+        height: u32,
+    ) -> Result<BlockRecord, ClientError> {
+        let header_block = self
+            .request_block_header(height)
+            .await?
+            .map_err(|rejection| ClientError::MissingBlock(rejection.height))?;
+
+        Ok(BlockRecord {
+            height: header_block.height(),
+            header_hash: header_block.header_hash(),
+            timestamp: header_block
+                .foliage_transaction_block
+                .map(|block| block.timestamp),
+        })
+    }
+
     /// Sends a message to the peer, but does not expect any response.
     pub async fn send<T>(&self, body: T) -> Result<(), ClientError>
     where
@@ -295,6 +465,14 @@ impl Peer {
 impl Drop for PeerInner {
     fn drop(&mut self) {
         self.inbound_handle.abort();
+
+        // Closing is async, so it can only be done if there is still a runtime to spawn it on.
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let sink = self.sink.clone();
+            runtime.spawn(async move {
+                sink.lock().await.close().await.ok();
+            });
+        }
     }
 }
 
@@ -302,6 +480,7 @@ async fn handle_inbound_messages(
     mut stream: Stream,
     sender: mpsc::Sender<Message>,
     requests: Arc<RequestMap>,
+    peak: Arc<Mutex<Option<NewPeakWallet>>>,
 ) -> Result<(), ClientError> {
     use tungstenite::Message::{Binary, Close, Frame, Ping, Pong, Text};
 
@@ -319,6 +498,10 @@ async fn handle_inbound_messages(
                 let message = Message::from_bytes(&binary)?;
 
                 let Some(id) = message.id else {
+                    if message.msg_type == ProtocolMessageTypes::NewPeakWallet {
+                        *peak.lock().await = Some(NewPeakWallet::from_bytes(&message.data)?);
+                    }
+
                     sender.send(message).await.ok();
                     continue;
                 };
@@ -337,3 +520,144 @@ async fn handle_inbound_messages(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::Signature;
+    use futures_util::{SinkExt, StreamExt};
+
+    use crate::mock_peer::mock_peer;
+
+    use super::*;
+
+    /// Connects to a mock peer, which announces a peak if given one,
+    /// and rejects every block header request.
+    async fn header_peer(peak: Option<NewPeakWallet>) -> anyhow::Result<Peer> {
+        mock_peer(|mut ws| async move {
+            if let Some(peak) = peak {
+                let message = Message::new(
+                    NewPeakWallet::msg_type(),
+                    None,
+                    peak.to_bytes().unwrap().into(),
+                );
+                ws.send(tungstenite::Message::Binary(message.to_bytes().unwrap()))
+                    .await
+                    .unwrap();
+            }
+
+            while let Some(Ok(tungstenite::Message::Binary(bytes))) = ws.next().await {
+                let message = Message::from_bytes(&bytes).unwrap();
+                assert_eq!(message.msg_type, RequestBlockHeader::msg_type());
+                let request = RequestBlockHeader::from_bytes(&message.data).unwrap();
+
+                let response = Message {
+                    msg_type: RejectHeaderRequest::msg_type(),
+                    id: message.id,
+                    data: RejectHeaderRequest::new(request.height)
+                        .to_bytes()
+                        .unwrap()
+                        .into(),
+                };
+
+                ws.send(tungstenite::Message::Binary(response.to_bytes().unwrap()))
+                    .await
+                    .unwrap();
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_blockchain_state() -> anyhow::Result<()> {
+        let peak = NewPeakWallet::new(Bytes32::new([1; 32]), 42, 1000, 41);
+        let peer = header_peer(Some(peak.clone())).await?;
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while peer.get_blockchain_state().await.is_err() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await?;
+
+        assert_eq!(peer.get_blockchain_state().await?, peak);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_not_synced() -> anyhow::Result<()> {
+        let peer = header_peer(None).await?;
+
+        assert!(matches!(
+            peer.get_blockchain_state().await,
+            Err(ClientError::NotSynced)
+        ));
+        assert!(matches!(
+            peer.get_block_record_by_height(100).await,
+            Err(ClientError::MissingBlock(100))
+        ));
+
+        Ok(())
+    }
+
+    /// Connects to a mock peer, which reports whether a close frame was received.
+    async fn close_listener() -> anyhow::Result<(Peer, oneshot::Receiver<bool>)> {
+        let (sender, receiver) = oneshot::channel();
+
+        let peer = mock_peer(|mut ws| async move {
+            let mut closed = false;
+
+            while let Some(Ok(message)) = ws.next().await {
+                if message.is_close() {
+                    closed = true;
+                    break;
+                }
+            }
+
+            sender.send(closed).ok();
+        })
+        .await?;
+
+        Ok((peer, receiver))
+    }
+
+    #[tokio::test]
+    async fn test_close() -> anyhow::Result<()> {
+        let (peer, closed) = close_listener().await?;
+
+        peer.close().await?;
+        assert!(tokio::time::timeout(Duration::from_secs(5), closed).await??);
+        assert!(peer.ping().await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close_on_drop() -> anyhow::Result<()> {
+        let (peer, closed) = close_listener().await?;
+
+        drop(peer);
+        assert!(tokio::time::timeout(Duration::from_secs(5), closed).await??);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_timeout() -> anyhow::Result<()> {
+        // The peer reads every message, but never acknowledges the transaction.
+        let peer =
+            mock_peer(|mut ws| async move { while let Some(Ok(_message)) = ws.next().await {} })
+                .await?;
+
+        let result = peer
+            .send_transaction_with_timeout(
+                SpendBundle::new(Vec::new(), Signature::default()),
+                Duration::from_millis(100),
+            )
+            .await;
+        assert!(matches!(result, Err(ClientError::TimedOut)));
+        assert!(peer.is_connected());
+
+        Ok(())
+    }
+}
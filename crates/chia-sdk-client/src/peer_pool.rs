@@ -0,0 +1,172 @@
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::sync::Mutex;
+
+use crate::Peer;
+
+/// A shared set of peers, which are handed out round-robin.
+/// Peers whose connection has closed or which fail a ping are removed in the background.
+#[derive(Debug, Clone)]
+pub struct PeerPool(Arc<PeerPoolInner>);
+
+#[derive(Debug)]
+struct PeerPoolInner {
+    peers: Mutex<Vec<Peer>>,
+    next: AtomicUsize,
+}
+
+impl PeerPool {
+    /// Creates an empty pool, and starts checking the health of its peers at the given interval.
+    /// The background task stops once every clone of the pool has been dropped.
+    ///
+    /// This must be called from within a Tokio runtime.
+    pub fn new(check_interval: Duration) -> Self {
+        let pool = Self(Arc::new(PeerPoolInner {
+            peers: Mutex::new(Vec::new()),
+            next: AtomicUsize::new(0),
+        }));
+
+        let inner = Arc::downgrade(&pool.0);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(check_interval);
+
+            loop {
+                interval.tick().await;
+
+                let Some(inner) = inner.upgrade() else {
+                    break;
+                };
+
+                Self(inner).check_health().await;
+            }
+        });
+
+        pool
+    }
+
+    /// Adds a peer to the pool, replacing any existing peer with the same socket address.
+    pub async fn add_peer(&self, peer: Peer) {
+        let mut peers = self.0.peers.lock().await;
+        peers.retain(|existing| existing.socket_addr() != peer.socket_addr());
+        peers.push(peer);
+    }
+
+    /// Removes the peer with the given socket address from the pool, if it's present.
+    pub async fn remove_peer(&self, socket_addr: SocketAddr) -> Option<Peer> {
+        let mut peers = self.0.peers.lock().await;
+        let index = peers
+            .iter()
+            .position(|peer| peer.socket_addr() == socket_addr)?;
+        Some(peers.remove(index))
+    }
+
+    pub async fn len(&self) -> usize {
+        self.0.peers.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.0.peers.lock().await.is_empty()
+    }
+
+    /// Returns the next connected peer in round-robin order, or `None` if there are none.
+    pub async fn acquire(&self) -> Option<Peer> {
+        let peers = self.0.peers.lock().await;
+        let start = self.0.next.fetch_add(1, Ordering::Relaxed);
+
+        (0..peers.len())
+            .map(|offset| &peers[(start + offset) % peers.len()])
+            .find(|peer| peer.is_connected())
+            .cloned()
+    }
+
+    /// Pings every peer, and removes the ones which are disconnected or fail to respond.
+    pub async fn check_health(&self) {
+        let peers = self.0.peers.lock().await.clone();
+
+        let mut unhealthy = Vec::new();
+
+        for peer in peers {
+            if !peer.is_connected() || peer.ping().await.is_err() {
+                unhealthy.push(peer.socket_addr());
+            }
+        }
+
+        self.0
+            .peers
+            .lock()
+            .await
+            .retain(|peer| !unhealthy.contains(&peer.socket_addr()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use crate::mock_peer::mock_peer;
+
+    use super::*;
+
+    /// Connects to a mock peer, which closes the connection when signaled.
+    async fn closable_peer() -> anyhow::Result<(Peer, oneshot::Sender<()>)> {
+        let (close, closed) = oneshot::channel::<()>();
+
+        let peer = mock_peer(|mut ws| async move {
+            closed.await.ok();
+            ws.close(None).await.ok();
+        })
+        .await?;
+
+        Ok((peer, close))
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_peer_evicted() -> anyhow::Result<()> {
+        let pool = PeerPool::new(Duration::from_secs(3600));
+
+        let (healthy, _keep_open) = closable_peer().await?;
+        let (unhealthy, close) = closable_peer().await?;
+
+        pool.add_peer(healthy.clone()).await;
+        pool.add_peer(unhealthy.clone()).await;
+        assert_eq!(pool.len().await, 2);
+
+        let mut seen = Vec::new();
+        for _ in 0..2 {
+            seen.push(pool.acquire().await.expect("no peer").socket_addr());
+        }
+        assert!(seen.contains(&healthy.socket_addr()));
+        assert!(seen.contains(&unhealthy.socket_addr()));
+
+        close.send(()).ok();
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while unhealthy.is_connected() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await?;
+
+        for _ in 0..4 {
+            let peer = pool.acquire().await.expect("no peer");
+            assert_eq!(peer.socket_addr(), healthy.socket_addr());
+        }
+
+        pool.check_health().await;
+        assert_eq!(pool.len().await, 1);
+
+        assert!(pool.remove_peer(healthy.socket_addr()).await.is_some());
+        assert!(pool.is_empty().await);
+        assert!(pool.acquire().await.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::{future::Future, time::Duration};
+
+use tracing::warn;
+
+use crate::ClientError;
+
+/// Determines how many times an operation is attempted, and how long to wait between attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay before the first retry, which is doubled after every subsequent attempt.
+    pub base_delay: Duration,
+    /// The upper bound for the delay between attempts, before jitter is applied.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The delay after a given failed attempt (starting at 1), with up to 50% jitter subtracted.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        delay.mul_f64(1.0 - fastrand::f64() * 0.5)
+    }
+}
+
+/// Runs an operation until it succeeds, fails with an error which isn't transient,
+/// or runs out of attempts according to the [`RetryPolicy`].
+pub async fn retry_with_backoff<T, F, Fut>(policy: RetryPolicy, mut f: F) -> Result<T, ClientError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ClientError>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(error) if error.is_transient() && attempt < policy.max_attempts => {
+                let delay = policy.delay(attempt);
+                warn!("Attempt {attempt} failed with {error}, retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::mock_peer::{connect_mock_peer, mock_server};
+
+    use super::*;
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(5),
+            max_delay: Duration::from_millis(20),
+        }
+    }
+
+    #[test]
+    fn test_delay_bounds() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+
+        for (attempt, expected) in [
+            (1, 100),
+            (2, 200),
+            (3, 400),
+            (4, 800),
+            (5, 1000),
+            (40, 1000),
+        ] {
+            let delay = policy.delay(attempt);
+            let expected = Duration::from_millis(expected);
+            assert!(delay <= expected);
+            assert!(delay >= expected / 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_refused_connections() -> anyhow::Result<()> {
+        let addr = mock_server(2, |_ws| std::future::pending()).await?;
+
+        let attempts = AtomicU32::new(0);
+
+        let peer = retry_with_backoff(fast_policy(5), || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            connect_mock_peer(addr)
+        })
+        .await?;
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(peer.socket_addr(), addr);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_succeeds_after_failure() -> anyhow::Result<()> {
+        let attempts = AtomicU32::new(0);
+
+        let value = retry_with_backoff(fast_policy(5), || {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt == 0 {
+                    Err(ClientError::NotSynced)
+                } else {
+                    Ok(42)
+                }
+            }
+        })
+        .await?;
+
+        assert_eq!(value, 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), ClientError> = retry_with_backoff(fast_policy(3), || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(ClientError::MissingHandshake) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(ClientError::MissingHandshake)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fatal_error_not_retried() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), ClientError> = retry_with_backoff(fast_policy(5), || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(ClientError::BannedPeer) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(ClientError::BannedPeer)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "rustls")]
+    #[tokio::test]
+    async fn test_tls_error_not_retried() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), ClientError> = retry_with_backoff(fast_policy(5), || {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async {
+                Err(ClientError::WebSocket(tungstenite::Error::Tls(
+                    rustls::Error::InvalidCertificate(rustls::CertificateError::UnknownIssuer)
+                        .into(),
+                )))
+            }
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ClientError::WebSocket(tungstenite::Error::Tls(..)))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        assert!(!ClientError::Rustls(rustls::Error::InvalidCertificate(
+            rustls::CertificateError::UnknownIssuer
+        ))
+        .is_transient());
+    }
+}
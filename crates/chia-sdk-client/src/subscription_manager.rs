@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+
+use chia_protocol::{Bytes32, ChiaProtocolMessage, CoinState, CoinStateUpdate, Message};
+use chia_traits::Streamable;
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::warn;
+
+use crate::{ClientError, Peer};
+
+/// Keeps track of the puzzle hashes and coin ids subscribed to on a peer, so that
+/// they can be subscribed to again when the connection is replaced.
+///
+/// Coin state updates for the subscriptions are forwarded to the receiver returned by [`SubscriptionManager::new`],
+/// and continue to be after reconnecting.
+#[derive(Debug)]
+pub struct SubscriptionManager {
+    peer: Peer,
+    puzzle_hashes: HashSet<Bytes32>,
+    coin_ids: HashSet<Bytes32>,
+    sender: mpsc::Sender<CoinStateUpdate>,
+    forward_task: JoinHandle<()>,
+}
+
+impl SubscriptionManager {
+    /// Creates a manager for the peer, using the receiver returned when it was connected.
+    pub fn new(
+        peer: Peer,
+        receiver: mpsc::Receiver<Message>,
+    ) -> (Self, mpsc::Receiver<CoinStateUpdate>) {
+        let (sender, updates) = mpsc::channel(32);
+        let forward_task = tokio::spawn(forward_updates(receiver, sender.clone()));
+
+        let manager = Self {
+            peer,
+            puzzle_hashes: HashSet::new(),
+            coin_ids: HashSet::new(),
+            sender,
+            forward_task,
+        };
+
+        (manager, updates)
+    }
+
+    pub fn peer(&self) -> &Peer {
+        &self.peer
+    }
+
+    pub fn puzzle_hashes(&self) -> &HashSet<Bytes32> {
+        &self.puzzle_hashes
+    }
+
+    pub fn coin_ids(&self) -> &HashSet<Bytes32> {
+        &self.coin_ids
+    }
+
+    /// Subscribes to the puzzle hashes, and returns their current coin states.
+    pub async fn subscribe_puzzle_hashes(
+        &mut self,
+        puzzle_hashes: Vec<Bytes32>,
+        min_height: u32,
+    ) -> Result<Vec<CoinState>, ClientError> {
+        let response = self
+            .peer
+            .register_for_ph_updates(puzzle_hashes.clone(), min_height)
+            .await?;
+        self.puzzle_hashes.extend(puzzle_hashes);
+        Ok(response.coin_states)
+    }
+
+    /// Subscribes to the coin ids, and returns their current coin states.
+    pub async fn subscribe_coin_ids(
+        &mut self,
+        coin_ids: Vec<Bytes32>,
+        min_height: u32,
+    ) -> Result<Vec<CoinState>, ClientError> {
+        let response = self
+            .peer
+            .register_for_coin_updates(coin_ids.clone(), min_height)
+            .await?;
+        self.coin_ids.extend(coin_ids);
+        Ok(response.coin_states)
+    }
+
+    /// Replaces the peer with a new connection, and subscribes to everything again.
+    /// The coin states returned include anything which changed since `min_height`,
+    /// which may include updates that were missed while disconnected.
+    pub async fn reconnect(
+        &mut self,
+        peer: Peer,
+        receiver: mpsc::Receiver<Message>,
+        min_height: u32,
+    ) -> Result<Vec<CoinState>, ClientError> {
+        self.forward_task.abort();
+        self.forward_task = tokio::spawn(forward_updates(receiver, self.sender.clone()));
+        self.peer = peer;
+
+        let mut coin_states = Vec::new();
+
+        if !self.puzzle_hashes.is_empty() {
+            let puzzle_hashes = self.puzzle_hashes.iter().copied().collect();
+            let response = self
+                .peer
+                .register_for_ph_updates(puzzle_hashes, min_height)
+                .await?;
+            coin_states.extend(response.coin_states);
+        }
+
+        if !self.coin_ids.is_empty() {
+            let coin_ids = self.coin_ids.iter().copied().collect();
+            let response = self
+                .peer
+                .register_for_coin_updates(coin_ids, min_height)
+                .await?;
+            coin_states.extend(response.coin_states);
+        }
+
+        Ok(coin_states)
+    }
+}
+
+impl Drop for SubscriptionManager {
+    fn drop(&mut self) {
+        self.forward_task.abort();
+    }
+}
+
+async fn forward_updates(
+    mut receiver: mpsc::Receiver<Message>,
+    sender: mpsc::Sender<CoinStateUpdate>,
+) {
+    while let Some(message) = receiver.recv().await {
+        if message.msg_type != CoinStateUpdate::msg_type() {
+            continue;
+        }
+
+        let update = match CoinStateUpdate::from_bytes(&message.data) {
+            Ok(update) => update,
+            Err(error) => {
+                warn!("Failed to parse coin state update: {error}");
+                continue;
+            }
+        };
+
+        if sender.send(update).await.is_err() {
+            break;
+        }
+    }
+}
@@ -41,64 +41,32 @@ pub fn create_native_tls_connector(cert: &ChiaCertificate) -> Result<Connector,
 /// Creates a rustls connector from a certificate.
 #[cfg(feature = "rustls")]
 pub fn create_rustls_connector(cert: &ChiaCertificate) -> Result<Connector, ClientError> {
+    rustls_connector(cert, None)
+}
+
+/// Creates a rustls connector from a certificate, which only accepts a peer whose certificate
+/// has the given SHA-256 fingerprint. Self-signed peer certificates are still allowed.
+#[cfg(feature = "rustls")]
+pub fn create_pinned_rustls_connector(
+    cert: &ChiaCertificate,
+    expected_peer_cert_sha256: [u8; 32],
+) -> Result<Connector, ClientError> {
+    rustls_connector(cert, Some(expected_peer_cert_sha256))
+}
+
+#[cfg(feature = "rustls")]
+fn rustls_connector(
+    cert: &ChiaCertificate,
+    pinned_fingerprint: Option<[u8; 32]>,
+) -> Result<Connector, ClientError> {
     use std::sync::Arc;
 
     use chia_ssl::CHIA_CA_CRT;
     use rustls::{
-        client::danger::HandshakeSignatureValid,
-        crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
-        pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime},
-        ClientConfig, DigitallySignedStruct, RootCertStore,
+        pki_types::{CertificateDer, PrivateKeyDer},
+        ClientConfig, RootCertStore,
     };
 
-    #[derive(Debug)]
-    struct NoCertificateVerification(CryptoProvider);
-
-    impl rustls::client::danger::ServerCertVerifier for NoCertificateVerification {
-        fn verify_server_cert(
-            &self,
-            _end_entity: &CertificateDer<'_>,
-            _intermediates: &[CertificateDer<'_>],
-            _server_name: &ServerName<'_>,
-            _ocsp: &[u8],
-            _now: UnixTime,
-        ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
-            Ok(rustls::client::danger::ServerCertVerified::assertion())
-        }
-
-        fn verify_tls12_signature(
-            &self,
-            message: &[u8],
-            cert: &CertificateDer<'_>,
-            dss: &DigitallySignedStruct,
-        ) -> Result<HandshakeSignatureValid, rustls::Error> {
-            verify_tls12_signature(
-                message,
-                cert,
-                dss,
-                &self.0.signature_verification_algorithms,
-            )
-        }
-
-        fn verify_tls13_signature(
-            &self,
-            message: &[u8],
-            cert: &CertificateDer<'_>,
-            dss: &DigitallySignedStruct,
-        ) -> Result<HandshakeSignatureValid, rustls::Error> {
-            verify_tls13_signature(
-                message,
-                cert,
-                dss,
-                &self.0.signature_verification_algorithms,
-            )
-        }
-
-        fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-            self.0.signature_verification_algorithms.supported_schemes()
-        }
-    }
-
     let mut root_cert_store = RootCertStore::empty();
 
     let ca: Vec<CertificateDer<'_>> =
@@ -119,9 +87,129 @@ pub fn create_rustls_connector(cert: &ChiaCertificate) -> Result<Connector, Clie
 
     config
         .dangerous()
-        .set_certificate_verifier(Arc::new(NoCertificateVerification(
-            rustls::crypto::aws_lc_rs::default_provider(),
-        )));
+        .set_certificate_verifier(Arc::new(PeerCertificateVerification {
+            provider: rustls::crypto::aws_lc_rs::default_provider(),
+            pinned_fingerprint,
+        }));
 
     Ok(Connector::Rustls(Arc::new(config)))
 }
+
+/// Accepts any peer certificate (since they are self-signed), unless a fingerprint is pinned.
+#[cfg(feature = "rustls")]
+#[derive(Debug)]
+struct PeerCertificateVerification {
+    provider: rustls::crypto::CryptoProvider,
+    pinned_fingerprint: Option<[u8; 32]>,
+}
+
+#[cfg(feature = "rustls")]
+impl rustls::client::danger::ServerCertVerifier for PeerCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        use clvmr::sha2::Sha256;
+
+        if let Some(pinned_fingerprint) = self.pinned_fingerprint {
+            let mut hasher = Sha256::new();
+            hasher.update(end_entity.as_ref());
+            let fingerprint: [u8; 32] = hasher.finalize();
+
+            if fingerprint != pinned_fingerprint {
+                return Err(rustls::Error::InvalidCertificate(
+                    rustls::CertificateError::ApplicationVerificationFailure,
+                ));
+            }
+        }
+
+        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(all(test, feature = "rustls"))]
+mod tests {
+    use clvmr::sha2::Sha256;
+    use rustls::{
+        client::danger::ServerCertVerifier,
+        pki_types::{CertificateDer, ServerName, UnixTime},
+    };
+
+    use super::*;
+
+    fn verify(pinned_fingerprint: Option<[u8; 32]>, cert: &CertificateDer<'_>) -> bool {
+        let verifier = PeerCertificateVerification {
+            provider: rustls::crypto::aws_lc_rs::default_provider(),
+            pinned_fingerprint,
+        };
+
+        verifier
+            .verify_server_cert(
+                cert,
+                &[],
+                &ServerName::try_from("chia.net").unwrap(),
+                &[],
+                UnixTime::now(),
+            )
+            .is_ok()
+    }
+
+    #[test]
+    fn test_pinned_fingerprint() -> anyhow::Result<()> {
+        let peer_cert = ChiaCertificate::generate()?;
+        let der: CertificateDer<'_> = rustls_pemfile::certs(&mut peer_cert.cert_pem.as_bytes())
+            .next()
+            .expect("missing certificate")?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(der.as_ref());
+        let fingerprint: [u8; 32] = hasher.finalize();
+
+        let mut wrong_fingerprint = fingerprint;
+        wrong_fingerprint[0] ^= 1;
+
+        assert!(verify(None, &der));
+        assert!(verify(Some(fingerprint), &der));
+        assert!(!verify(Some(wrong_fingerprint), &der));
+
+        Ok(())
+    }
+}
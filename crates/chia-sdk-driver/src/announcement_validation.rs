@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+
+use chia_protocol::CoinSpend;
+use chia_sdk_types::{coin_announcement_id, puzzle_announcement_id, run_puzzle, Condition};
+use clvm_traits::{FromClvm, ToClvm};
+use clvmr::Allocator;
+
+use crate::ValidationError;
+
+/// Checks that every coin and puzzle announcement asserted by the coin spends is created by one of them.
+/// The first assertion which isn't satisfied is returned as [`ValidationError::UnsatisfiedAnnouncement`].
+///
+/// Spend bundles with a missing announcement are rejected by the mempool, so this catches broken links
+/// between coin spends before submission. Announcements that are only satisfied by other spend bundles
+/// in the same block aren't accounted for.
+pub fn validate_announcements(
+    allocator: &mut Allocator,
+    coin_spends: &[CoinSpend],
+) -> Result<(), ValidationError> {
+    let mut coin_announcements = HashSet::new();
+    let mut puzzle_announcements = HashSet::new();
+    let mut coin_assertions = Vec::new();
+    let mut puzzle_assertions = Vec::new();
+
+    for coin_spend in coin_spends {
+        let coin_id = coin_spend.coin.coin_id();
+
+        let puzzle = coin_spend
+            .puzzle_reveal
+            .to_clvm(allocator)
+            .map_err(|error| ValidationError::ToClvm { coin_id, error })?;
+        let solution = coin_spend
+            .solution
+            .to_clvm(allocator)
+            .map_err(|error| ValidationError::ToClvm { coin_id, error })?;
+
+        let result = run_puzzle(allocator, puzzle, solution)
+            .map_err(|error| ValidationError::Eval { coin_id, error })?;
+        let conditions = Vec::<Condition>::from_clvm(allocator, result)
+            .map_err(|error| ValidationError::FromClvm { coin_id, error })?;
+
+        for condition in conditions {
+            match condition {
+                Condition::CreateCoinAnnouncement(announcement) => {
+                    coin_announcements.insert(coin_announcement_id(coin_id, announcement.message));
+                }
+                Condition::CreatePuzzleAnnouncement(announcement) => {
+                    puzzle_announcements.insert(puzzle_announcement_id(
+                        coin_spend.coin.puzzle_hash,
+                        announcement.message,
+                    ));
+                }
+                Condition::AssertCoinAnnouncement(assertion) => {
+                    coin_assertions.push((coin_id, assertion.announcement_id));
+                }
+                Condition::AssertPuzzleAnnouncement(assertion) => {
+                    puzzle_assertions.push((coin_id, assertion.announcement_id));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let unsatisfied = coin_assertions
+        .into_iter()
+        .find(|(_, id)| !coin_announcements.contains(id))
+        .or_else(|| {
+            puzzle_assertions
+                .into_iter()
+                .find(|(_, id)| !puzzle_announcements.contains(id))
+        });
+
+    match unsatisfied {
+        Some((coin_id, announcement_id)) => Err(ValidationError::UnsatisfiedAnnouncement {
+            coin_id,
+            announcement_id,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Bytes;
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::{coin_announcement_pair, puzzle_announcement_pair, Conditions};
+
+    use crate::{SpendContext, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_validate_announcements() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (_sk, alice_pk, alice_puzzle_hash, alice) = sim.new_p2(1)?;
+        let (_sk, bob_pk, _bob_puzzle_hash, bob) = sim.new_p2(1)?;
+
+        let (create_coin, assert_coin) =
+            coin_announcement_pair(alice.coin_id(), Bytes::from(b"coin".to_vec()));
+        let (create_puzzle, assert_puzzle) =
+            puzzle_announcement_pair(alice_puzzle_hash, Bytes::from(b"puzzle".to_vec()));
+
+        StandardLayer::new(alice_pk).spend(
+            ctx,
+            alice,
+            Conditions::new()
+                .with(create_coin.clone())
+                .with(create_puzzle),
+        )?;
+        StandardLayer::new(bob_pk).spend(
+            ctx,
+            bob,
+            Conditions::new().with(assert_coin).with(assert_puzzle),
+        )?;
+
+        let coin_spends = ctx.take();
+        validate_announcements(&mut ctx.allocator, &coin_spends)?;
+
+        // Without Alice's spend, neither of Bob's assertions can be satisfied.
+        assert!(matches!(
+            validate_announcements(&mut ctx.allocator, &coin_spends[1..]),
+            Err(ValidationError::UnsatisfiedAnnouncement { coin_id, announcement_id })
+                if coin_id == bob.coin_id() && announcement_id == assert_coin.announcement_id
+        ));
+
+        // A message which doesn't match the announcement is a broken link.
+        StandardLayer::new(alice_pk).spend(
+            ctx,
+            alice,
+            Conditions::new()
+                .with(create_coin)
+                .create_puzzle_announcement(b"other".to_vec().into()),
+        )?;
+        let mut broken = ctx.take();
+        broken.push(coin_spends[1].clone());
+
+        assert!(matches!(
+            validate_announcements(&mut ctx.allocator, &broken),
+            Err(ValidationError::UnsatisfiedAnnouncement { coin_id, announcement_id })
+                if coin_id == bob.coin_id() && announcement_id == assert_puzzle.announcement_id
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use chia_protocol::SpendBundle;
+use chia_sdk_types::MAINNET_CONSTANTS;
+use clvm_traits::{FromClvm, ToClvm};
+use clvmr::{reduction::Reduction, run_program, Allocator, ChiaDialect, NodePtr};
+
+use crate::DriverError;
+
+/// The cost of each `CREATE_COIN` condition.
+pub const CREATE_COIN_COST: u64 = 1_800_000;
+
+/// The cost of each `AGG_SIG_*` condition.
+pub const AGG_SIG_COST: u64 = 1_200_000;
+
+/// The cost of a `SOFTFORK` condition is its first argument multiplied by this.
+const SOFTFORK_COST_MULTIPLIER: u64 = 10_000;
+
+/// Estimates the CLVM cost of a spend bundle, which can be multiplied by a fee rate to calculate the fee.
+///
+/// This is the sum of running each puzzle, the cost of the conditions it outputs, and the cost per byte
+/// of its puzzle reveal and solution. The bytes used to wrap each spend in the block generator aren't
+/// included, so this is slightly lower than the cost reported by a full node.
+///
+/// Spend bundles which cost more than the maximum block cost can never be included in a block,
+/// so this returns [`DriverError::CostExceeded`] instead of an estimate.
+pub fn estimate_cost(
+    allocator: &mut Allocator,
+    spend_bundle: &SpendBundle,
+) -> Result<u64, DriverError> {
+    let max_cost = MAINNET_CONSTANTS.max_block_cost_clvm;
+    let cost_per_byte = MAINNET_CONSTANTS.cost_per_byte;
+
+    let mut total = 0;
+
+    for coin_spend in &spend_bundle.coin_spends {
+        let puzzle = coin_spend.puzzle_reveal.to_clvm(allocator)?;
+        let solution = coin_spend.solution.to_clvm(allocator)?;
+
+        let Reduction(cost, output) = run_program(
+            allocator,
+            &ChiaDialect::new(0),
+            puzzle,
+            solution,
+            max_cost - total,
+        )?;
+
+        total = add_cost(total, cost, max_cost)?;
+        total = add_cost(
+            total,
+            condition_cost(allocator, output, max_cost)?,
+            max_cost,
+        )?;
+
+        let bytes = coin_spend.puzzle_reveal.as_ref().len() + coin_spend.solution.as_ref().len();
+        let byte_cost = u64::try_from(bytes)?
+            .checked_mul(cost_per_byte)
+            .ok_or(DriverError::CostExceeded(max_cost))?;
+        total = add_cost(total, byte_cost, max_cost)?;
+    }
+
+    Ok(total)
+}
+
+fn add_cost(total: u64, cost: u64, max_cost: u64) -> Result<u64, DriverError> {
+    total
+        .checked_add(cost)
+        .filter(|&total| total <= max_cost)
+        .ok_or(DriverError::CostExceeded(max_cost))
+}
+
+fn condition_cost(
+    allocator: &Allocator,
+    output: NodePtr,
+    max_cost: u64,
+) -> Result<u64, DriverError> {
+    let mut cost = 0;
+
+    for condition in Vec::<NodePtr>::from_clvm(allocator, output)? {
+        // Conditions with an opcode that doesn't fit are unknown, and don't have a cost.
+        let Ok((opcode, args)) = <(u16, NodePtr)>::from_clvm(allocator, condition) else {
+            continue;
+        };
+
+        let condition_cost = match opcode {
+            51 => CREATE_COIN_COST,
+            43..=50 => AGG_SIG_COST,
+            90 => {
+                let (softfork_cost, _rest) = <(u64, NodePtr)>::from_clvm(allocator, args)?;
+                softfork_cost
+                    .checked_mul(SOFTFORK_COST_MULTIPLIER)
+                    .ok_or(DriverError::CostExceeded(max_cost))?
+            }
+            _ => 0,
+        };
+
+        cost = add_cost(cost, condition_cost, max_cost)?;
+    }
+
+    Ok(cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::Signature;
+    use chia_consensus::gen::{
+        conditions::EmptyVisitor, run_block_generator::run_block_generator,
+        solution_generator::solution_generator,
+    };
+    use chia_protocol::{Bytes32, Coin, CoinSpend, Program};
+    use chia_puzzles::standard::StandardArgs;
+    use chia_sdk_test::test_secret_key;
+    use chia_sdk_types::{Conditions, TESTNET11_CONSTANTS};
+
+    use crate::{SpendContext, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_standard_spend_cost() -> anyhow::Result<()> {
+        let ctx = &mut SpendContext::new();
+
+        let pk = test_secret_key()?.public_key();
+        let puzzle_hash = StandardArgs::curry_tree_hash(pk).into();
+        let coin = Coin::new(Bytes32::default(), puzzle_hash, 100);
+
+        StandardLayer::new(pk).spend(
+            ctx,
+            coin,
+            Conditions::new()
+                .create_coin(puzzle_hash, 60, Vec::new())
+                .create_coin(puzzle_hash, 40, Vec::new()),
+        )?;
+
+        let coin_spends = ctx.take();
+
+        let generator = solution_generator(
+            coin_spends
+                .iter()
+                .map(|cs| (cs.coin, cs.puzzle_reveal.clone(), cs.solution.clone())),
+        )?;
+        let conds = run_block_generator::<Vec<u8>, EmptyVisitor, _>(
+            &mut ctx.allocator,
+            &generator,
+            [],
+            11_000_000_000,
+            0,
+            &TESTNET11_CONSTANTS,
+        )?;
+
+        let estimate = estimate_cost(
+            &mut ctx.allocator,
+            &SpendBundle::new(coin_spends, Signature::default()),
+        )?;
+
+        // The only difference should be the bytes used to wrap the spend in the generator.
+        assert!(estimate <= conds.cost);
+        assert!(conds.cost - estimate < 1_000_000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cost_exceeds_max_block_cost() -> anyhow::Result<()> {
+        let ctx = &mut SpendContext::new();
+
+        for softfork_cost in [u64::MAX, MAINNET_CONSTANTS.max_block_cost_clvm] {
+            let puzzle_reveal = ctx.serialize(&(1, [(90, (softfork_cost, ()))]))?;
+            let coin = Coin::new(Bytes32::default(), Bytes32::default(), 1);
+            let coin_spend = CoinSpend::new(coin, puzzle_reveal, Program::default());
+
+            assert!(matches!(
+                estimate_cost(
+                    &mut ctx.allocator,
+                    &SpendBundle::new(vec![coin_spend], Signature::default()),
+                ),
+                Err(DriverError::CostExceeded(..))
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt::Write;
+
+use chia_protocol::CoinSpend;
+use clvmr::{serde::node_from_bytes_backrefs, Allocator, NodePtr, SExp};
+
+use crate::DriverError;
+
+/// The operator keywords, indexed by opcode.
+/// Only the original operators are included, so that condition opcodes such as `51` aren't shown as BLS operators.
+const KEYWORDS: [&str; 37] = [
+    ".",
+    "q",
+    "a",
+    "i",
+    "c",
+    "f",
+    "r",
+    "l",
+    "x",
+    "=",
+    ">s",
+    "sha256",
+    "substr",
+    "strlen",
+    "concat",
+    ".",
+    "+",
+    "-",
+    "*",
+    "/",
+    "divmod",
+    ">",
+    "ash",
+    "lsh",
+    "logand",
+    "logior",
+    "logxor",
+    "lognot",
+    ".",
+    "point_add",
+    "pubkey_for_exp",
+    ".",
+    "not",
+    "any",
+    "all",
+    ".",
+    "softfork",
+];
+
+/// Converts a CLVM value to s-expression text, in the same format as `brun` and `opd`.
+///
+/// Atoms at the start of a list are shown as operator keywords, short atoms which are
+/// canonical integers are shown in decimal, printable atoms are quoted, and anything else is hex.
+pub fn disassemble(allocator: &Allocator, ptr: NodePtr) -> String {
+    let mut output = String::new();
+    write_node(allocator, ptr, None, &mut output);
+    output
+}
+
+/// Deserializes the puzzle reveal and solution of a coin spend, which may be backref encoded,
+/// and disassembles them for logging.
+pub fn disassemble_coin_spend(
+    allocator: &mut Allocator,
+    coin_spend: &CoinSpend,
+) -> Result<String, DriverError> {
+    let puzzle = node_from_bytes_backrefs(allocator, coin_spend.puzzle_reveal.as_ref())?;
+    let solution = node_from_bytes_backrefs(allocator, coin_spend.solution.as_ref())?;
+
+    Ok(format!(
+        "coin_id: 0x{}\npuzzle: {}\nsolution: {}",
+        hex::encode(coin_spend.coin.coin_id()),
+        disassemble(allocator, puzzle),
+        disassemble(allocator, solution)
+    ))
+}
+
+enum Op {
+    Node(NodePtr, Option<bool>),
+    List(NodePtr, Option<bool>, bool),
+    Close,
+}
+
+fn write_node(
+    allocator: &Allocator,
+    ptr: NodePtr,
+    allow_keyword: Option<bool>,
+    output: &mut String,
+) {
+    // An explicit stack is used rather than recursion, since deeply nested values would overflow the stack.
+    let mut ops = vec![Op::Node(ptr, allow_keyword)];
+
+    while let Some(op) = ops.pop() {
+        match op {
+            Op::Node(ptr, allow_keyword) => match allocator.sexp(ptr) {
+                SExp::Atom => write_atom(
+                    allocator.atom(ptr).as_ref(),
+                    allow_keyword == Some(true),
+                    output,
+                ),
+                SExp::Pair(..) => {
+                    output.push('(');
+                    ops.push(Op::List(ptr, allow_keyword, true));
+                }
+            },
+            Op::List(ptr, allow_keyword, first) => match allocator.sexp(ptr) {
+                SExp::Pair(item, rest) => {
+                    if !first {
+                        output.push(' ');
+                    }
+
+                    // Only the first item of each list can be a keyword, unless it's nested in another list.
+                    let allow_item_keyword = if allow_keyword.is_none()
+                        || matches!(allocator.sexp(item), SExp::Pair(..))
+                    {
+                        Some(true)
+                    } else {
+                        allow_keyword
+                    };
+
+                    ops.push(Op::List(rest, Some(false), false));
+                    ops.push(Op::Node(item, allow_item_keyword));
+                }
+                SExp::Atom => {
+                    if allocator.atom(ptr).as_ref().is_empty() {
+                        output.push(')');
+                    } else {
+                        output.push_str(" . ");
+                        ops.push(Op::Close);
+                        ops.push(Op::Node(ptr, Some(false)));
+                    }
+                }
+            },
+            Op::Close => output.push(')'),
+        }
+    }
+}
+
+fn write_atom(atom: &[u8], allow_keyword: bool, output: &mut String) {
+    if allow_keyword && atom.len() == 1 {
+        if let Some(keyword) = KEYWORDS.get(usize::from(atom[0])) {
+            if *keyword != "." {
+                output.push_str(keyword);
+                return;
+            }
+        }
+    }
+
+    if atom.is_empty() {
+        output.push_str("()");
+        return;
+    }
+
+    if atom.len() > 2 {
+        if atom.iter().all(|byte| (0x20..0x7f).contains(byte)) {
+            let text = String::from_utf8_lossy(atom);
+            let quote = if text.contains('"') { '\'' } else { '"' };
+            output.push(quote);
+            output.push_str(&text);
+            output.push(quote);
+        } else {
+            output.push_str("0x");
+            output.push_str(&hex::encode(atom));
+        }
+        return;
+    }
+
+    if let Some(value) = canonical_int(atom) {
+        write!(output, "{value}").expect("writing to a string can't fail");
+    } else {
+        output.push_str("0x");
+        output.push_str(&hex::encode(atom));
+    }
+}
+
+/// Decodes a one or two byte atom as a signed integer, if it has no redundant leading bytes.
+fn canonical_int(atom: &[u8]) -> Option<i16> {
+    let value = match atom {
+        [byte] => i16::from(i8::from_be_bytes([*byte])),
+        [high, low] => i16::from_be_bytes([*high, *low]),
+        _ => return None,
+    };
+
+    // Zero is encoded as nil, and a leading byte is only needed to preserve the sign.
+    let redundant = match atom {
+        [byte] => *byte == 0x00,
+        [high, low] => (*high == 0x00 && low & 0x80 == 0) || (*high == 0xff && low & 0x80 != 0),
+        _ => false,
+    };
+
+    (!redundant).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::{Bytes32, Coin};
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::Conditions;
+
+    use crate::{SpendContext, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_disassemble_standard_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+
+        StandardLayer::new(pk).spend(
+            ctx,
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+
+        let coin_spends = ctx.take();
+        let output = disassemble_coin_spend(&mut ctx.allocator, &coin_spends[0])?;
+
+        assert!(output.starts_with(&format!("coin_id: 0x{}", hex::encode(coin.coin_id()))));
+        assert!(output.contains("puzzle: (a (q 2 (q 2 (i 11"));
+        assert!(output.contains("(point_add 11 (pubkey_for_exp (sha256 11"));
+        assert!(output.contains(&format!("0x{}", hex::encode(pk.to_bytes()))));
+        assert!(output.contains(&format!(
+            "solution: (() (q (51 0x{} 1",
+            hex::encode(puzzle_hash)
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_disassemble_atoms() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let coin_spend = CoinSpend::new(
+            Coin::new(Bytes32::default(), Bytes32::default(), 0),
+            // (q . "hello")
+            vec![0xff, 0x01, 0x85, b'h', b'e', b'l', b'l', b'o'].into(),
+            // (1 -1 128 0x0001)
+            vec![
+                0xff, 0x01, 0xff, 0x81, 0xff, 0xff, 0x82, 0x00, 0x80, 0xff, 0x82, 0x00, 0x01, 0x80,
+            ]
+            .into(),
+        );
+
+        let output = disassemble_coin_spend(&mut allocator, &coin_spend)?;
+        assert!(output.contains("puzzle: (q . \"hello\")"));
+        assert!(output.contains("solution: (q -1 128 0x0001)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_disassemble_deeply_nested() {
+        let mut allocator = Allocator::new();
+        let mut ptr = NodePtr::NIL;
+
+        // Nesting in the first item makes each level a separate list, rather than another item of the same one.
+        for _ in 0..100_000 {
+            ptr = allocator.new_pair(ptr, NodePtr::NIL).unwrap();
+        }
+
+        assert_eq!(
+            disassemble(&allocator, ptr),
+            format!("{}(){}", "(".repeat(100_000), ")".repeat(100_000))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::num::TryFromIntError;
 
+use chia_protocol::Bytes32;
 use clvm_traits::{FromClvmError, ToClvmError};
 use clvmr::reduction::EvalErr;
 use thiserror::Error;
@@ -45,6 +46,33 @@ pub enum DriverError {
     #[error("expected even oracle fee, but it was odd")]
     OddOracleFee,
 
+    #[error("singleton amount {0} is even, but singletons must have an odd amount")]
+    EvenSingletonAmount(u64),
+
+    #[error("recovery list doesn't match the DID's recovery list hash, or recovery is disabled")]
+    InvalidRecoveryList,
+
+    #[error("expected {required} attestments from the recovery list, but only found {found}")]
+    InsufficientAttestments { required: u64, found: u64 },
+
+    #[error("duplicate launcher amount {0}, which would result in the same launcher id")]
+    DuplicateLauncherAmount(u64),
+
+    #[error("no coins were provided")]
+    NoCoins,
+
+    #[error("duplicate output amount {0}, which would result in the same coin id")]
+    DuplicateOutputAmount(u64),
+
+    #[error("output amount {output} exceeds the coin amount {input}")]
+    InsufficientAmount { input: u64, output: u64 },
+
+    #[error("missing merkle proof for puzzle hash {0}")]
+    MissingMerkleProof(Bytes32),
+
+    #[error("cost exceeds the maximum block cost of {0}")]
+    CostExceeded(u64),
+
     #[error("custom driver error: {0}")]
     Custom(String),
 }
@@ -1,6 +1,7 @@
 use chia_protocol::{Coin, CoinSpend};
 use clvm_traits::{FromClvm, ToClvm};
 use clvmr::{Allocator, NodePtr};
+use tracing::instrument;
 
 use crate::{DriverError, Puzzle, Spend, SpendContext};
 
@@ -36,6 +37,7 @@ pub trait Layer {
     ) -> Result<NodePtr, DriverError>;
 
     /// Creates a spend for this layer.
+    #[instrument(level = "trace", skip_all, fields(layer = std::any::type_name::<Self>()))]
     fn construct_spend(
         &self,
         ctx: &mut SpendContext,
@@ -47,6 +49,11 @@ pub trait Layer {
     }
 
     /// Creates a coin spend for this layer.
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(layer = std::any::type_name::<Self>(), coin_id = %coin.coin_id())
+    )]
     fn construct_coin_spend(
         &self,
         ctx: &mut SpendContext,
@@ -117,6 +117,11 @@ where
 mod tests {
     use chia_protocol::Coin;
     use chia_puzzles::CoinProof;
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::Conditions;
+    use clvm_traits::ToClvm;
+
+    use crate::{Cat, StandardLayer};
 
     use super::*;
 
@@ -173,4 +178,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_cat_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (issue_cat, cat) = Cat::single_issuance_eve(
+            ctx,
+            coin.coin_id(),
+            1,
+            Conditions::new().create_coin(puzzle_hash, 1, vec![puzzle_hash.into()]),
+        )?;
+        p2.spend(ctx, coin, issue_cat)?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let puzzle_reveal = sim
+            .puzzle_reveal(cat.coin.coin_id())
+            .expect("missing puzzle")
+            .to_clvm(&mut ctx.allocator)?;
+        let solution = sim
+            .solution(cat.coin.coin_id())
+            .expect("missing solution")
+            .to_clvm(&mut ctx.allocator)?;
+
+        let puzzle = Puzzle::parse(&ctx.allocator, puzzle_reveal);
+        let layer =
+            CatLayer::<Puzzle>::parse_puzzle(&ctx.allocator, puzzle)?.expect("invalid CAT layer");
+        assert_eq!(layer.asset_id, cat.asset_id);
+        assert_eq!(
+            Bytes32::from(layer.inner_puzzle.curried_puzzle_hash()),
+            cat.p2_puzzle_hash
+        );
+
+        let parsed_solution = CatLayer::<Puzzle>::parse_solution(&ctx.allocator, solution)?;
+        assert_eq!(parsed_solution.this_coin_info, cat.coin);
+        assert_eq!(parsed_solution.lineage_proof, None);
+
+        let reconstructed_puzzle = layer.construct_puzzle(ctx)?;
+        assert_eq!(
+            ctx.tree_hash(reconstructed_puzzle),
+            puzzle.curried_puzzle_hash()
+        );
+
+        let reconstructed_solution = layer.construct_solution(ctx, parsed_solution)?;
+        assert_eq!(
+            ctx.tree_hash(reconstructed_solution),
+            ctx.tree_hash(solution)
+        );
+
+        Ok(())
+    }
 }
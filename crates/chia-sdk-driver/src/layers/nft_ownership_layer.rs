@@ -119,3 +119,61 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::SecretKey;
+    use chia_puzzles::standard::StandardSolution;
+
+    use crate::{RoyaltyTransferLayer, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_ownership_layer_round_trip() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let synthetic_key = SecretKey::from_seed(&[1; 32]).public_key();
+
+        let layer = NftOwnershipLayer::new(
+            Some(Bytes32::new([2; 32])),
+            RoyaltyTransferLayer::new(Bytes32::new([3; 32]), Bytes32::new([4; 32]), 300),
+            StandardLayer::new(synthetic_key),
+        );
+
+        let puzzle = layer.construct_puzzle(&mut ctx)?;
+        assert_eq!(ctx.tree_hash(puzzle), layer.tree_hash());
+
+        let parsed = NftOwnershipLayer::<RoyaltyTransferLayer, StandardLayer>::parse_puzzle(
+            &ctx.allocator,
+            Puzzle::parse(&ctx.allocator, puzzle),
+        )?
+        .expect("invalid nft ownership layer");
+        assert_eq!(parsed, layer);
+
+        // The new owner and trade prices are passed through the inner puzzle's conditions,
+        // so the layer's own solution only wraps the inner solution.
+        let delegated_puzzle = ctx.alloc(&(1, ()))?;
+        let solution = layer.construct_solution(
+            &mut ctx,
+            NftOwnershipLayerSolution {
+                inner_solution: StandardSolution {
+                    original_public_key: None,
+                    delegated_puzzle,
+                    solution: NodePtr::NIL,
+                },
+            },
+        )?;
+
+        let parsed = NftOwnershipLayer::<RoyaltyTransferLayer, StandardLayer>::parse_solution(
+            &ctx.allocator,
+            solution,
+        )?;
+        assert_eq!(parsed.inner_solution.original_public_key, None);
+        assert_eq!(
+            ctx.tree_hash(parsed.inner_solution.delegated_puzzle),
+            ctx.tree_hash(delegated_puzzle)
+        );
+
+        Ok(())
+    }
+}
@@ -157,3 +157,76 @@ impl<M, I> NftStateLayer<M, I> {
         Ok(parsed.metadata_info.new_metadata)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::SecretKey;
+    use chia_puzzles::{
+        nft::{NftMetadata, NFT_METADATA_UPDATER_PUZZLE_HASH},
+        standard::StandardSolution,
+    };
+    use hex_literal::hex;
+
+    use crate::StandardLayer;
+
+    use super::*;
+
+    #[test]
+    fn test_state_layer_round_trip() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let synthetic_key = SecretKey::from_seed(&[1; 32]).public_key();
+
+        let metadata = NftMetadata {
+            edition_number: 1,
+            edition_total: 1,
+            data_uris: vec!["https://example.com/data".to_string()],
+            data_hash: Some(Bytes32::new(hex!(
+                "278de91c1746b60d2b914b380d360ef393850aa5391c31ee4523aee2368e0d37"
+            ))),
+            metadata_uris: vec!["https://example.com/metadata".to_string()],
+            metadata_hash: Some(Bytes32::new(hex!(
+                "5158025f5b241c6ec1848972395c383548945f66c1610bfac0dea907b65e8d60"
+            ))),
+            license_uris: Vec::new(),
+            license_hash: None,
+        };
+
+        let layer = NftStateLayer::new(
+            metadata,
+            NFT_METADATA_UPDATER_PUZZLE_HASH.into(),
+            StandardLayer::new(synthetic_key),
+        );
+
+        let puzzle = layer.construct_puzzle(&mut ctx)?;
+        assert_eq!(ctx.tree_hash(puzzle), layer.tree_hash());
+
+        let parsed = NftStateLayer::<NftMetadata, StandardLayer>::parse_puzzle(
+            &ctx.allocator,
+            Puzzle::parse(&ctx.allocator, puzzle),
+        )?
+        .expect("invalid nft state layer");
+        assert_eq!(parsed, layer);
+
+        let delegated_puzzle = ctx.alloc(&(1, ()))?;
+        let solution = layer.construct_solution(
+            &mut ctx,
+            NftStateLayerSolution {
+                inner_solution: StandardSolution {
+                    original_public_key: None,
+                    delegated_puzzle,
+                    solution: NodePtr::NIL,
+                },
+            },
+        )?;
+
+        let parsed =
+            NftStateLayer::<NftMetadata, StandardLayer>::parse_solution(&ctx.allocator, solution)?;
+        assert_eq!(parsed.inner_solution.original_public_key, None);
+        assert_eq!(
+            ctx.tree_hash(parsed.inner_solution.delegated_puzzle),
+            ctx.tree_hash(delegated_puzzle)
+        );
+
+        Ok(())
+    }
+}
@@ -66,10 +66,10 @@ pub struct P2OneOfManyArgs {
     pub merkle_root: Bytes32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
 #[clvm(list)]
 pub struct P2OneOfManySolution<P, S> {
-    pub merkle_proof: Bytes32,
+    pub merkle_proof: (u32, Vec<Bytes32>),
     pub puzzle: P,
     pub solution: S,
 }
@@ -103,3 +103,36 @@ impl ToTreeHash for RoyaltyTransferLayer {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use clvmr::serde::node_from_bytes;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_royalty_terms() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let layer = RoyaltyTransferLayer::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 300);
+        let puzzle_ptr = layer.construct_puzzle(&mut ctx)?;
+        let puzzle_reveal = ctx.serialize(&puzzle_ptr)?;
+
+        // The terms can be read from the serialized reveal alone, without knowing them in advance.
+        let mut allocator = Allocator::new();
+        let ptr = node_from_bytes(&mut allocator, puzzle_reveal.as_ref())?;
+        let parsed =
+            RoyaltyTransferLayer::parse_puzzle(&allocator, Puzzle::parse(&allocator, ptr))?
+                .expect("invalid royalty transfer layer");
+
+        assert_eq!(parsed.launcher_id, Bytes32::new([1; 32]));
+        assert_eq!(parsed.royalty_puzzle_hash, Bytes32::new([2; 32]));
+        assert_eq!(parsed.royalty_ten_thousandths, 300);
+        assert_eq!(parsed.tree_hash(), layer.tree_hash());
+
+        // Other puzzles aren't mistaken for a royalty transfer program.
+        let puzzle = Puzzle::parse(&ctx.allocator, NodePtr::NIL);
+        assert!(RoyaltyTransferLayer::parse_puzzle(&ctx.allocator, puzzle)?.is_none());
+
+        Ok(())
+    }
+}
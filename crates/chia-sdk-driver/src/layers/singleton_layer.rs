@@ -102,6 +102,10 @@ where
         ctx: &mut SpendContext,
         solution: Self::Solution,
     ) -> Result<NodePtr, DriverError> {
+        if solution.amount % 2 == 0 {
+            return Err(DriverError::EvenSingletonAmount(solution.amount));
+        }
+
         let inner_solution = self
             .inner_puzzle
             .construct_solution(ctx, solution.inner_solution)?;
@@ -118,12 +122,8 @@ where
     I: ToTreeHash,
 {
     fn tree_hash(&self) -> TreeHash {
-        let inner_puzzle = self.inner_puzzle.tree_hash();
-        SingletonArgs {
-            singleton_struct: SingletonStruct::new(self.launcher_id),
-            inner_puzzle,
-        }
-        .tree_hash()
+        let inner_puzzle_hash = self.inner_puzzle.tree_hash();
+        SingletonArgs::curry_tree_hash(self.launcher_id, inner_puzzle_hash)
     }
 }
 
@@ -140,3 +140,140 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::SecretKey;
+    use chia_puzzles::{standard::StandardSolution, EveProof, Proof};
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::Conditions;
+    use clvm_traits::ToClvm;
+
+    use crate::{Launcher, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_singleton_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, _puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 1).create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+        let _did = did.update(ctx, &p2, Conditions::new())?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let puzzle_reveal = sim
+            .puzzle_reveal(did.coin.coin_id())
+            .expect("missing puzzle")
+            .to_clvm(&mut ctx.allocator)?;
+        let solution = sim
+            .solution(did.coin.coin_id())
+            .expect("missing solution")
+            .to_clvm(&mut ctx.allocator)?;
+
+        let puzzle = Puzzle::parse(&ctx.allocator, puzzle_reveal);
+        let layer = SingletonLayer::<Puzzle>::parse_puzzle(&ctx.allocator, puzzle)?
+            .expect("invalid singleton layer");
+        assert_eq!(layer.launcher_id, did.info.launcher_id);
+
+        let parsed_solution = SingletonLayer::<Puzzle>::parse_solution(&ctx.allocator, solution)?;
+        assert_eq!(parsed_solution.lineage_proof, did.proof);
+        assert_eq!(parsed_solution.amount, did.coin.amount);
+
+        let reconstructed_puzzle = layer.construct_puzzle(ctx)?;
+        assert_eq!(
+            Bytes32::from(ctx.tree_hash(reconstructed_puzzle)),
+            did.coin.puzzle_hash
+        );
+        assert_eq!(Bytes32::from(layer.tree_hash()), did.coin.puzzle_hash);
+
+        let reconstructed_solution = layer.construct_solution(ctx, parsed_solution)?;
+        assert_eq!(
+            ctx.tree_hash(reconstructed_solution),
+            ctx.tree_hash(solution)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_layers() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let synthetic_key = SecretKey::from_seed(&[1; 32]).public_key();
+
+        // Layers compose by nesting, so the outer layer curries and solves the inner layer for us.
+        let layer = SingletonLayer::new(Bytes32::new([2; 32]), StandardLayer::new(synthetic_key));
+        let puzzle = layer.construct_puzzle(&mut ctx)?;
+        assert_eq!(ctx.tree_hash(puzzle), layer.tree_hash());
+
+        let parsed = SingletonLayer::<StandardLayer>::parse_puzzle(
+            &ctx.allocator,
+            Puzzle::parse(&ctx.allocator, puzzle),
+        )?
+        .expect("invalid singleton layer");
+        assert_eq!(parsed, layer);
+
+        let delegated_puzzle = ctx.alloc(&(1, ()))?;
+        let lineage_proof = Proof::Eve(EveProof {
+            parent_parent_coin_info: Bytes32::default(),
+            parent_amount: 1,
+        });
+
+        let solution = layer.construct_solution(
+            &mut ctx,
+            SingletonSolution {
+                lineage_proof,
+                amount: 1,
+                inner_solution: StandardSolution {
+                    original_public_key: None,
+                    delegated_puzzle,
+                    solution: NodePtr::NIL,
+                },
+            },
+        )?;
+
+        let parsed = SingletonLayer::<StandardLayer>::parse_solution(&ctx.allocator, solution)?;
+        assert_eq!(parsed.lineage_proof, lineage_proof);
+        assert_eq!(parsed.amount, 1);
+        assert_eq!(parsed.inner_solution.original_public_key, None);
+        assert_eq!(
+            ctx.tree_hash(parsed.inner_solution.delegated_puzzle),
+            ctx.tree_hash(delegated_puzzle)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_even_singleton_amount() {
+        let mut ctx = SpendContext::new();
+        let layer = SingletonLayer::new(Bytes32::default(), NodePtr::NIL);
+
+        let solution = |amount| SingletonSolution {
+            lineage_proof: Proof::Eve(EveProof {
+                parent_parent_coin_info: Bytes32::default(),
+                parent_amount: 1,
+            }),
+            amount,
+            inner_solution: NodePtr::NIL,
+        };
+
+        assert!(matches!(
+            layer.construct_solution(&mut ctx, solution(2)),
+            Err(DriverError::EvenSingletonAmount(2))
+        ));
+        assert!(layer.construct_solution(&mut ctx, solution(1)).is_ok());
+    }
+
+    #[test]
+    fn test_not_a_singleton() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let ptr = ctx.alloc(&"Hello, world!")?;
+        let puzzle = Puzzle::parse(&ctx.allocator, ptr);
+        assert!(SingletonLayer::<Puzzle>::parse_puzzle(&ctx.allocator, puzzle)?.is_none());
+        Ok(())
+    }
+}
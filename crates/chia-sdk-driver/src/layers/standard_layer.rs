@@ -1,5 +1,5 @@
 use chia_bls::PublicKey;
-use chia_protocol::Coin;
+use chia_protocol::{Bytes32, Coin};
 use chia_puzzles::standard::{StandardArgs, StandardSolution, STANDARD_PUZZLE_HASH};
 use chia_sdk_types::Conditions;
 use clvm_traits::{clvm_quote, FromClvm};
@@ -39,6 +39,97 @@ impl StandardLayer {
         ctx.spend(coin, spend)
     }
 
+    /// Spends all of the coins and consolidates their value into a single coin with this puzzle hash.
+    /// Every coin other than the first asserts that it's spent alongside the first, which creates the output.
+    pub fn combine_coins(
+        &self,
+        ctx: &mut SpendContext,
+        coins: &[Coin],
+    ) -> Result<Coin, DriverError> {
+        let Some((first, rest)) = coins.split_first() else {
+            return Err(DriverError::NoCoins);
+        };
+
+        let puzzle_hash = self.tree_hash().into();
+        let amount = u64::try_from(
+            coins
+                .iter()
+                .map(|coin| u128::from(coin.amount))
+                .sum::<u128>(),
+        )?;
+
+        self.spend(
+            ctx,
+            *first,
+            Conditions::new().create_coin(puzzle_hash, amount, Vec::new()),
+        )?;
+
+        for &coin in rest {
+            self.spend(
+                ctx,
+                coin,
+                Conditions::new().assert_concurrent_spend(first.coin_id()),
+            )?;
+        }
+
+        Ok(Coin::new(first.coin_id(), puzzle_hash, amount))
+    }
+
+    /// Spends the coin to create an output with this puzzle hash for each amount.
+    /// The amounts must be distinct, since otherwise the outputs would have the same coin id.
+    /// Any value left over after the outputs are created is paid as a fee.
+    pub fn split_coin(
+        &self,
+        ctx: &mut SpendContext,
+        coin: Coin,
+        amounts: &[u64],
+    ) -> Result<Vec<Coin>, DriverError> {
+        let puzzle_hash = self.tree_hash().into();
+        let mut conditions = Conditions::new();
+        let mut children: Vec<Coin> = Vec::with_capacity(amounts.len());
+        let mut output: u64 = 0;
+
+        for &amount in amounts {
+            if children.iter().any(|child| child.amount == amount) {
+                return Err(DriverError::DuplicateOutputAmount(amount));
+            }
+
+            output = output.saturating_add(amount);
+
+            if output > coin.amount {
+                return Err(DriverError::InsufficientAmount {
+                    input: coin.amount,
+                    output,
+                });
+            }
+
+            conditions = conditions.create_coin(puzzle_hash, amount, Vec::new());
+            children.push(Coin::new(coin.coin_id(), puzzle_hash, amount));
+        }
+
+        self.spend(ctx, coin, conditions)?;
+
+        Ok(children)
+    }
+
+    /// The message that the synthetic key must sign with `AGG_SIG_ME` to spend a coin with [`StandardLayer::spend`].
+    /// It's the tree hash of the quoted conditions, followed by the coin id and the network's `AGG_SIG_ME`
+    /// additional data, so offline signers can compute it without running the puzzle.
+    pub fn agg_sig_me_message(
+        ctx: &mut SpendContext,
+        coin_id: Bytes32,
+        conditions: &Conditions,
+        agg_sig_me_additional_data: Bytes32,
+    ) -> Result<Vec<u8>, DriverError> {
+        let delegated_puzzle = ctx.alloc(&clvm_quote!(conditions))?;
+        let delegated_puzzle_hash = ctx.tree_hash(delegated_puzzle);
+
+        let mut message = delegated_puzzle_hash.to_vec();
+        message.extend_from_slice(&coin_id);
+        message.extend_from_slice(&agg_sig_me_additional_data);
+        Ok(message)
+    }
+
     pub fn delegated_inner_spend(
         &self,
         ctx: &mut SpendContext,
@@ -124,10 +215,123 @@ impl ToTreeHash for StandardLayer {
 
 #[cfg(test)]
 mod tests {
-    use chia_sdk_test::Simulator;
+    use chia_protocol::Bytes;
+    use chia_sdk_test::{test_secret_key, Simulator};
+    use chia_sdk_types::{run_puzzle, Condition};
+    use clvm_traits::ToClvm;
 
     use super::*;
 
+    #[test]
+    fn test_parse_standard_puzzle() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let layer = StandardLayer::new(test_secret_key()?.public_key());
+
+        let ptr = layer.construct_puzzle(&mut ctx)?;
+        assert_eq!(ctx.tree_hash(ptr), layer.tree_hash());
+
+        let puzzle = Puzzle::parse(&ctx.allocator, ptr);
+        assert_eq!(
+            StandardLayer::parse_puzzle(&ctx.allocator, puzzle)?,
+            Some(layer)
+        );
+
+        let other = ctx.alloc(&1)?;
+        let puzzle = Puzzle::parse(&ctx.allocator, other);
+        assert_eq!(StandardLayer::parse_puzzle(&ctx.allocator, puzzle)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_standard_solution() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let layer = StandardLayer::new(test_secret_key()?.public_key());
+
+        let conditions = Conditions::new().reserve_fee(1);
+        let spend = layer.spend_with_conditions(&mut ctx, conditions.clone())?;
+        let solution = StandardLayer::parse_solution(&ctx.allocator, spend.solution)?;
+
+        let delegated_puzzle = ctx.alloc(&clvm_quote!(conditions))?;
+
+        assert_eq!(solution.original_public_key, None);
+        assert_eq!(solution.solution, NodePtr::NIL);
+        assert_eq!(
+            ctx.tree_hash(solution.delegated_puzzle),
+            ctx.tree_hash(delegated_puzzle)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_combine_coins() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let coins = [
+            coin,
+            sim.new_coin(puzzle_hash, 2),
+            sim.new_coin(puzzle_hash, 3),
+        ];
+
+        let combined = p2.combine_coins(ctx, &coins)?;
+        assert_eq!(combined.amount, 6);
+        assert_eq!(combined.puzzle_hash, puzzle_hash);
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+        assert!(sim.coin_state(combined.coin_id()).is_some());
+
+        for coin in coins {
+            assert!(sim
+                .coin_state(coin.coin_id())
+                .expect("missing coin state")
+                .spent_height
+                .is_some());
+        }
+
+        assert!(matches!(
+            p2.combine_coins(ctx, &[]),
+            Err(DriverError::NoCoins)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_coin() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(10)?;
+        let p2 = StandardLayer::new(pk);
+
+        assert!(matches!(
+            p2.split_coin(ctx, coin, &[1, 2, 1]),
+            Err(DriverError::DuplicateOutputAmount(1))
+        ));
+        assert!(matches!(
+            p2.split_coin(ctx, coin, &[6, 5]),
+            Err(DriverError::InsufficientAmount {
+                input: 10,
+                output: 11
+            })
+        ));
+
+        let children = p2.split_coin(ctx, coin, &[1, 2, 3, 4])?;
+        assert_eq!(children.len(), 4);
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        for child in children {
+            assert_eq!(child.puzzle_hash, puzzle_hash);
+            assert!(sim.coin_state(child.coin_id()).is_some());
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_flash_loan() -> anyhow::Result<()> {
         let mut sim = Simulator::new();
@@ -151,4 +355,76 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_create_coin_with_memos() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let memos = vec![Bytes::new(b"Payment for invoice #42".to_vec())];
+
+        p2.spend(
+            ctx,
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, memos.clone()),
+        )?;
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let child = Coin::new(coin.coin_id(), puzzle_hash, 1);
+        assert!(sim.coin_state(child.coin_id()).is_some());
+
+        // Memos aren't part of the coin record, so they are read from the parent's spend.
+        let mut allocator = Allocator::new();
+        let puzzle = sim
+            .puzzle_reveal(coin.coin_id())
+            .expect("missing puzzle")
+            .to_clvm(&mut allocator)?;
+        let solution = sim
+            .solution(coin.coin_id())
+            .expect("missing solution")
+            .to_clvm(&mut allocator)?;
+        let output = run_puzzle(&mut allocator, puzzle, solution)?;
+
+        let create_coin = Vec::<Condition>::from_clvm(&allocator, output)?
+            .into_iter()
+            .find_map(Condition::into_create_coin)
+            .expect("missing create coin");
+        assert_eq!(create_coin.memos, memos);
+        assert_eq!(create_coin.hint(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fee_from_change() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1000)?;
+        let p2 = StandardLayer::new(pk);
+
+        let payment_puzzle_hash = Bytes32::new([5; 32]);
+
+        p2.spend(
+            ctx,
+            coin,
+            Conditions::new()
+                .create_coin(payment_puzzle_hash, 600, Vec::new())
+                .create_coin(puzzle_hash, 400, Vec::new())
+                .fee_from_change(puzzle_hash, 150)?,
+        )?;
+
+        assert_eq!(ctx.net_fee()?, 150);
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let payment = Coin::new(coin.coin_id(), payment_puzzle_hash, 600);
+        let change = Coin::new(coin.coin_id(), puzzle_hash, 250);
+        assert!(sim.coin_state(payment.coin_id()).is_some());
+        assert!(sim.coin_state(change.coin_id()).is_some());
+
+        Ok(())
+    }
 }
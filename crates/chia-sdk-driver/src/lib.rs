@@ -1,5 +1,8 @@
 #![doc = include_str!("../docs.md")]
 
+mod announcement_validation;
+mod cost;
+mod disassemble;
 mod driver_error;
 mod hashed_ptr;
 mod layer;
@@ -9,8 +12,13 @@ mod primitives;
 mod puzzle;
 mod spend;
 mod spend_context;
+mod spend_context_pool;
 mod spend_with_conditions;
+mod validation_error;
 
+pub use announcement_validation::*;
+pub use cost::*;
+pub use disassemble::*;
 pub use driver_error::*;
 pub use hashed_ptr::*;
 pub use layer::*;
@@ -20,4 +28,6 @@ pub use primitives::*;
 pub use puzzle::*;
 pub use spend::*;
 pub use spend_context::*;
+pub use spend_context_pool::*;
 pub use spend_with_conditions::*;
+pub use validation_error::*;
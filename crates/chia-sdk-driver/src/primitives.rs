@@ -1,14 +1,18 @@
 mod cat;
+mod clawback;
 mod did;
 mod intermediate_launcher;
 mod launcher;
 mod nft;
+mod parsed_puzzle;
 
 pub use cat::*;
+pub use clawback::*;
 pub use did::*;
 pub use intermediate_launcher::*;
 pub use launcher::*;
 pub use nft::*;
+pub use parsed_puzzle::*;
 
 #[cfg(feature = "chip-0035")]
 mod datalayer;
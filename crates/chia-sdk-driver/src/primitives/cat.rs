@@ -11,9 +11,11 @@ use clvmr::{Allocator, NodePtr};
 
 use crate::{CatLayer, DriverError, Layer, Puzzle, Spend, SpendContext};
 
+mod cat_info;
 mod cat_spend;
 mod single_cat_spend;
 
+pub use cat_info::*;
 pub use cat_spend::*;
 pub use single_cat_spend::*;
 
@@ -53,12 +55,12 @@ impl Cat {
             args: GenesisByCoinIdTailArgs::new(parent_coin_id),
         })?;
 
-        Self::create_and_spend_eve(
+        Self::issue_with_tail(
             ctx,
             parent_coin_id,
-            ctx.tree_hash(tail).into(),
+            Spend::new(tail, NodePtr::NIL),
             amount,
-            extra_conditions.run_cat_tail(tail, NodePtr::NIL),
+            extra_conditions,
         )
     }
 
@@ -76,12 +78,30 @@ impl Cat {
             args: EverythingWithSignatureTailArgs::new(public_key),
         })?;
 
+        Self::issue_with_tail(
+            ctx,
+            parent_coin_id,
+            Spend::new(tail, NodePtr::NIL),
+            amount,
+            extra_conditions,
+        )
+    }
+
+    /// Issues an eve CAT with an arbitrary TAIL, which is revealed and run with the given solution.
+    /// The asset id is the tree hash of the TAIL puzzle.
+    pub fn issue_with_tail(
+        ctx: &mut SpendContext,
+        parent_coin_id: Bytes32,
+        tail: Spend,
+        amount: u64,
+        extra_conditions: Conditions,
+    ) -> Result<(Conditions, Cat), DriverError> {
         Self::create_and_spend_eve(
             ctx,
             parent_coin_id,
-            ctx.tree_hash(tail).into(),
+            ctx.tree_hash(tail.puzzle).into(),
             amount,
-            extra_conditions.run_cat_tail(tail, NodePtr::NIL),
+            extra_conditions.run_cat_tail(tail.puzzle, tail.solution),
         )
     }
 
@@ -343,6 +363,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_custom_tail_cat() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let tail = ctx.alloc(&clvm_quote!(()))?;
+        let asset_id = ctx.tree_hash(tail).into();
+
+        let (issue_cat, cat) = Cat::issue_with_tail(
+            ctx,
+            coin.coin_id(),
+            Spend::new(tail, NodePtr::NIL),
+            1,
+            Conditions::new().create_coin(puzzle_hash, 1, vec![puzzle_hash.into()]),
+        )?;
+        p2.spend(ctx, coin, issue_cat)?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let cat = cat.wrapped_child(puzzle_hash, 1);
+        assert_eq!(cat.asset_id, asset_id);
+        assert!(sim.coin_state(cat.coin.coin_id()).is_some());
+
+        Ok(())
+    }
+
     #[test]
     fn test_missing_cat_issuance_output() -> anyhow::Result<()> {
         let mut sim = Simulator::new();
@@ -504,6 +551,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_spend_simulated_cat_coin() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, _coin) = sim.new_p2(0)?;
+        let p2 = StandardLayer::new(pk);
+
+        let asset_id = Bytes32::new([42; 32]);
+        let (coin, lineage_proof) = sim.new_cat_coin(asset_id, puzzle_hash, 1000);
+        let cat = Cat::new(coin, Some(lineage_proof), asset_id, puzzle_hash);
+
+        let inner_spend = p2.spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(puzzle_hash, 1000, vec![puzzle_hash.into()]),
+        )?;
+        Cat::spend_all(ctx, &[CatSpend::new(cat, inner_spend)])?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let child = cat.wrapped_child(puzzle_hash, 1000);
+        assert!(sim.coin_state(child.coin.coin_id()).is_some());
+
+        Ok(())
+    }
+
     #[test]
     fn test_cat_melt() -> anyhow::Result<()> {
         let mut sim = Simulator::new();
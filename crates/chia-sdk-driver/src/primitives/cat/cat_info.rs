@@ -0,0 +1,36 @@
+use chia_protocol::Bytes32;
+use clvm_utils::ToTreeHash;
+use clvmr::Allocator;
+
+use crate::{CatLayer, DriverError, Layer, Puzzle};
+
+/// The information that can be determined about a CAT from its puzzle alone.
+/// Unlike [`Cat`](crate::Cat), this doesn't include the coin or lineage proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatInfo {
+    pub asset_id: Bytes32,
+    pub p2_puzzle_hash: Bytes32,
+}
+
+impl CatInfo {
+    pub fn new(asset_id: Bytes32, p2_puzzle_hash: Bytes32) -> Self {
+        Self {
+            asset_id,
+            p2_puzzle_hash,
+        }
+    }
+
+    /// Parses the CAT info and p2 puzzle that corresponds to the p2 puzzle hash.
+    pub fn parse(
+        allocator: &Allocator,
+        puzzle: Puzzle,
+    ) -> Result<Option<(Self, Puzzle)>, DriverError> {
+        let Some(layer) = CatLayer::<Puzzle>::parse_puzzle(allocator, puzzle)? else {
+            return Ok(None);
+        };
+
+        let info = Self::new(layer.asset_id, layer.inner_puzzle.tree_hash().into());
+
+        Ok(Some((info, layer.inner_puzzle)))
+    }
+}
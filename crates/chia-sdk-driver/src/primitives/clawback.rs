@@ -0,0 +1,427 @@
+use chia_protocol::Bytes32;
+use chia_sdk_types::AssertSecondsRelative;
+use clvm_traits::{FromClvm, ToClvm};
+use clvm_utils::{CurriedProgram, ToTreeHash, TreeHash};
+use clvmr::NodePtr;
+use hex_literal::hex;
+
+use crate::{
+    DriverError, Layer, MerkleTree, P2OneOfMany, P2OneOfManyArgs, P2OneOfManySolution, Spend,
+    SpendContext, P2_ONE_OF_MANY_PUZZLE_HASH,
+};
+
+/// A clawback locks a coin such that the recipient can claim it once the timelock has passed,
+/// and the sender can claw it back at any time. This uses the same puzzles as the reference wallet,
+/// which is a [`P2OneOfMany`] with one path for the recipient and one for the sender.
+///
+/// The puzzle only commits to the merkle root of the paths, so it can't be parsed back into a [`Clawback`].
+/// The sender and recipient have to know the parameters ahead of time, for example from the memos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clawback {
+    /// The puzzle hash of the sender, which can spend the coin at any time.
+    pub sender_puzzle_hash: Bytes32,
+    /// The puzzle hash of the recipient, which can spend the coin after the timelock.
+    pub recipient_puzzle_hash: Bytes32,
+    /// The number of seconds after the coin's creation at which it can be claimed.
+    pub timelock: u64,
+}
+
+impl Clawback {
+    pub fn new(sender_puzzle_hash: Bytes32, recipient_puzzle_hash: Bytes32, timelock: u64) -> Self {
+        Self {
+            sender_puzzle_hash,
+            recipient_puzzle_hash,
+            timelock,
+        }
+    }
+
+    /// The puzzle hash of the path which lets the recipient claim the coin after the timelock.
+    pub fn claim_path_hash(&self) -> TreeHash {
+        AugmentedConditionArgs::new(
+            AssertSecondsRelative::new(self.timelock),
+            P2PuzzleHashArgs::curry_tree_hash(self.recipient_puzzle_hash),
+        )
+        .curry_tree_hash()
+    }
+
+    /// The puzzle hash of the path which lets the sender claw back the coin.
+    pub fn claw_back_path_hash(&self) -> TreeHash {
+        P2PuzzleHashArgs::curry_tree_hash(self.sender_puzzle_hash)
+    }
+
+    pub fn merkle_tree(&self) -> MerkleTree {
+        MerkleTree::new(&[
+            self.claim_path_hash().into(),
+            self.claw_back_path_hash().into(),
+        ])
+    }
+
+    /// The underlying [`P2OneOfMany`] layer, which is curried with the merkle root of both paths.
+    pub fn to_layer(&self) -> P2OneOfMany {
+        P2OneOfMany {
+            merkle_root: self.merkle_tree().root,
+        }
+    }
+
+    pub fn construct_puzzle(&self, ctx: &mut SpendContext) -> Result<NodePtr, DriverError> {
+        self.to_layer().construct_puzzle(ctx)
+    }
+
+    /// Spends the coin with the sender's inner spend, which is valid at any time.
+    pub fn claw_back(
+        &self,
+        ctx: &mut SpendContext,
+        sender_spend: Spend,
+    ) -> Result<Spend, DriverError> {
+        let puzzle = CurriedProgram {
+            program: ctx.p2_puzzle_hash_puzzle()?,
+            args: P2PuzzleHashArgs::new(self.sender_puzzle_hash),
+        };
+        let puzzle = ctx.alloc(&puzzle)?;
+        let solution = ctx.alloc(&P2PuzzleHashSolution {
+            inner_puzzle: sender_spend.puzzle,
+            inner_solution: sender_spend.solution,
+        })?;
+
+        self.spend_path(
+            ctx,
+            self.claw_back_path_hash(),
+            Spend::new(puzzle, solution),
+        )
+    }
+
+    /// Spends the coin with the recipient's inner spend, which is only valid after the timelock.
+    pub fn claim(
+        &self,
+        ctx: &mut SpendContext,
+        recipient_spend: Spend,
+    ) -> Result<Spend, DriverError> {
+        let inner_puzzle = CurriedProgram {
+            program: ctx.p2_puzzle_hash_puzzle()?,
+            args: P2PuzzleHashArgs::new(self.recipient_puzzle_hash),
+        };
+        let puzzle = CurriedProgram {
+            program: ctx.augmented_condition_puzzle()?,
+            args: AugmentedConditionArgs::new(
+                AssertSecondsRelative::new(self.timelock),
+                inner_puzzle,
+            ),
+        };
+        let puzzle = ctx.alloc(&puzzle)?;
+        let solution = ctx.alloc(&AugmentedConditionSolution {
+            inner_solution: P2PuzzleHashSolution {
+                inner_puzzle: recipient_spend.puzzle,
+                inner_solution: recipient_spend.solution,
+            },
+        })?;
+
+        self.spend_path(ctx, self.claim_path_hash(), Spend::new(puzzle, solution))
+    }
+
+    fn spend_path(
+        &self,
+        ctx: &mut SpendContext,
+        path_hash: TreeHash,
+        path_spend: Spend,
+    ) -> Result<Spend, DriverError> {
+        let merkle_proof = self
+            .merkle_tree()
+            .get_proof(path_hash.into())
+            .ok_or(DriverError::MissingMerkleProof(path_hash.into()))?;
+
+        self.to_layer().construct_spend(
+            ctx,
+            P2OneOfManySolution {
+                merkle_proof,
+                puzzle: path_spend.puzzle,
+                solution: path_spend.solution,
+            },
+        )
+    }
+}
+
+impl ToTreeHash for Clawback {
+    fn tree_hash(&self) -> TreeHash {
+        CurriedProgram {
+            program: P2_ONE_OF_MANY_PUZZLE_HASH,
+            args: P2OneOfManyArgs {
+                merkle_root: self.merkle_tree().root,
+            },
+        }
+        .tree_hash()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(curry)]
+pub struct AugmentedConditionArgs<T, I> {
+    pub condition: T,
+    pub inner_puzzle: I,
+}
+
+impl<T, I> AugmentedConditionArgs<T, I> {
+    pub fn new(condition: T, inner_puzzle: I) -> Self {
+        Self {
+            condition,
+            inner_puzzle,
+        }
+    }
+}
+
+impl<T> AugmentedConditionArgs<T, TreeHash>
+where
+    T: ToTreeHash,
+{
+    pub fn curry_tree_hash(&self) -> TreeHash {
+        CurriedProgram {
+            program: AUGMENTED_CONDITION_PUZZLE_HASH,
+            args: AugmentedConditionArgs::new(self.condition.tree_hash(), self.inner_puzzle),
+        }
+        .tree_hash()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct AugmentedConditionSolution<S> {
+    pub inner_solution: S,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(curry)]
+pub struct P2PuzzleHashArgs {
+    pub inner_puzzle_hash: Bytes32,
+}
+
+impl P2PuzzleHashArgs {
+    pub fn new(inner_puzzle_hash: Bytes32) -> Self {
+        Self { inner_puzzle_hash }
+    }
+
+    pub fn curry_tree_hash(inner_puzzle_hash: Bytes32) -> TreeHash {
+        CurriedProgram {
+            program: P2_PUZZLE_HASH_PUZZLE_HASH,
+            args: Self::new(inner_puzzle_hash),
+        }
+        .tree_hash()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct P2PuzzleHashSolution<P, S> {
+    pub inner_puzzle: P,
+    pub inner_solution: S,
+}
+
+pub const AUGMENTED_CONDITION_PUZZLE: [u8; 13] = hex!("ff04ff02ffff02ff05ff0b8080");
+
+pub const AUGMENTED_CONDITION_PUZZLE_HASH: TreeHash = TreeHash::new(hex!(
+    "d303eafa617bedf0bc05850dd014e10fbddf622187dc07891a2aacba9d8a93f6"
+));
+
+pub const P2_PUZZLE_HASH_PUZZLE: [u8; 143] = hex!(
+    "
+    ff02ffff01ff02ffff03ffff09ff05ffff02ff02ffff04ff02ffff04ff0bff80
+    80808080ffff01ff02ff0bff1780ffff01ff088080ff0180ffff04ffff01ff02
+    ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff02ffff04ff02ffff04ff
+    09ff80808080ffff02ff02ffff04ff02ffff04ff0dff8080808080ffff01ff0b
+    ffff0101ff058080ff0180ff018080
+    "
+);
+
+pub const P2_PUZZLE_HASH_PUZZLE_HASH: TreeHash = TreeHash::new(hex!(
+    "13e29a62b42cd2ef72a79e4bacdc59733ca6310d65af83d349360d36ec622363"
+));
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::SecretKey;
+    use chia_consensus::gen::validation_error::ErrorCode;
+    use chia_protocol::Coin;
+    use chia_puzzles::standard::StandardArgs;
+    use chia_sdk_test::{test_secret_keys, Simulator, SimulatorError};
+    use chia_sdk_types::Conditions;
+
+    use crate::{assert_puzzle_hash, SpendWithConditions, StandardLayer};
+
+    use super::*;
+
+    const TIMELOCK: u64 = 3600;
+
+    #[test]
+    fn test_puzzle_hash() -> anyhow::Result<()> {
+        assert_puzzle_hash!(AUGMENTED_CONDITION_PUZZLE => AUGMENTED_CONDITION_PUZZLE_HASH);
+        assert_puzzle_hash!(P2_PUZZLE_HASH_PUZZLE => P2_PUZZLE_HASH_PUZZLE_HASH);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clawback_tree_hash() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+
+        let clawback = Clawback::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), TIMELOCK);
+        let ptr = clawback.construct_puzzle(&mut ctx)?;
+        assert_eq!(ctx.tree_hash(ptr), clawback.tree_hash());
+
+        let p2_puzzle_hash_puzzle = ctx.p2_puzzle_hash_puzzle()?;
+        let augmented_condition_puzzle = ctx.augmented_condition_puzzle()?;
+
+        let claim_path = ctx.alloc(&CurriedProgram {
+            program: augmented_condition_puzzle,
+            args: AugmentedConditionArgs::new(
+                AssertSecondsRelative::new(TIMELOCK),
+                CurriedProgram {
+                    program: p2_puzzle_hash_puzzle,
+                    args: P2PuzzleHashArgs::new(clawback.recipient_puzzle_hash),
+                },
+            ),
+        })?;
+        assert_eq!(ctx.tree_hash(claim_path), clawback.claim_path_hash());
+
+        Ok(())
+    }
+
+    fn create_clawback(
+        sim: &mut Simulator,
+        ctx: &mut SpendContext,
+        claim_after: u64,
+    ) -> anyhow::Result<(Vec<SecretKey>, Clawback, Coin)> {
+        let sks = test_secret_keys(2)?;
+        let sender = StandardLayer::new(sks[0].public_key());
+        let sender_puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(sks[0].public_key()).into();
+        let recipient_puzzle_hash = StandardArgs::curry_tree_hash(sks[1].public_key()).into();
+
+        let coin = sim.new_coin(sender_puzzle_hash, 1);
+        let clawback = Clawback::new(sender_puzzle_hash, recipient_puzzle_hash, TIMELOCK);
+        let clawback_puzzle_hash = clawback.tree_hash().into();
+
+        sender.spend(
+            ctx,
+            coin,
+            Conditions::new().create_coin(clawback_puzzle_hash, 1, Vec::new()),
+        )?;
+        sim.spend_coins(ctx.take(), &sks[..1])?;
+        sim.pass_time(claim_after);
+
+        let clawback_coin = Coin::new(coin.coin_id(), clawback_puzzle_hash, 1);
+
+        Ok((sks, clawback, clawback_coin))
+    }
+
+    #[test]
+    fn test_claw_back_before_timelock() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sks, clawback, coin) = create_clawback(&mut sim, ctx, TIMELOCK - 1)?;
+
+        let sender_spend = StandardLayer::new(sks[0].public_key()).spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(clawback.sender_puzzle_hash, 1, Vec::new()),
+        )?;
+        let spend = clawback.claw_back(ctx, sender_spend)?;
+        ctx.spend(coin, spend)?;
+
+        sim.spend_coins(ctx.take(), &sks[..1])?;
+        assert!(sim
+            .coin_state(coin.coin_id())
+            .unwrap()
+            .spent_height
+            .is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_claw_back_after_timelock() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sks, clawback, coin) = create_clawback(&mut sim, ctx, TIMELOCK)?;
+
+        let sender_spend = StandardLayer::new(sks[0].public_key()).spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(clawback.sender_puzzle_hash, 1, Vec::new()),
+        )?;
+        let spend = clawback.claw_back(ctx, sender_spend)?;
+        ctx.spend(coin, spend)?;
+
+        sim.spend_coins(ctx.take(), &sks[..1])?;
+        assert!(sim
+            .coin_state(coin.coin_id())
+            .unwrap()
+            .spent_height
+            .is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_claim_before_timelock() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sks, clawback, coin) = create_clawback(&mut sim, ctx, TIMELOCK - 1)?;
+
+        let recipient_spend = StandardLayer::new(sks[1].public_key()).spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(clawback.recipient_puzzle_hash, 1, Vec::new()),
+        )?;
+        let spend = clawback.claim(ctx, recipient_spend)?;
+        ctx.spend(coin, spend)?;
+
+        assert!(matches!(
+            sim.spend_coins(ctx.take(), &sks[1..]).unwrap_err(),
+            SimulatorError::Validation(ErrorCode::AssertSecondsRelativeFailed)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_claim_after_timelock() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sks, clawback, coin) = create_clawback(&mut sim, ctx, TIMELOCK)?;
+
+        let recipient_spend = StandardLayer::new(sks[1].public_key()).spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(clawback.recipient_puzzle_hash, 1, Vec::new()),
+        )?;
+        let spend = clawback.claim(ctx, recipient_spend)?;
+        ctx.spend(coin, spend)?;
+
+        sim.spend_coins(ctx.take(), &sks[1..])?;
+        assert!(sim
+            .coin_state(coin.coin_id())
+            .unwrap()
+            .spent_height
+            .is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sender_cannot_claim() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sks, clawback, coin) = create_clawback(&mut sim, ctx, TIMELOCK)?;
+
+        let sender_spend = StandardLayer::new(sks[0].public_key()).spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(clawback.sender_puzzle_hash, 1, Vec::new()),
+        )?;
+        let spend = clawback.claim(ctx, sender_spend)?;
+        ctx.spend(coin, spend)?;
+
+        assert!(matches!(
+            sim.spend_coins(ctx.take(), &sks[..1]).unwrap_err(),
+            SimulatorError::Signer(..)
+        ));
+
+        Ok(())
+    }
+}
@@ -1,18 +1,26 @@
 use chia_protocol::Coin;
-use chia_puzzles::{did::DidSolution, singleton::SingletonSolution, LineageProof, Proof};
+use chia_puzzles::{
+    did::DidSolution,
+    singleton::{SingletonArgs, SingletonSolution},
+    LineageProof, Proof,
+};
 use chia_sdk_types::{run_puzzle, Condition, Conditions};
 use clvm_traits::{FromClvm, ToClvm};
 use clvm_utils::{tree_hash, ToTreeHash};
 use clvmr::{Allocator, NodePtr};
 
 use crate::{
-    DidLayer, DriverError, Layer, Puzzle, SingletonLayer, Spend, SpendContext, SpendWithConditions,
+    DidLayer, DriverError, HashedPtr, Layer, Puzzle, SingletonLayer, Spend, SpendContext,
+    SpendWithConditions,
 };
 
 mod did_info;
 mod did_launcher;
+mod did_nft_mint;
+mod did_recovery;
 
 pub use did_info::*;
+pub use did_recovery::*;
 
 #[must_use]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -125,7 +133,36 @@ where
             ),
         )?;
 
-        Ok(self.wrapped_child().with_metadata(metadata))
+        let child = self.wrapped_child().with_metadata(metadata);
+
+        Ok(Did {
+            coin: Coin::new(
+                child.coin.parent_coin_info,
+                SingletonArgs::curry_tree_hash(child.info.launcher_id, new_inner_puzzle_hash)
+                    .into(),
+                child.coin.amount,
+            ),
+            ..child
+        })
+    }
+
+    /// Recreates this DID with new metadata which only needs to be convertible to CLVM.
+    /// The metadata is allocated and hashed in the [`SpendContext`], so the child DID tracks it as a [`HashedPtr`].
+    pub fn update_metadata<I, N>(
+        self,
+        ctx: &mut SpendContext,
+        inner: &I,
+        metadata: N,
+        extra_conditions: Conditions,
+    ) -> Result<Did<HashedPtr>, DriverError>
+    where
+        I: SpendWithConditions,
+        M: ToTreeHash,
+        N: ToClvm<Allocator>,
+    {
+        let metadata_ptr = ctx.alloc(&metadata)?;
+        let metadata = HashedPtr::from_ptr(&ctx.allocator, metadata_ptr);
+        self.update_with_metadata(ctx, inner, metadata, extra_conditions)
     }
 
     /// Creates a new DID coin with the given metadata.
@@ -335,6 +372,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_update_lazy_did_metadata() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, _puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let launcher = Launcher::new(coin.coin_id(), 1);
+        let (create_did, did) = launcher.create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+        sim.spend_coins(ctx.take(), &[sk.clone()])?;
+
+        let metadata = clvm_list!("Name".to_string(), 42);
+        let did = did.update_metadata(ctx, &p2, metadata.clone(), Conditions::new())?;
+        sim.spend_coins(ctx.take(), &[sk.clone()])?;
+        assert!(sim.coin_state(did.coin.coin_id()).is_some());
+
+        let expected_metadata = ctx.alloc(&metadata)?;
+        assert_eq!(
+            did.info.metadata.tree_hash(),
+            ctx.tree_hash(expected_metadata)
+        );
+
+        // Spend the updated DID so that its puzzle is revealed, then parse the new metadata back out of it.
+        let _did = did.update(ctx, &p2, Conditions::new())?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let puzzle_reveal = sim
+            .puzzle_reveal(did.coin.coin_id())
+            .expect("missing puzzle")
+            .to_clvm(&mut ctx.allocator)?;
+        let puzzle = Puzzle::parse(&ctx.allocator, puzzle_reveal);
+
+        let (info, _p2_puzzle) =
+            DidInfo::<HashedPtr>::parse(&ctx.allocator, puzzle)?.expect("could not parse did");
+        assert_eq!(info, did.info);
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_did() -> anyhow::Result<()> {
         let mut sim = Simulator::new();
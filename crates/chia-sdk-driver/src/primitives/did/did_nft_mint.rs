@@ -0,0 +1,148 @@
+use chia_protocol::Bytes32;
+use chia_sdk_types::Conditions;
+use clvm_traits::{FromClvm, ToClvm};
+use clvm_utils::ToTreeHash;
+use clvmr::Allocator;
+
+use crate::{
+    DidOwner, DriverError, IntermediateLauncher, Nft, NftMint, SpendContext, SpendWithConditions,
+};
+
+use super::Did;
+
+impl<M> Did<M>
+where
+    M: ToClvm<Allocator> + FromClvm<Allocator> + ToTreeHash + Clone,
+{
+    /// Mints an NFT for each of the metadata values, with this DID as the owner, and recreates the DID.
+    ///
+    /// Each NFT is launched from its own intermediate launcher, so the launcher ids are unique.
+    /// The DID spend asserts the announcements of the NFTs being assigned to it, which proves that
+    /// they were minted by the DID. Royalties are paid to the p2 puzzle hash of the NFTs.
+    pub fn mint_nfts<I, N>(
+        self,
+        ctx: &mut SpendContext,
+        inner: &I,
+        metadatas: Vec<N>,
+        p2_puzzle_hash: Bytes32,
+        royalty_ten_thousandths: u16,
+        extra_conditions: Conditions,
+    ) -> Result<(Did<M>, Vec<Nft<N>>), DriverError>
+    where
+        I: SpendWithConditions,
+        N: ToClvm<Allocator> + FromClvm<Allocator> + ToTreeHash + Clone,
+    {
+        let owner = DidOwner::from_did_info(&self.info);
+        let mint_total = metadatas.len();
+
+        let mut conditions = extra_conditions;
+        let mut nfts = Vec::with_capacity(mint_total);
+
+        for (mint_number, metadata) in metadatas.into_iter().enumerate() {
+            let (mint_nft, nft) =
+                IntermediateLauncher::new(self.coin.coin_id(), mint_number, mint_total)
+                    .create(ctx)?
+                    .mint_nft(
+                        ctx,
+                        NftMint::new(
+                            metadata,
+                            p2_puzzle_hash,
+                            royalty_ten_thousandths,
+                            Some(owner),
+                        ),
+                    )?;
+
+            conditions = conditions.extend(mint_nft);
+            nfts.push(nft);
+        }
+
+        let did = self.update(ctx, inner, conditions)?;
+
+        Ok((did, nfts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_puzzles::nft::NftMetadata;
+    use chia_sdk_test::Simulator;
+
+    use crate::{Launcher, Puzzle, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_mint_nfts_from_did() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        // One mojo for the DID, and the rest is used by the launchers of the NFTs.
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(6)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 1).create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+
+        let metadatas: Vec<NftMetadata> = (1..=5)
+            .map(|edition_number| NftMetadata {
+                edition_number,
+                edition_total: 5,
+                ..Default::default()
+            })
+            .collect();
+
+        let did_id = did.info.launcher_id;
+        let (did, nfts) = did.mint_nfts(
+            ctx,
+            &p2,
+            metadatas.clone(),
+            puzzle_hash,
+            300,
+            Conditions::new(),
+        )?;
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        assert!(sim.coin_state(did.coin.coin_id()).is_some());
+        assert_eq!(nfts.len(), 5);
+
+        for (nft, metadata) in nfts.iter().zip(metadatas) {
+            assert_eq!(nft.info.current_owner, Some(did_id));
+            assert_eq!(nft.info.metadata, metadata);
+
+            // Parse the NFT from the eve spend on chain, to check the owner that was assigned.
+            let parent_coin = sim
+                .coin_state(nft.coin.parent_coin_info)
+                .expect("missing eve coin")
+                .coin;
+
+            let mut allocator = Allocator::new();
+
+            let puzzle_reveal = sim
+                .puzzle_reveal(parent_coin.coin_id())
+                .expect("missing puzzle")
+                .to_clvm(&mut allocator)?;
+
+            let solution = sim
+                .solution(parent_coin.coin_id())
+                .expect("missing solution")
+                .to_clvm(&mut allocator)?;
+
+            let puzzle = Puzzle::parse(&allocator, puzzle_reveal);
+
+            let parsed =
+                Nft::<NftMetadata>::parse_child(&mut allocator, parent_coin, puzzle, solution)?
+                    .expect("could not parse nft");
+
+            assert_eq!(parsed, *nft);
+            assert_eq!(parsed.info.current_owner, Some(did_id));
+        }
+
+        let launcher_ids: Vec<Bytes32> = nfts.iter().map(|nft| nft.info.launcher_id).collect();
+        for (i, launcher_id) in launcher_ids.iter().enumerate() {
+            assert!(!launcher_ids[i + 1..].contains(launcher_id));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use chia_bls::PublicKey;
+use chia_protocol::{Bytes32, Coin};
+use chia_puzzles::{
+    singleton::{SingletonArgs, SingletonSolution},
+    Proof,
+};
+use chia_sdk_types::Conditions;
+use clvm_traits::{apply_constants, clvm_quote, FromClvm, ToClvm};
+use clvm_utils::ToTreeHash;
+use clvmr::{Allocator, NodePtr};
+
+use crate::{DriverError, Layer, Spend, SpendContext, SpendWithConditions};
+
+use super::{Did, DidInfo};
+
+/// An approval from one of the DIDs in a recovery list, allowing the recovering DID to move to a new inner puzzle hash.
+/// This is created by [`Did::create_attestment`] and consumed by [`Did::recover`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DidAttestment {
+    /// The launcher id of the attesting DID.
+    pub launcher_id: Bytes32,
+    /// The parent coin id of the attesting DID coin which created the message coin.
+    pub parent_coin_info: Bytes32,
+    /// The inner puzzle hash of the attesting DID coin.
+    pub inner_puzzle_hash: Bytes32,
+    /// The amount of the attesting DID coin.
+    pub amount: u64,
+}
+
+/// The DID inner puzzle solution for recovery mode.
+#[derive(ToClvm, FromClvm)]
+#[apply_constants]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[clvm(list)]
+pub struct DidRecoveryInnerSolution {
+    #[clvm(constant = 0)]
+    pub mode: u8,
+    pub amount: u64,
+    pub new_inner_puzzle_hash: Bytes32,
+    /// The attesting coin info for each DID in the recovery list, in the same order.
+    pub attestments: Vec<DidAttestmentCoin>,
+    pub public_key: PublicKey,
+    pub recovery_list: Vec<Bytes32>,
+    pub coin_id: Bytes32,
+}
+
+/// The information needed by the recovering DID to calculate the coin id of an attesting DID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(list)]
+pub struct DidAttestmentCoin {
+    pub parent_coin_info: Bytes32,
+    pub inner_puzzle_hash: Bytes32,
+    pub amount: u64,
+}
+
+impl<M> Did<M>
+where
+    M: ToClvm<Allocator> + FromClvm<Allocator> + ToTreeHash + Clone,
+{
+    /// Attests to the recovery of another DID, which must have this DID in its recovery list.
+    ///
+    /// This DID is recreated, and an ephemeral message coin is created and spent alongside it.
+    /// The message coin requires a signature of the new inner puzzle hash by the given public key,
+    /// which is the key that the new owner will use to complete the recovery.
+    pub fn create_attestment<I>(
+        self,
+        ctx: &mut SpendContext,
+        inner: &I,
+        recovering_coin_id: Bytes32,
+        new_inner_puzzle_hash: Bytes32,
+        public_key: PublicKey,
+    ) -> Result<(Did<M>, DidAttestment), DriverError>
+    where
+        I: SpendWithConditions,
+    {
+        let message_puzzle = ctx.alloc(&clvm_quote!(Conditions::new()
+            .create_coin_announcement(recovering_coin_id.into())
+            .agg_sig_unsafe(public_key, new_inner_puzzle_hash.into())))?;
+        let message_puzzle_hash = ctx.tree_hash(message_puzzle).into();
+
+        let attestment = DidAttestment {
+            launcher_id: self.info.launcher_id,
+            parent_coin_info: self.coin.parent_coin_info,
+            inner_puzzle_hash: self.info.inner_puzzle_hash().into(),
+            amount: self.coin.amount,
+        };
+
+        let message_coin = Coin::new(self.coin.coin_id(), message_puzzle_hash, 0);
+        ctx.spend(message_coin, Spend::new(message_puzzle, NodePtr::NIL))?;
+
+        let did = self.update(
+            ctx,
+            inner,
+            Conditions::new().create_coin(message_puzzle_hash, 0, Vec::new()),
+        )?;
+
+        Ok((did, attestment))
+    }
+
+    /// Recovers this DID to a new p2 puzzle hash, using attestments from the DIDs in its recovery list.
+    ///
+    /// The current p2 puzzle must be revealed, but doesn't need to be spent.
+    /// The recovery list must match the one the DID was created with, and every DID in it must have attested,
+    /// even if fewer verifications are required. The puzzle's branch for skipping a DID which didn't attest
+    /// drops one of its own arguments, so a recovery spend with a skipped entry always fails to run.
+    /// Attestments from DIDs that aren't in the list are ignored.
+    /// The new owner must sign the new inner puzzle hash with the public key used for the attestments.
+    pub fn recover(
+        self,
+        ctx: &mut SpendContext,
+        p2_puzzle: NodePtr,
+        recovery_list: Vec<Bytes32>,
+        new_p2_puzzle_hash: Bytes32,
+        public_key: PublicKey,
+        attestments: &[DidAttestment],
+    ) -> Result<Did<M>, DriverError> {
+        if self.info.num_verifications_required == 0
+            || self.info.recovery_list_hash != Some(recovery_list.tree_hash().into())
+        {
+            return Err(DriverError::InvalidRecoveryList);
+        }
+
+        let mut required = self.info.num_verifications_required;
+        let mut found = 0;
+        let mut attestment_coins = Vec::new();
+
+        for (listed, launcher_id) in (1..).zip(&recovery_list) {
+            required = required.max(listed);
+
+            if let Some(attestment) = attestments
+                .iter()
+                .find(|attestment| attestment.launcher_id == *launcher_id)
+            {
+                found += 1;
+                attestment_coins.push(DidAttestmentCoin {
+                    parent_coin_info: attestment.parent_coin_info,
+                    inner_puzzle_hash: attestment.inner_puzzle_hash,
+                    amount: attestment.amount,
+                });
+            }
+        }
+
+        if found < required {
+            return Err(DriverError::InsufficientAttestments { required, found });
+        }
+
+        let new_info = DidInfo {
+            p2_puzzle_hash: new_p2_puzzle_hash,
+            ..self.info.clone()
+        };
+        let new_inner_puzzle_hash = new_info.inner_puzzle_hash();
+
+        let puzzle = self
+            .info
+            .clone()
+            .into_layers(p2_puzzle)
+            .construct_puzzle(ctx)?;
+
+        let inner_solution = ctx.alloc(&DidRecoveryInnerSolution {
+            amount: self.coin.amount,
+            new_inner_puzzle_hash: new_inner_puzzle_hash.into(),
+            attestments: attestment_coins,
+            public_key,
+            recovery_list,
+            coin_id: self.coin.coin_id(),
+        })?;
+
+        let solution = ctx.alloc(&SingletonSolution {
+            lineage_proof: self.proof,
+            amount: self.coin.amount,
+            inner_solution,
+        })?;
+
+        ctx.spend(self.coin, Spend::new(puzzle, solution))?;
+
+        let puzzle_hash =
+            SingletonArgs::curry_tree_hash(self.info.launcher_id, new_inner_puzzle_hash);
+
+        Ok(Did::new(
+            Coin::new(self.coin.coin_id(), puzzle_hash.into(), self.coin.amount),
+            Proof::Lineage(self.child_lineage_proof()),
+            new_info,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_puzzles::standard::StandardArgs;
+    use chia_sdk_test::{test_secret_keys, Simulator};
+
+    use crate::{Launcher, StandardLayer};
+
+    use super::*;
+
+    #[test]
+    fn test_did_recovery() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sk, pk, _puzzle_hash, coin) = sim.new_p2(16)?;
+        let p2 = StandardLayer::new(pk);
+
+        let new_sk = test_secret_keys(2)?.remove(1);
+        let new_public_key = new_sk.public_key();
+        let new_p2 = StandardLayer::new(new_public_key);
+
+        let mut parent_conditions = Conditions::new();
+        let mut recovery_dids = Vec::new();
+
+        for launcher in Launcher::batch(coin.coin_id(), vec![1, 3, 5])? {
+            let (create_did, did) = launcher.create_simple_did(ctx, &p2)?;
+            parent_conditions = parent_conditions.extend(create_did);
+            recovery_dids.push(did);
+        }
+
+        let recovery_list: Vec<Bytes32> = recovery_dids
+            .iter()
+            .map(|did| did.info.launcher_id)
+            .collect();
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 7).create_did(
+            ctx,
+            Some(recovery_list.tree_hash().into()),
+            2,
+            (),
+            &p2,
+        )?;
+        parent_conditions = parent_conditions.extend(create_did);
+
+        p2.spend(ctx, coin, parent_conditions)?;
+        sim.spend_coins(ctx.take(), &[sk.clone()])?;
+
+        let new_p2_puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(new_public_key).into();
+        let new_inner_puzzle_hash = DidInfo {
+            p2_puzzle_hash: new_p2_puzzle_hash,
+            ..did.info
+        }
+        .inner_puzzle_hash()
+        .into();
+
+        let mut attestments = Vec::new();
+
+        for recovery_did in recovery_dids {
+            let (_recovery_did, attestment) = recovery_did.create_attestment(
+                ctx,
+                &p2,
+                did.coin.coin_id(),
+                new_inner_puzzle_hash,
+                new_public_key,
+            )?;
+            attestments.push(attestment);
+        }
+
+        let p2_puzzle = p2.construct_puzzle(ctx)?;
+
+        // Only 2 verifications are required, but the third DID in the list must attest too.
+        assert!(matches!(
+            did.recover(
+                ctx,
+                p2_puzzle,
+                recovery_list.clone(),
+                new_p2_puzzle_hash,
+                new_public_key,
+                &attestments[..2],
+            ),
+            Err(DriverError::InsufficientAttestments {
+                required: 3,
+                found: 2
+            })
+        ));
+
+        assert!(matches!(
+            did.recover(
+                ctx,
+                p2_puzzle,
+                recovery_list[..1].to_vec(),
+                new_p2_puzzle_hash,
+                new_public_key,
+                &attestments,
+            ),
+            Err(DriverError::InvalidRecoveryList)
+        ));
+
+        let did = did.recover(
+            ctx,
+            p2_puzzle,
+            recovery_list,
+            new_p2_puzzle_hash,
+            new_public_key,
+            &attestments,
+        )?;
+        assert_eq!(did.info.p2_puzzle_hash, new_p2_puzzle_hash);
+
+        sim.spend_coins(ctx.take(), &[sk, new_sk.clone()])?;
+        assert!(sim.coin_state(did.coin.coin_id()).is_some());
+
+        // The recovered DID can now be spent by the new owner.
+        let _did = did.update(ctx, &new_p2, Conditions::new())?;
+        sim.spend_coins(ctx.take(), &[new_sk])?;
+
+        Ok(())
+    }
+}
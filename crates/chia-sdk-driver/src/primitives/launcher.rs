@@ -65,6 +65,28 @@ impl Launcher {
         )
     }
 
+    /// Creates a [`Launcher`] for each amount, all of which will be created by the same parent coin.
+    /// The amounts must be distinct, since otherwise the launcher coins would have the same id.
+    ///
+    /// Each launcher is spent separately, and the conditions returned by each spend must be included in the parent spend.
+    /// Since the parent asserts the announcement of every launcher, either all of the singletons are created or none are.
+    pub fn batch(parent_coin_id: Bytes32, amounts: Vec<u64>) -> Result<Vec<Self>, DriverError> {
+        let mut launchers: Vec<Self> = Vec::with_capacity(amounts.len());
+
+        for amount in amounts {
+            if launchers
+                .iter()
+                .any(|launcher| launcher.coin.amount == amount)
+            {
+                return Err(DriverError::DuplicateLauncherAmount(amount));
+            }
+
+            launchers.push(Self::new(parent_coin_id, amount));
+        }
+
+        Ok(launchers)
+    }
+
     /// The parent coin specified when constructing the [`Launcher`] will create the launcher coin.
     /// By default, no hint is used when creating the coin. To specify a hint, use [`Launcher::create_early_hinted`].
     ///
@@ -129,6 +151,8 @@ impl Launcher {
 
     /// Spends the launcher coin to create the eve singleton.
     /// Includes an optional metadata value that is traditionally a list of key value pairs.
+    ///
+    /// Returns [`DriverError::EvenSingletonAmount`] if the singleton amount is even, since it could never be spent.
     pub fn spend<T>(
         self,
         ctx: &mut SpendContext,
@@ -138,6 +162,10 @@ impl Launcher {
     where
         T: ToClvm<Allocator>,
     {
+        if self.singleton_amount % 2 == 0 {
+            return Err(DriverError::EvenSingletonAmount(self.singleton_amount));
+        }
+
         let singleton_puzzle_hash =
             SingletonArgs::curry_tree_hash(self.coin.coin_id(), singleton_inner_puzzle_hash.into())
                 .into();
@@ -218,4 +246,67 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_even_singleton_amount() {
+        let ctx = &mut SpendContext::new();
+
+        let launcher = Launcher::new(Bytes32::default(), 2);
+        assert!(matches!(
+            launcher.spend(ctx, Bytes32::default(), ()),
+            Err(DriverError::EvenSingletonAmount(2))
+        ));
+
+        let launcher = Launcher::new(Bytes32::default(), 1).with_singleton_amount(0);
+        assert!(matches!(
+            launcher.spend(ctx, Bytes32::default(), ()),
+            Err(DriverError::EvenSingletonAmount(0))
+        ));
+
+        assert_eq!(ctx.take().len(), 0);
+    }
+
+    #[test]
+    fn test_batch_singleton_launchers() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let (sk, pk, _puzzle_hash, coin) = sim.new_p2(9)?;
+
+        let ctx = &mut SpendContext::new();
+        let launchers = Launcher::batch(coin.coin_id(), vec![1, 3, 5])?;
+
+        let mut parent_conditions = Conditions::new();
+        let mut singletons = Vec::new();
+
+        for launcher in launchers {
+            let (conditions, singleton) = launcher.spend(ctx, Bytes32::default(), ())?;
+            parent_conditions = parent_conditions.extend(conditions);
+            singletons.push(singleton);
+        }
+
+        StandardLayer::new(pk).spend(ctx, coin, parent_conditions)?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        assert_ne!(
+            singletons[0].parent_coin_info,
+            singletons[1].parent_coin_info
+        );
+        assert_ne!(
+            singletons[1].parent_coin_info,
+            singletons[2].parent_coin_info
+        );
+
+        for singleton in singletons {
+            assert!(sim.coin_state(singleton.coin_id()).is_some());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_duplicate_amounts() {
+        assert!(matches!(
+            Launcher::batch(Bytes32::default(), vec![1, 3, 1]),
+            Err(DriverError::DuplicateLauncherAmount(1))
+        ));
+    }
 }
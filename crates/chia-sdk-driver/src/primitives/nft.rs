@@ -180,6 +180,52 @@ where
         ))
     }
 
+    /// Updates the metadata of this NFT without changing its p2 puzzle hash.
+    pub fn update_metadata<I>(
+        self,
+        ctx: &mut SpendContext,
+        inner: &I,
+        metadata_update: &MetadataUpdate,
+        extra_conditions: Conditions,
+    ) -> Result<Nft<M>, DriverError>
+    where
+        I: SpendWithConditions,
+        M: ToTreeHash,
+    {
+        let p2_puzzle_hash = self.info.p2_puzzle_hash;
+        let metadata_update = metadata_update.spend(ctx)?;
+        self.transfer_with_metadata(
+            ctx,
+            inner,
+            p2_puzzle_hash,
+            metadata_update,
+            extra_conditions,
+        )
+    }
+
+    /// Adds a URI to the metadata of this NFT with the standard metadata updater.
+    ///
+    /// The new URI is prepended to the list by the updater puzzle, and all other URIs and hashes are kept.
+    pub fn add_uri<I>(
+        self,
+        ctx: &mut SpendContext,
+        inner: &I,
+        kind: UriKind,
+        uri: String,
+        extra_conditions: Conditions,
+    ) -> Result<Nft<M>, DriverError>
+    where
+        I: SpendWithConditions,
+        M: ToTreeHash,
+    {
+        self.update_metadata(
+            ctx,
+            inner,
+            &MetadataUpdate::new_uri(kind, uri),
+            extra_conditions,
+        )
+    }
+
     /// Transfers this NFT to a new p2 puzzle hash.
     ///
     /// Note: This does not update the metadata. If you update the metadata manually, the child will be incorrect.
@@ -212,6 +258,25 @@ where
         Ok(self.wrapped_child(p2_puzzle_hash, self.info.current_owner, metadata))
     }
 
+    /// Assigns this NFT to a DID, or clears its DID owner if [`None`], without changing its p2 puzzle hash.
+    /// Returns a list of conditions to be used in the DID spend, which approves the assignment.
+    ///
+    /// When clearing the owner, no DID spend is required and the returned conditions can be ignored.
+    pub fn assign_did<I>(
+        self,
+        ctx: &mut SpendContext,
+        inner: &I,
+        owner: Option<DidOwner>,
+        extra_conditions: Conditions,
+    ) -> Result<(Conditions, Nft<M>), DriverError>
+    where
+        M: ToTreeHash,
+        I: SpendWithConditions,
+    {
+        let p2_puzzle_hash = self.info.p2_puzzle_hash;
+        self.transfer_to_did(ctx, inner, p2_puzzle_hash, owner, extra_conditions)
+    }
+
     /// Transfers this NFT to a new p2 puzzle hash and updates the DID owner.
     /// Returns a list of conditions to be used in the DID spend.
     ///
@@ -334,10 +399,16 @@ where
         }
 
         if let Some(new_metadata) = new_metadata {
+            let metadata_updater_solution = clvm_list!(
+                &layers.inner_puzzle.metadata,
+                layers.inner_puzzle.metadata_updater_puzzle_hash,
+                new_metadata.updater_solution
+            )
+            .to_clvm(allocator)?;
             let output = run_puzzle(
                 allocator,
                 new_metadata.updater_puzzle_reveal,
-                new_metadata.updater_solution,
+                metadata_updater_solution,
             )?;
 
             let output =
@@ -422,6 +493,62 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_nft_update_metadata_and_transfer() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 1).create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+
+        // The metadata updater only prepends to URI lists which are already present.
+        let metadata = NftMetadata {
+            data_uris: vec!["example.org".to_string()],
+            ..Default::default()
+        };
+
+        let mint = NftMint::new(
+            metadata,
+            puzzle_hash,
+            300,
+            Some(DidOwner::from_did_info(&did.info)),
+        );
+
+        let (mint_nft, nft) = IntermediateLauncher::new(did.coin.coin_id(), 0, 1)
+            .create(ctx)?
+            .mint_nft(ctx, mint)?;
+        let _did = did.update(ctx, &p2, mint_nft)?;
+
+        let nft = nft.update_metadata(
+            ctx,
+            &p2,
+            &MetadataUpdate::NewDataUri("example.com".to_string()),
+            Conditions::new(),
+        )?;
+        assert_eq!(
+            nft.info.metadata.data_uris,
+            ["example.com".to_string(), "example.org".to_string()]
+        );
+        assert_eq!(nft.info.p2_puzzle_hash, puzzle_hash);
+
+        let new_puzzle_hash = Bytes32::new([42; 32]);
+        let nft = nft.transfer(ctx, &p2, new_puzzle_hash, Conditions::new())?;
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let coin_state = sim
+            .coin_state(nft.coin.coin_id())
+            .expect("missing nft coin");
+        assert_eq!(coin_state.coin, nft.coin);
+        assert_eq!(coin_state.spent_height, None);
+        assert_eq!(nft.info.p2_puzzle_hash, new_puzzle_hash);
+
+        Ok(())
+    }
+
     #[test]
     fn test_nft_lineage() -> anyhow::Result<()> {
         let mut sim = Simulator::new();
@@ -519,4 +646,149 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_nft_add_uri() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 1).create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+
+        let metadata = NftMetadata {
+            edition_number: 2,
+            edition_total: 10,
+            data_uris: vec!["https://example.com/data".to_string()],
+            data_hash: Some(Bytes32::new([1; 32])),
+            metadata_uris: vec!["https://example.com/metadata".to_string()],
+            metadata_hash: Some(Bytes32::new([2; 32])),
+            license_uris: Vec::new(),
+            license_hash: Some(Bytes32::new([3; 32])),
+        };
+
+        let (mint_nft, nft) = IntermediateLauncher::new(did.coin.coin_id(), 0, 1)
+            .create(ctx)?
+            .mint_nft(
+                ctx,
+                NftMint::new(
+                    metadata.clone(),
+                    puzzle_hash,
+                    300,
+                    Some(DidOwner::from_did_info(&did.info)),
+                ),
+            )?;
+        let _did = did.update(ctx, &p2, mint_nft)?;
+
+        let parent_coin = nft.coin;
+        let expected_nft = nft.add_uri(
+            ctx,
+            &p2,
+            UriKind::Data,
+            "https://mirror.example.com/data".to_string(),
+            Conditions::new(),
+        )?;
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let coin_state = sim
+            .coin_state(expected_nft.coin.coin_id())
+            .expect("missing nft coin");
+        assert_eq!(coin_state.spent_height, None);
+
+        let mut allocator = Allocator::new();
+
+        let puzzle_reveal = sim
+            .puzzle_reveal(parent_coin.coin_id())
+            .expect("missing puzzle")
+            .to_clvm(&mut allocator)?;
+
+        let solution = sim
+            .solution(parent_coin.coin_id())
+            .expect("missing solution")
+            .to_clvm(&mut allocator)?;
+
+        let puzzle = Puzzle::parse(&allocator, puzzle_reveal);
+
+        let nft = Nft::<NftMetadata>::parse_child(&mut allocator, parent_coin, puzzle, solution)?
+            .expect("could not parse nft");
+
+        assert_eq!(nft, expected_nft);
+        assert_eq!(
+            nft.info.metadata,
+            NftMetadata {
+                data_uris: vec![
+                    "https://mirror.example.com/data".to_string(),
+                    "https://example.com/data".to_string(),
+                ],
+                ..metadata
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nft_assign_and_clear_did() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 1).create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+
+        let (mint_nft, nft) = IntermediateLauncher::new(did.coin.coin_id(), 0, 1)
+            .create(ctx)?
+            .mint_nft(
+                ctx,
+                NftMint::new(NftMetadata::default(), puzzle_hash, 300, None),
+            )?;
+        let did = did.update(ctx, &p2, mint_nft)?;
+        assert_eq!(nft.info.current_owner, None);
+
+        // The DID approves the assignment by asserting the NFT's announcement.
+        let (assign_nft, nft) = nft.assign_did(
+            ctx,
+            &p2,
+            Some(DidOwner::from_did_info(&did.info)),
+            Conditions::new(),
+        )?;
+        let did_id = did.info.launcher_id;
+        let _did = did.update(ctx, &p2, assign_nft)?;
+        assert_eq!(nft.info.current_owner, Some(did_id));
+        assert_eq!(nft.info.p2_puzzle_hash, puzzle_hash);
+
+        let parent_coin = nft.coin;
+        let (_conditions, cleared_nft) = nft.assign_did(ctx, &p2, None, Conditions::new())?;
+        assert_eq!(cleared_nft.info.current_owner, None);
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        let mut allocator = Allocator::new();
+
+        let puzzle_reveal = sim
+            .puzzle_reveal(parent_coin.coin_id())
+            .expect("missing puzzle")
+            .to_clvm(&mut allocator)?;
+
+        let solution = sim
+            .solution(parent_coin.coin_id())
+            .expect("missing solution")
+            .to_clvm(&mut allocator)?;
+
+        let puzzle = Puzzle::parse(&allocator, puzzle_reveal);
+
+        let parsed =
+            Nft::<NftMetadata>::parse_child(&mut allocator, parent_coin, puzzle, solution)?
+                .expect("could not parse nft");
+
+        assert_eq!(parsed, cleared_nft);
+        assert!(sim.coin_state(cleared_nft.coin.coin_id()).is_some());
+
+        Ok(())
+    }
 }
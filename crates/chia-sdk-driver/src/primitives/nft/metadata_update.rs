@@ -1,5 +1,13 @@
 use crate::{DriverError, Spend, SpendContext};
 
+/// The kind of URI list in the standard NFT metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UriKind {
+    Data,
+    Metadata,
+    License,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MetadataUpdate {
     NewDataUri(String),
@@ -8,6 +16,15 @@ pub enum MetadataUpdate {
 }
 
 impl MetadataUpdate {
+    /// Creates an update which prepends a URI to the given list.
+    pub fn new_uri(kind: UriKind, uri: String) -> Self {
+        match kind {
+            UriKind::Data => Self::NewDataUri(uri),
+            UriKind::Metadata => Self::NewMetadataUri(uri),
+            UriKind::License => Self::NewLicenseUri(uri),
+        }
+    }
+
     pub fn spend(&self, ctx: &mut SpendContext) -> Result<Spend, DriverError> {
         let solution = ctx.alloc(&match self {
             Self::NewDataUri(uri) => ("u", uri),
@@ -0,0 +1,187 @@
+use chia_bls::PublicKey;
+use chia_protocol::CoinSpend;
+use clvm_traits::ToClvm;
+use clvmr::Allocator;
+
+use crate::{CatInfo, DidInfo, DriverError, HashedPtr, Layer, NftInfo, Puzzle, StandardLayer};
+
+/// The primitive that a coin spend's puzzle reveal was recognized as, by [`parse_spend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsedPuzzle {
+    Did(DidInfo<HashedPtr>),
+    Nft(NftInfo<HashedPtr>),
+    Cat(CatInfo),
+    /// The standard puzzle, with its synthetic key.
+    Standard(PublicKey),
+    Unknown,
+}
+
+/// Identifies which primitive a coin spend belongs to, by peeling the layers of its puzzle reveal.
+/// Only the outermost primitive is reported, so a DID or NFT won't be reported as [`ParsedPuzzle::Standard`]
+/// even if its p2 puzzle is the standard puzzle.
+pub fn parse_spend(
+    allocator: &mut Allocator,
+    coin_spend: &CoinSpend,
+) -> Result<ParsedPuzzle, DriverError> {
+    let ptr = coin_spend.puzzle_reveal.to_clvm(allocator)?;
+    let puzzle = Puzzle::parse(allocator, ptr);
+
+    if let Some((did, _p2_puzzle)) = DidInfo::<HashedPtr>::parse(allocator, puzzle)? {
+        return Ok(ParsedPuzzle::Did(did));
+    }
+
+    if let Some((nft, _p2_puzzle)) = NftInfo::<HashedPtr>::parse(allocator, puzzle)? {
+        return Ok(ParsedPuzzle::Nft(nft));
+    }
+
+    if let Some((cat, _p2_puzzle)) = CatInfo::parse(allocator, puzzle)? {
+        return Ok(ParsedPuzzle::Cat(cat));
+    }
+
+    if let Some(standard) = StandardLayer::parse_puzzle(allocator, puzzle)? {
+        return Ok(ParsedPuzzle::Standard(standard.synthetic_key));
+    }
+
+    Ok(ParsedPuzzle::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::{Bytes32, Coin, Program};
+    use chia_puzzles::nft::NftMetadata;
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::Conditions;
+
+    use crate::{Cat, DidOwner, IntermediateLauncher, Launcher, NftMint, SpendContext};
+
+    use super::*;
+
+    fn parse_coin(
+        ctx: &mut SpendContext,
+        coin_spends: &[CoinSpend],
+        coin: Coin,
+    ) -> anyhow::Result<ParsedPuzzle> {
+        let coin_spend = coin_spends
+            .iter()
+            .find(|coin_spend| coin_spend.coin == coin)
+            .expect("missing coin spend");
+        Ok(parse_spend(&mut ctx.allocator, coin_spend)?)
+    }
+
+    #[test]
+    fn test_parse_standard_and_did_spends() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let launcher = Launcher::new(coin.coin_id(), 1);
+        let launcher_coin = launcher.coin();
+        let (create_did, did) = launcher.create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+        let _did = did.update(ctx, &p2, Conditions::new())?;
+
+        let coin_spends = ctx.take();
+
+        assert_eq!(
+            parse_coin(ctx, &coin_spends, coin)?,
+            ParsedPuzzle::Standard(pk)
+        );
+        assert_eq!(
+            parse_coin(ctx, &coin_spends, launcher_coin)?,
+            ParsedPuzzle::Unknown
+        );
+
+        let ParsedPuzzle::Did(info) = parse_coin(ctx, &coin_spends, did.coin)? else {
+            panic!("expected a did spend");
+        };
+        assert_eq!(info.launcher_id, did.info.launcher_id);
+        assert_eq!(info.p2_puzzle_hash, puzzle_hash);
+
+        sim.spend_coins(coin_spends, &[sk])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_nft_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (create_did, did) = Launcher::new(coin.coin_id(), 1).create_simple_did(ctx, &p2)?;
+        p2.spend(ctx, coin, create_did)?;
+
+        let mint = NftMint::new(
+            NftMetadata::default(),
+            puzzle_hash,
+            300,
+            Some(DidOwner::from_did_info(&did.info)),
+        );
+
+        let (mint_nft, nft) = IntermediateLauncher::new(did.coin.coin_id(), 0, 1)
+            .create(ctx)?
+            .mint_nft(ctx, mint)?;
+        let _did = did.update(ctx, &p2, mint_nft)?;
+
+        let nft_coin = nft.coin;
+        let launcher_id = nft.info.launcher_id;
+        let _nft = nft.transfer(ctx, &p2, puzzle_hash, Conditions::new())?;
+
+        let coin_spends = ctx.take();
+
+        let ParsedPuzzle::Nft(info) = parse_coin(ctx, &coin_spends, nft_coin)? else {
+            panic!("expected an nft spend");
+        };
+        assert_eq!(info.launcher_id, launcher_id);
+        assert_eq!(info.p2_puzzle_hash, puzzle_hash);
+        assert_eq!(info.royalty_ten_thousandths, 300);
+
+        sim.spend_coins(coin_spends, &[sk])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_cat_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        let (issue_cat, cat) = Cat::single_issuance_eve(
+            ctx,
+            coin.coin_id(),
+            1,
+            Conditions::new().create_coin(puzzle_hash, 1, vec![puzzle_hash.into()]),
+        )?;
+        p2.spend(ctx, coin, issue_cat)?;
+
+        let coin_spends = ctx.take();
+
+        assert_eq!(
+            parse_coin(ctx, &coin_spends, cat.coin)?,
+            ParsedPuzzle::Cat(CatInfo::new(cat.asset_id, cat.p2_puzzle_hash))
+        );
+
+        sim.spend_coins(coin_spends, &[sk])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unknown_spend() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let coin_spend = CoinSpend::new(
+            Coin::new(Bytes32::default(), Bytes32::default(), 1),
+            Program::from(vec![1]),
+            Program::from(vec![0x80]),
+        );
+        assert_eq!(
+            parse_spend(&mut allocator, &coin_spend)?,
+            ParsedPuzzle::Unknown
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
+use chia_protocol::{Bytes32, Coin, Program};
 use clvm_traits::FromClvm;
 use clvm_utils::{tree_hash, CurriedProgram, ToTreeHash, TreeHash};
-use clvmr::{Allocator, NodePtr};
+use clvmr::{serde::node_from_bytes_backrefs, Allocator, NodePtr};
 
 use crate::{DriverError, Layer, SpendContext};
 
@@ -135,3 +136,54 @@ impl Layer for Puzzle {
         Ok(solution)
     }
 }
+
+/// Calculates the puzzle hash of a serialized puzzle reveal, which may be backref encoded.
+pub fn puzzle_hash_of_reveal(
+    allocator: &mut Allocator,
+    reveal: &Program,
+) -> Result<Bytes32, DriverError> {
+    let ptr = node_from_bytes_backrefs(allocator, reveal.as_ref())?;
+    Ok(tree_hash(allocator, ptr).into())
+}
+
+/// Checks whether a puzzle reveal corresponds to the coin's puzzle hash,
+/// such as when verifying a coin spend fetched from a peer.
+pub fn matches_coin(
+    allocator: &mut Allocator,
+    reveal: &Program,
+    coin: &Coin,
+) -> Result<bool, DriverError> {
+    Ok(puzzle_hash_of_reveal(allocator, reveal)? == coin.puzzle_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::PublicKey;
+    use chia_puzzles::standard::StandardArgs;
+
+    use crate::StandardLayer;
+
+    use super::*;
+
+    #[test]
+    fn test_matches_coin() -> anyhow::Result<()> {
+        let ctx = &mut SpendContext::new();
+
+        let puzzle = StandardLayer::new(PublicKey::default()).construct_puzzle(ctx)?;
+        let reveal = ctx.serialize(&puzzle)?;
+
+        let puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(PublicKey::default()).into();
+        assert_eq!(
+            puzzle_hash_of_reveal(&mut ctx.allocator, &reveal)?,
+            puzzle_hash
+        );
+
+        let coin = Coin::new(Bytes32::default(), puzzle_hash, 1);
+        assert!(matches_coin(&mut ctx.allocator, &reveal, &coin)?);
+
+        let other_coin = Coin::new(Bytes32::default(), Bytes32::new([42; 32]), 1);
+        assert!(!matches_coin(&mut ctx.allocator, &reveal, &other_coin)?);
+
+        Ok(())
+    }
+}
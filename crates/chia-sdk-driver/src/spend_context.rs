@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 use chia_protocol::{Coin, CoinSpend, Program};
 use chia_puzzles::{
@@ -21,15 +24,19 @@ use chia_puzzles::{
     },
     standard::{STANDARD_PUZZLE, STANDARD_PUZZLE_HASH},
 };
-use chia_sdk_types::run_puzzle;
+use chia_sdk_types::{run_puzzle, Condition};
 use clvm_traits::{FromClvm, ToClvm};
 use clvm_utils::{tree_hash, TreeHash};
-use clvmr::{serde::node_from_bytes, Allocator, NodePtr};
+use clvmr::{allocator, serde::node_from_bytes, Allocator, NodePtr};
+use tracing::instrument;
 
 use crate::{
-    DriverError, Spend, P2_DELEGATED_CONDITIONS_PUZZLE, P2_DELEGATED_CONDITIONS_PUZZLE_HASH,
-    P2_DELEGATED_SINGLETON_PUZZLE, P2_DELEGATED_SINGLETON_PUZZLE_HASH, P2_ONE_OF_MANY_PUZZLE,
-    P2_ONE_OF_MANY_PUZZLE_HASH, P2_SINGLETON_PUZZLE, P2_SINGLETON_PUZZLE_HASH,
+    DriverError, Spend, ValidationError, AUGMENTED_CONDITION_PUZZLE,
+    AUGMENTED_CONDITION_PUZZLE_HASH, P2_DELEGATED_CONDITIONS_PUZZLE,
+    P2_DELEGATED_CONDITIONS_PUZZLE_HASH, P2_DELEGATED_SINGLETON_PUZZLE,
+    P2_DELEGATED_SINGLETON_PUZZLE_HASH, P2_ONE_OF_MANY_PUZZLE, P2_ONE_OF_MANY_PUZZLE_HASH,
+    P2_PUZZLE_HASH_PUZZLE, P2_PUZZLE_HASH_PUZZLE_HASH, P2_SINGLETON_PUZZLE,
+    P2_SINGLETON_PUZZLE_HASH,
 };
 
 /// A wrapper around [`Allocator`] that caches puzzles and keeps track of a list of [`CoinSpend`].
@@ -50,16 +57,63 @@ impl SpendContext {
         self.coin_spends.iter()
     }
 
+    /// Swaps out the underlying [`Allocator`], returning the old one.
+    /// The puzzle cache is cleared, since its pointers are only valid for the old allocator.
+    pub fn replace_allocator(&mut self, allocator: Allocator) -> Allocator {
+        self.puzzles.clear();
+        std::mem::replace(&mut self.allocator, allocator)
+    }
+
+    /// Consumes the context and returns the underlying [`Allocator`], so that it can be reused.
+    /// Any [`CoinSpend`] that hasn't been taken is discarded.
+    pub fn into_allocator(self) -> Allocator {
+        self.allocator
+    }
+
     /// Remove all of the [`CoinSpend`] that have been collected so far.
+    #[instrument(
+        level = "debug",
+        skip_all,
+        fields(coin_spends = self.coin_spends.len(), bytes = self.serialized_size())
+    )]
     pub fn take(&mut self) -> Vec<CoinSpend> {
         std::mem::take(&mut self.coin_spends)
     }
 
+    /// The total size of the serialized puzzle reveals and solutions collected so far.
+    fn serialized_size(&self) -> usize {
+        self.coin_spends
+            .iter()
+            .map(|coin_spend| {
+                coin_spend.puzzle_reveal.as_ref().len() + coin_spend.solution.as_ref().len()
+            })
+            .sum()
+    }
+
     /// Adds a [`CoinSpend`] to the collection.
     pub fn insert(&mut self, coin_spend: CoinSpend) {
         self.coin_spends.push(coin_spend);
     }
 
+    /// Captures the current state of the context, so that speculative spends can be discarded later.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            allocator: self.allocator.checkpoint(),
+            puzzles: self.puzzles.clone(),
+            coin_spends: self.coin_spends.len(),
+        }
+    }
+
+    /// Discards any [`CoinSpend`] and allocations added since the [`Checkpoint`] was created.
+    ///
+    /// Any [`NodePtr`] allocated after the checkpoint is no longer valid once restored.
+    /// The checkpoint must have been created from this context, with the same [`Allocator`].
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.allocator.restore_checkpoint(&checkpoint.allocator);
+        self.puzzles = checkpoint.puzzles;
+        self.coin_spends.truncate(checkpoint.coin_spends);
+    }
+
     /// Serializes a [`Spend`] and adds it to the list of [`CoinSpend`].
     pub fn spend(&mut self, coin: Coin, spend: Spend) -> Result<(), DriverError> {
         let puzzle_reveal = self.serialize(&spend.puzzle)?;
@@ -68,6 +122,72 @@ impl SpendContext {
         Ok(())
     }
 
+    /// Runs the puzzle of every coin spend collected so far, to catch errors before submission.
+    /// Also checks that no coin is spent or created twice, and that the outputs don't exceed the inputs.
+    ///
+    /// This is not a replacement for full mempool validation, since signatures, announcements,
+    /// and other assertions are not checked. Announcements can be checked separately with
+    /// [`validate_announcements`](crate::validate_announcements).
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.net_fee().map(|_| ())
+    }
+
+    /// Calculates the fee paid by the coin spends collected so far, which is the total amount
+    /// of the coins being spent minus the total amount of the coins being created.
+    ///
+    /// The same checks are performed as in [`SpendContext::validate`].
+    pub fn net_fee(&self) -> Result<u64, ValidationError> {
+        let mut allocator = Allocator::new();
+        let mut spent_coins = HashSet::new();
+        let mut created_coins = HashSet::new();
+        let mut input = 0;
+        let mut output = 0;
+
+        for coin_spend in &self.coin_spends {
+            let coin_id = coin_spend.coin.coin_id();
+
+            if !spent_coins.insert(coin_id) {
+                return Err(ValidationError::DoubleSpend(coin_id));
+            }
+
+            input += u128::from(coin_spend.coin.amount);
+
+            let puzzle = coin_spend
+                .puzzle_reveal
+                .to_clvm(&mut allocator)
+                .map_err(|error| ValidationError::ToClvm { coin_id, error })?;
+            let solution = coin_spend
+                .solution
+                .to_clvm(&mut allocator)
+                .map_err(|error| ValidationError::ToClvm { coin_id, error })?;
+
+            let result = run_puzzle(&mut allocator, puzzle, solution)
+                .map_err(|error| ValidationError::Eval { coin_id, error })?;
+            let conditions = Vec::<Condition>::from_clvm(&allocator, result)
+                .map_err(|error| ValidationError::FromClvm { coin_id, error })?;
+
+            for condition in conditions {
+                let Some(create_coin) = condition.into_create_coin() else {
+                    continue;
+                };
+
+                let child = Coin::new(coin_id, create_coin.puzzle_hash, create_coin.amount);
+
+                if !created_coins.insert(child.coin_id()) {
+                    return Err(ValidationError::DuplicateOutput(child.coin_id()));
+                }
+
+                output += u128::from(create_coin.amount);
+            }
+        }
+
+        if output > input {
+            return Err(ValidationError::InsufficientInput { input, output });
+        }
+
+        Ok(u64::try_from(input - output).unwrap_or(u64::MAX))
+    }
+
     /// Allocate a new node and return its pointer.
     pub fn alloc<T>(&mut self, value: &T) -> Result<NodePtr, DriverError>
     where
@@ -94,6 +214,19 @@ impl SpendContext {
         Ok(run_puzzle(&mut self.allocator, puzzle, solution)?)
     }
 
+    /// The coins created by a spend of the parent coin with the given output conditions.
+    pub fn created_coins<T>(parent: &Coin, conditions: &[Condition<T>]) -> Vec<Coin> {
+        let parent_coin_id = parent.coin_id();
+
+        conditions
+            .iter()
+            .filter_map(Condition::as_create_coin)
+            .map(|create_coin| {
+                Coin::new(parent_coin_id, create_coin.puzzle_hash, create_coin.amount)
+            })
+            .collect()
+    }
+
     /// Serialize a value and return a `Program`.
     pub fn serialize<T>(&mut self, value: &T) -> Result<Program, DriverError>
     where
@@ -209,6 +342,16 @@ impl SpendContext {
         )
     }
 
+    /// Allocate the augmented condition puzzle and return its pointer.
+    pub fn augmented_condition_puzzle(&mut self) -> Result<NodePtr, DriverError> {
+        self.puzzle(AUGMENTED_CONDITION_PUZZLE_HASH, &AUGMENTED_CONDITION_PUZZLE)
+    }
+
+    /// Allocate the p2 puzzle hash puzzle and return its pointer.
+    pub fn p2_puzzle_hash_puzzle(&mut self) -> Result<NodePtr, DriverError> {
+        self.puzzle(P2_PUZZLE_HASH_PUZZLE_HASH, &P2_PUZZLE_HASH_PUZZLE)
+    }
+
     /// Preload a puzzle into the cache.
     pub fn preload(&mut self, puzzle_hash: TreeHash, ptr: NodePtr) {
         self.puzzles.insert(puzzle_hash, ptr);
@@ -235,6 +378,21 @@ impl SpendContext {
     }
 }
 
+/// A snapshot of a [`SpendContext`], created by [`SpendContext::checkpoint`].
+pub struct Checkpoint {
+    allocator: allocator::Checkpoint,
+    puzzles: HashMap<TreeHash, NodePtr>,
+    coin_spends: usize,
+}
+
+impl fmt::Debug for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkpoint")
+            .field("coin_spends", &self.coin_spends)
+            .finish_non_exhaustive()
+    }
+}
+
 impl IntoIterator for SpendContext {
     type Item = CoinSpend;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -253,3 +411,308 @@ impl From<Allocator> for SpendContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use chia_protocol::Bytes32;
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::{Conditions, ReserveFee};
+    use tracing::{subscriber, Level};
+    use tracing_subscriber::fmt::{format::FmtSpan, MakeWriter};
+
+    use crate::StandardLayer;
+
+    use super::*;
+
+    #[test]
+    fn test_puzzle_cache() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+
+        let first = ctx.standard_puzzle()?;
+        let second = ctx.standard_puzzle()?;
+        assert_eq!(first, second);
+        assert_eq!(ctx.get_puzzle(&STANDARD_PUZZLE_HASH), Some(first));
+        assert_eq!(ctx.tree_hash(first), STANDARD_PUZZLE_HASH);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_allocator_clears_cache() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+
+        ctx.standard_puzzle()?;
+        ctx.replace_allocator(Allocator::new());
+        assert_eq!(ctx.get_puzzle(&STANDARD_PUZZLE_HASH), None);
+
+        let puzzle = ctx.standard_puzzle()?;
+        assert_eq!(ctx.tree_hash(puzzle), STANDARD_PUZZLE_HASH);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spend_spans() -> anyhow::Result<()> {
+        static LOGS: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::TRACE)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(|| LOGS.make_writer())
+            .finish();
+
+        let coin_spends = subscriber::with_default(subscriber, || {
+            StandardLayer::new(pk).spend(
+                &mut ctx,
+                coin,
+                Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+            )?;
+            anyhow::Ok(ctx.take())
+        })?;
+
+        let bytes =
+            coin_spends[0].puzzle_reveal.as_ref().len() + coin_spends[0].solution.as_ref().len();
+        let logs = String::from_utf8(LOGS.lock().unwrap().clone())?;
+
+        assert!(logs.contains("construct_spend{layer="));
+        assert!(logs.contains(&format!("take{{coin_spends=1 bytes={bytes}}}")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_owned_allocator() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+
+        let mut allocator = Allocator::new();
+        let existing = allocator.new_number(42.into())?;
+
+        let mut ctx = SpendContext::from(allocator);
+        StandardLayer::new(pk).spend(
+            &mut ctx,
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+
+        let coin_spends = ctx.take();
+        assert_eq!(coin_spends.len(), 1);
+        assert_eq!(coin_spends[0].coin, coin);
+
+        // Nodes allocated before the context was created are still valid afterwards.
+        let allocator = ctx.into_allocator();
+        assert_eq!(allocator.number(existing), 42.into());
+
+        sim.spend_coins(coin_spends, &[sk])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkpoint_restore() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let p2 = StandardLayer::new(pk);
+
+        p2.spend(
+            &mut ctx,
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+
+        let checkpoint = ctx.checkpoint();
+
+        let extra_coin = sim.new_coin(puzzle_hash, 2);
+        p2.spend(
+            &mut ctx,
+            extra_coin,
+            Conditions::new().create_coin(puzzle_hash, 2, Vec::new()),
+        )?;
+        ctx.cat_puzzle()?;
+        assert_eq!(ctx.iter().count(), 2);
+
+        ctx.restore(checkpoint);
+
+        let coin_spends: Vec<Coin> = ctx.iter().map(|coin_spend| coin_spend.coin).collect();
+        assert_eq!(coin_spends, [coin]);
+        assert_eq!(ctx.get_puzzle(&CAT_PUZZLE_HASH), None);
+
+        // The context is still usable after restoring.
+        let puzzle = ctx.cat_puzzle()?;
+        assert_eq!(ctx.tree_hash(puzzle), CAT_PUZZLE_HASH);
+
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+
+        StandardLayer::new(pk).spend(
+            &mut ctx,
+            coin,
+            Conditions::new()
+                .create_coin(puzzle_hash, 1, Vec::new())
+                .create_coin(puzzle_hash, 0, Vec::new()),
+        )?;
+
+        ctx.validate()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_overspend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+
+        StandardLayer::new(pk).spend(
+            &mut ctx,
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 2, Vec::new()),
+        )?;
+
+        assert!(matches!(
+            ctx.validate(),
+            Err(ValidationError::InsufficientInput {
+                input: 1,
+                output: 2
+            })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_double_spend() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+        let p2 = StandardLayer::new(pk);
+
+        p2.spend(
+            &mut ctx,
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+        p2.spend(&mut ctx, coin, Conditions::new())?;
+
+        let Err(ValidationError::DoubleSpend(coin_id)) = ctx.validate() else {
+            panic!("expected double spend error");
+        };
+        assert_eq!(coin_id, coin.coin_id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_duplicate_output() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(2)?;
+
+        StandardLayer::new(pk).spend(
+            &mut ctx,
+            coin,
+            Conditions::new()
+                .create_coin(puzzle_hash, 1, Vec::new())
+                .create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+
+        assert!(matches!(
+            ctx.validate(),
+            Err(ValidationError::DuplicateOutput(..))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_eval_error() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let coin = Coin::new(Bytes32::default(), Bytes32::default(), 1);
+
+        let puzzle = ctx.alloc(&"not a valid program")?;
+        ctx.spend(coin, Spend::new(puzzle, NodePtr::NIL))?;
+
+        let Err(ValidationError::Eval { coin_id, .. }) = ctx.validate() else {
+            panic!("expected eval error");
+        };
+        assert_eq!(coin_id, coin.coin_id());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_net_fee() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(100)?;
+        let p2 = StandardLayer::new(pk);
+
+        let spends = [
+            (coin, 10),
+            (sim.new_coin(puzzle_hash, 200), 20),
+            (sim.new_coin(puzzle_hash, 300), 30),
+        ];
+
+        let mut fees = Conditions::new();
+
+        for (coin, fee) in spends {
+            fees = fees.reserve_fee(fee);
+            p2.spend(
+                &mut ctx,
+                coin,
+                Conditions::new().create_coin(puzzle_hash, coin.amount - fee, Vec::new()),
+            )?;
+        }
+
+        let fees = fees.aggregate_reserve_fees();
+        assert_eq!(fees.as_ref(), [Condition::ReserveFee(ReserveFee::new(60))]);
+
+        let fee_coin = sim.new_coin(puzzle_hash, 0);
+        p2.spend(&mut ctx, fee_coin, fees)?;
+
+        assert_eq!(ctx.net_fee()?, 60);
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_created_coins() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let mut ctx = SpendContext::new();
+
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(3)?;
+
+        let conditions = Conditions::new()
+            .create_coin(puzzle_hash, 1, Vec::new())
+            .create_coin(Bytes32::new([1; 32]), 2, Vec::new());
+
+        let created = SpendContext::created_coins(&coin, conditions.as_ref());
+        assert_eq!(created.len(), 2);
+
+        StandardLayer::new(pk).spend(&mut ctx, coin, conditions)?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        for child in created {
+            let coin_state = sim.coin_state(child.coin_id()).expect("missing child coin");
+            assert_eq!(coin_state.coin, child);
+        }
+
+        Ok(())
+    }
+}
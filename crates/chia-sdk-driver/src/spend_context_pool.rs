@@ -0,0 +1,131 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+};
+
+use crate::{Checkpoint, SpendContext};
+
+/// A pool of [`SpendContext`] values whose allocators are reused, rather than being reallocated for every transaction.
+/// Cloning the pool is cheap, and the clones share the same contexts.
+#[derive(Debug, Default, Clone)]
+pub struct SpendContextPool {
+    contexts: Arc<Mutex<Vec<SpendContext>>>,
+}
+
+impl SpendContextPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes a context from the pool, or creates a new one if none are available.
+    /// The context is reset and returned to the pool when the guard is dropped.
+    pub fn get(&self) -> PooledSpendContext {
+        let ctx = self
+            .contexts
+            .lock()
+            .ok()
+            .and_then(|mut contexts| contexts.pop())
+            .unwrap_or_default();
+
+        let checkpoint = ctx.checkpoint();
+
+        PooledSpendContext {
+            ctx: Some(ctx),
+            checkpoint: Some(checkpoint),
+            contexts: self.contexts.clone(),
+        }
+    }
+
+    /// The number of contexts which are waiting in the pool to be reused.
+    pub fn available(&self) -> usize {
+        self.contexts.lock().map_or(0, |contexts| contexts.len())
+    }
+}
+
+/// A [`SpendContext`] borrowed from a [`SpendContextPool`].
+///
+/// When dropped, the allocations, cached puzzles, and coin spends are all discarded,
+/// and the context is returned to the pool. Coin spends must be taken out before then.
+#[derive(Debug)]
+pub struct PooledSpendContext {
+    ctx: Option<SpendContext>,
+    checkpoint: Option<Checkpoint>,
+    contexts: Arc<Mutex<Vec<SpendContext>>>,
+}
+
+impl Deref for PooledSpendContext {
+    type Target = SpendContext;
+
+    fn deref(&self) -> &Self::Target {
+        self.ctx
+            .as_ref()
+            .expect("the context is only taken when dropped")
+    }
+}
+
+impl DerefMut for PooledSpendContext {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.ctx
+            .as_mut()
+            .expect("the context is only taken when dropped")
+    }
+}
+
+impl Drop for PooledSpendContext {
+    fn drop(&mut self) {
+        let (Some(mut ctx), Some(checkpoint)) = (self.ctx.take(), self.checkpoint.take()) else {
+            return;
+        };
+
+        // The checkpoint was created while the context was empty, so this clears everything.
+        ctx.restore(checkpoint);
+
+        if let Ok(mut contexts) = self.contexts.lock() {
+            contexts.push(ctx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::{Bytes32, Coin};
+    use chia_sdk_test::test_secret_keys;
+    use chia_sdk_types::Conditions;
+
+    use crate::StandardLayer;
+
+    use super::*;
+
+    #[test]
+    fn test_spend_context_pool_isolation() -> anyhow::Result<()> {
+        let pool = SpendContextPool::new();
+        let p2 = StandardLayer::new(test_secret_keys(1)?[0].public_key());
+        let conditions = Conditions::new().create_coin(Bytes32::default(), 1, Vec::new());
+
+        let first_coin = Coin::new(Bytes32::new([1; 32]), Bytes32::default(), 1);
+        let second_coin = Coin::new(Bytes32::new([2; 32]), Bytes32::default(), 1);
+
+        // The coin spend is deliberately left behind when the context is returned.
+        {
+            let mut ctx = pool.get();
+            p2.spend(&mut ctx, first_coin, conditions.clone())?;
+        }
+        assert_eq!(pool.available(), 1);
+
+        let mut ctx = pool.get();
+        assert_eq!(pool.available(), 0);
+        assert_eq!(ctx.iter().count(), 0);
+
+        p2.spend(&mut ctx, second_coin, conditions.clone())?;
+        let recycled = ctx.take();
+
+        let fresh = &mut SpendContext::new();
+        p2.spend(fresh, second_coin, conditions)?;
+        assert_eq!(recycled, fresh.take());
+
+        drop(ctx);
+        assert_eq!(pool.available(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use chia_protocol::Bytes32;
+use clvm_traits::{FromClvmError, ToClvmError};
+use clvmr::reduction::EvalErr;
+use thiserror::Error;
+
+/// Errors that can be found by [`SpendContext::validate`](crate::SpendContext::validate)
+/// and [`validate_announcements`](crate::validate_announcements).
+#[derive(Debug, Error)]
+pub enum ValidationError {
+    #[error("failed to load puzzle or solution for coin {coin_id}: {error}")]
+    ToClvm {
+        coin_id: Bytes32,
+        error: ToClvmError,
+    },
+
+    #[error("puzzle for coin {coin_id} failed to run: {error}")]
+    Eval { coin_id: Bytes32, error: EvalErr },
+
+    #[error("invalid conditions output by coin {coin_id}: {error}")]
+    FromClvm {
+        coin_id: Bytes32,
+        error: FromClvmError,
+    },
+
+    #[error("coin {0} is spent more than once")]
+    DoubleSpend(Bytes32),
+
+    #[error("coin {0} is created more than once")]
+    DuplicateOutput(Bytes32),
+
+    #[error("output amount {output} exceeds input amount {input}")]
+    InsufficientInput { input: u128, output: u128 },
+
+    #[error("coin {coin_id} asserts announcement {announcement_id}, which isn't created")]
+    UnsatisfiedAnnouncement {
+        coin_id: Bytes32,
+        announcement_id: Bytes32,
+    },
+}
@@ -2,12 +2,13 @@ use std::io::Read;
 
 use chia_puzzles::{
     cat::{CAT_PUZZLE, CAT_PUZZLE_V1},
+    did::DID_INNER_PUZZLE,
     nft::{
-        NFT_METADATA_UPDATER_PUZZLE, NFT_OWNERSHIP_LAYER_PUZZLE, NFT_ROYALTY_TRANSFER_PUZZLE,
-        NFT_STATE_LAYER_PUZZLE,
+        NFT_INTERMEDIATE_LAUNCHER_PUZZLE, NFT_METADATA_UPDATER_PUZZLE, NFT_OWNERSHIP_LAYER_PUZZLE,
+        NFT_ROYALTY_TRANSFER_PUZZLE, NFT_STATE_LAYER_PUZZLE,
     },
     offer::{SETTLEMENT_PAYMENTS_PUZZLE, SETTLEMENT_PAYMENTS_PUZZLE_V1},
-    singleton::SINGLETON_TOP_LAYER_PUZZLE,
+    singleton::{SINGLETON_LAUNCHER_PUZZLE, SINGLETON_TOP_LAYER_PUZZLE},
     standard::STANDARD_PUZZLE,
 };
 use flate2::{
@@ -15,30 +16,114 @@ use flate2::{
     Compress, Compression, Decompress, FlushDecompress,
 };
 use once_cell::sync::Lazy;
+use tracing::{field::Empty, instrument, Span};
 
 use crate::OfferError;
 
-static COMPRESSION_ZDICT: Lazy<Vec<u8>> = Lazy::new(|| {
-    let mut bytes = Vec::new();
-    bytes.extend_from_slice(&STANDARD_PUZZLE);
-    bytes.extend_from_slice(&CAT_PUZZLE_V1);
-    bytes.extend_from_slice(&SETTLEMENT_PAYMENTS_PUZZLE_V1);
-    bytes.extend_from_slice(&SINGLETON_TOP_LAYER_PUZZLE);
-    bytes.extend_from_slice(&NFT_STATE_LAYER_PUZZLE);
-    bytes.extend_from_slice(&NFT_OWNERSHIP_LAYER_PUZZLE);
-    bytes.extend_from_slice(&NFT_METADATA_UPDATER_PUZZLE);
-    bytes.extend_from_slice(&NFT_ROYALTY_TRANSFER_PUZZLE);
-    bytes.extend_from_slice(&CAT_PUZZLE);
-    bytes.extend_from_slice(&SETTLEMENT_PAYMENTS_PUZZLE);
+/// The oldest compression version that is still produced. Offers with an older version prefix
+/// are decompressed with this version's dictionary.
+pub const MIN_COMPRESSION_VERSION: u16 = 6;
+
+/// The newest compression version, which can be decompressed but is only produced when needed.
+pub const MAX_COMPRESSION_VERSION: u16 = 7;
+
+/// The puzzle reveals in the version 6 dictionary, which matches the reference wallet.
+const VERSION_6_PUZZLES: [&[u8]; 10] = [
+    &STANDARD_PUZZLE,
+    &CAT_PUZZLE_V1,
+    &SETTLEMENT_PAYMENTS_PUZZLE_V1,
+    &SINGLETON_TOP_LAYER_PUZZLE,
+    &NFT_STATE_LAYER_PUZZLE,
+    &NFT_OWNERSHIP_LAYER_PUZZLE,
+    &NFT_METADATA_UPDATER_PUZZLE,
+    &NFT_ROYALTY_TRANSFER_PUZZLE,
+    &CAT_PUZZLE,
+    &SETTLEMENT_PAYMENTS_PUZZLE,
+];
+
+/// The puzzle reveals appended to the dictionary in version 7.
+const VERSION_7_PUZZLES: [&[u8]; 3] = [
+    &DID_INNER_PUZZLE,
+    &SINGLETON_LAUNCHER_PUZZLE,
+    &NFT_INTERMEDIATE_LAUNCHER_PUZZLE,
+];
+
+static COMPRESSION_ZDICT_V6: Lazy<Vec<u8>> = Lazy::new(|| VERSION_6_PUZZLES.concat());
+
+static COMPRESSION_ZDICT_V7: Lazy<Vec<u8>> = Lazy::new(|| {
+    let mut bytes = COMPRESSION_ZDICT_V6.clone();
+    bytes.extend(VERSION_7_PUZZLES.concat());
     bytes
 });
 
+fn compression_zdict(version: u16) -> Result<&'static [u8], OfferError> {
+    match version {
+        0..=6 => Ok(COMPRESSION_ZDICT_V6.as_slice()),
+        7 => Ok(COMPRESSION_ZDICT_V7.as_slice()),
+        _ => Err(OfferError::UnsupportedVersion),
+    }
+}
+
+/// Returns the lowest compression version whose dictionary includes every known puzzle reveal in the bytes.
+/// Using the lowest version keeps offers readable by wallets that don't support newer versions.
+pub fn required_compression_version(bytes: &[u8]) -> u16 {
+    if VERSION_7_PUZZLES
+        .iter()
+        .any(|puzzle| bytes.windows(puzzle.len()).any(|window| window == *puzzle))
+    {
+        return 7;
+    }
+
+    MIN_COMPRESSION_VERSION
+}
+
+/// The version of the CAT puzzle used by a coin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CatVersion {
+    /// The original CAT puzzle, which was deprecated after a security issue was found.
+    V1,
+    V2,
+}
+
+/// Detects whether a serialized puzzle reveal is a CAT, and if so which version of the puzzle it uses.
+/// This can be used to warn about offers which still use version 1 CAT coins.
+///
+/// Only the outermost layer is checked, so a CAT wrapped in another puzzle isn't detected.
+pub fn detect_cat_version(puzzle_reveal: &[u8]) -> Option<CatVersion> {
+    // A curried puzzle is serialized as `(a (q . MOD) ARGS)`, so the mod follows this prefix directly.
+    let curried_mod = puzzle_reveal.strip_prefix(&[0xff, 0x02, 0xff, 0xff, 0x01])?;
+
+    if curried_mod.starts_with(&CAT_PUZZLE) {
+        Some(CatVersion::V2)
+    } else if curried_mod.starts_with(&CAT_PUZZLE_V1) {
+        Some(CatVersion::V1)
+    } else {
+        None
+    }
+}
+
+#[instrument(
+    level = "debug",
+    skip_all,
+    fields(input_bytes = bytes.len(), output_bytes = Empty, version = Empty)
+)]
 pub fn compress_offer_bytes(bytes: &[u8]) -> Result<Vec<u8>, OfferError> {
-    let mut output = 6u16.to_be_bytes().to_vec();
-    output.extend(zlib_compress(bytes, &COMPRESSION_ZDICT)?);
+    let version = required_compression_version(bytes);
+    let mut output = version.to_be_bytes().to_vec();
+    output.extend(zlib_compress(bytes, compression_zdict(version)?)?);
+
+    let span = Span::current();
+    span.record("version", version);
+    span.record("output_bytes", output.len());
+
     Ok(output)
 }
 
+#[instrument(
+    level = "debug",
+    skip_all,
+    fields(input_bytes = bytes.len(), output_bytes = Empty, version = Empty)
+)]
 pub fn decompress_offer_bytes(bytes: &[u8]) -> Result<Vec<u8>, OfferError> {
     let version_bytes: [u8; 2] = bytes
         .get(0..2)
@@ -46,12 +131,13 @@ pub fn decompress_offer_bytes(bytes: &[u8]) -> Result<Vec<u8>, OfferError> {
         .try_into()?;
 
     let version = u16::from_be_bytes(version_bytes);
+    let output = zlib_decompress(&bytes[2..], compression_zdict(version)?)?;
 
-    if version > 6 {
-        return Err(OfferError::UnsupportedVersion);
-    }
+    let span = Span::current();
+    span.record("version", version);
+    span.record("output_bytes", output.len());
 
-    zlib_decompress(&bytes[2..], &COMPRESSION_ZDICT)
+    Ok(output)
 }
 
 fn zlib_compress(input: &[u8], zdict: &[u8]) -> std::io::Result<Vec<u8>> {
@@ -83,8 +169,20 @@ fn zlib_decompress(input: &[u8], zdict: &[u8]) -> Result<Vec<u8>, OfferError> {
 
 #[cfg(test)]
 mod tests {
-    use chia_protocol::SpendBundle;
+    use std::sync::Mutex;
+
+    use chia_bls::Signature;
+    use chia_protocol::{Bytes32, Coin, CoinSpend, Program, SpendBundle};
+    use chia_puzzles::cat::CatArgs;
     use chia_traits::Streamable;
+    use clvm_traits::ToClvm;
+    use clvm_utils::CurriedProgram;
+    use clvmr::{
+        serde::{node_from_bytes, node_to_bytes},
+        Allocator,
+    };
+    use tracing::{subscriber, Level};
+    use tracing_subscriber::fmt::{format::FmtSpan, MakeWriter};
 
     use super::*;
 
@@ -102,6 +200,102 @@ mod tests {
         assert_eq!(hex::encode(output), DECOMPRESSED_OFFER.trim());
     }
 
+    #[test]
+    fn test_compression_spans() -> anyhow::Result<()> {
+        static LOGS: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::DEBUG)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_writer(|| LOGS.make_writer())
+            .finish();
+
+        let decompressed_offer = hex::decode(DECOMPRESSED_OFFER.trim())?;
+        let compressed_offer = hex::decode(COMPRESSED_OFFER.trim())?;
+
+        subscriber::with_default(subscriber, || {
+            compress_offer_bytes(&decompressed_offer)?;
+            decompress_offer_bytes(&compressed_offer)
+        })?;
+
+        let (decompressed, compressed) = (decompressed_offer.len(), compressed_offer.len());
+        let logs = String::from_utf8(LOGS.lock().unwrap().clone())?;
+
+        assert!(logs.contains(&format!(
+            "compress_offer_bytes{{input_bytes={decompressed} version=6 output_bytes={compressed}}}"
+        )));
+        assert!(logs.contains(&format!(
+            "decompress_offer_bytes{{input_bytes={compressed} version=6 output_bytes={decompressed}}}"
+        )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_version_7_compression() -> anyhow::Result<()> {
+        let spend_bundle = SpendBundle::new(
+            vec![CoinSpend::new(
+                Coin::new(Bytes32::default(), Bytes32::default(), 1),
+                Program::from(DID_INNER_PUZZLE.to_vec()),
+                Program::from(vec![0x80]),
+            )],
+            Signature::default(),
+        );
+        let decompressed_offer = spend_bundle.to_bytes()?;
+        assert_eq!(required_compression_version(&decompressed_offer), 7);
+
+        let compressed_offer = compress_offer_bytes(&decompressed_offer)?;
+        assert_eq!(compressed_offer[0..2], 7u16.to_be_bytes());
+        assert_eq!(
+            decompress_offer_bytes(&compressed_offer)?,
+            decompressed_offer
+        );
+
+        // Version 6 offers don't contain any version 7 puzzles, so they keep their version.
+        let decompressed_offer = hex::decode(DECOMPRESSED_OFFER.trim())?;
+        assert_eq!(required_compression_version(&decompressed_offer), 6);
+
+        let mut unsupported = compressed_offer;
+        unsupported[0..2].copy_from_slice(&8u16.to_be_bytes());
+        assert!(matches!(
+            decompress_offer_bytes(&unsupported),
+            Err(OfferError::UnsupportedVersion)
+        ));
+
+        Ok(())
+    }
+
+    fn curried_cat(mod_bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut allocator = Allocator::new();
+        let program = node_from_bytes(&mut allocator, mod_bytes)?;
+        let inner_puzzle = node_from_bytes(&mut allocator, &STANDARD_PUZZLE)?;
+        let puzzle = CurriedProgram {
+            program,
+            args: CatArgs::new(Bytes32::new([42; 32]), inner_puzzle),
+        }
+        .to_clvm(&mut allocator)?;
+        Ok(node_to_bytes(&allocator, puzzle)?)
+    }
+
+    #[test]
+    fn test_detect_cat_version() -> anyhow::Result<()> {
+        assert_eq!(
+            detect_cat_version(&curried_cat(&CAT_PUZZLE)?),
+            Some(CatVersion::V2)
+        );
+        assert_eq!(
+            detect_cat_version(&curried_cat(&CAT_PUZZLE_V1)?),
+            Some(CatVersion::V1)
+        );
+
+        // Neither the uncurried CAT puzzle nor other curried puzzles are CATs.
+        assert_eq!(detect_cat_version(&CAT_PUZZLE), None);
+        assert_eq!(detect_cat_version(&curried_cat(&STANDARD_PUZZLE)?), None);
+        assert_eq!(detect_cat_version(&[]), None);
+
+        Ok(())
+    }
+
     #[test]
     fn parse_spend_bundle() {
         let decompressed_offer = hex::decode(DECOMPRESSED_OFFER.trim()).unwrap();
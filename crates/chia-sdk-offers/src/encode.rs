@@ -7,7 +7,7 @@ pub fn encode_offer_data(offer: &[u8]) -> Result<String, OfferError> {
         .into_iter()
         .map(u5::try_from_u8)
         .collect::<Result<Vec<_>, bech32::Error>>()?;
-    Ok(bech32::encode("offer1", data, Variant::Bech32m)?)
+    Ok(bech32::encode("offer", data, Variant::Bech32m)?)
 }
 
 pub fn decode_offer_data(offer: &str) -> Result<Vec<u8>, OfferError> {
@@ -23,3 +23,26 @@ pub fn decode_offer_data(offer: &str) -> Result<Vec<u8>, OfferError> {
 
     Ok(bech32::convert_bits(&data, 5, 8, false)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{compress_offer_bytes, decompress_offer_bytes};
+
+    use super::*;
+
+    #[test]
+    fn test_encode_roundtrip() -> anyhow::Result<()> {
+        let compressed = hex::decode(include_str!("../test_data/compressed.offer").trim())?;
+        let decompressed = hex::decode(include_str!("../test_data/decompressed.offer").trim())?;
+
+        let text = encode_offer_data(&compress_offer_bytes(&decompressed)?)?;
+        assert!(text.starts_with("offer1"));
+        assert!(!text.starts_with("offer11"));
+
+        let decoded = decode_offer_data(&text)?;
+        assert_eq!(decoded, compressed);
+        assert_eq!(decompress_offer_bytes(&decoded)?, decompressed);
+
+        Ok(())
+    }
+}
@@ -1,8 +1,14 @@
 use std::{array::TryFromSliceError, io, num::TryFromIntError};
 
+use chia_protocol::Bytes32;
+use chia_sdk_driver::DriverError;
+use chia_sdk_signer::SignerError;
 use clvm_traits::{FromClvmError, ToClvmError};
+use clvmr::reduction::EvalErr;
 use thiserror::Error;
 
+use crate::OfferAsset;
+
 #[derive(Debug, Error)]
 pub enum OfferError {
     #[error("IO error: {0}")]
@@ -44,6 +50,30 @@ pub enum OfferError {
     #[error("From CLVM error: {0}")]
     FromClvm(#[from] FromClvmError),
 
+    #[error("Eval error: {0}")]
+    Eval(#[from] EvalErr),
+
     #[error("Requested payment puzzle mismatch")]
     PuzzleMismatch,
+
+    #[error("No settlement coin to pay the requested payments for puzzle hash {0}")]
+    MissingSettlementCoin(Bytes32),
+
+    #[error("Unsupported requested payment puzzle: {0}")]
+    UnsupportedRequestedPuzzle(Bytes32),
+
+    #[error("Percentage must be between 1 and 100, but got {0}")]
+    InvalidPercentage(u8),
+
+    #[error("Amount {amount} can't be divided into {percentage}%")]
+    IndivisibleAmount { amount: u64, percentage: u8 },
+
+    #[error("Can't partially offer or request {0:?}")]
+    UnsupportedPartialAsset(OfferAsset),
+
+    #[error("Driver error: {0}")]
+    Driver(#[from] DriverError),
+
+    #[error("Signer error: {0}")]
+    Signer(#[from] SignerError),
 }
@@ -3,11 +3,23 @@ mod encode;
 mod error;
 mod offer;
 mod offer_builder;
+mod offer_signature;
+mod offer_summary;
 mod parsed_offer;
+mod partial_offer;
+mod royalty;
+mod settlement_payments;
+mod take_offer;
 
 pub use compress::*;
 pub use encode::*;
 pub use error::*;
 pub use offer::*;
 pub use offer_builder::*;
+pub use offer_signature::*;
+pub use offer_summary::*;
 pub use parsed_offer::*;
+pub use partial_offer::*;
+pub use royalty::*;
+pub use settlement_payments::*;
+pub use take_offer::*;
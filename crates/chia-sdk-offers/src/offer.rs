@@ -1,6 +1,7 @@
-use chia_protocol::{Bytes32, SpendBundle};
+use chia_bls::PublicKey;
+use chia_protocol::{Bytes32, Coin, SpendBundle};
 use chia_puzzles::offer::SettlementPaymentsSolution;
-use chia_sdk_driver::Puzzle;
+use chia_sdk_driver::{Puzzle, SpendContext};
 use chia_traits::Streamable;
 use clvm_traits::{FromClvm, ToClvm};
 use clvm_utils::{tree_hash, ToTreeHash};
@@ -22,17 +23,16 @@ impl Offer {
         Self { spend_bundle }
     }
 
-    pub fn build(coin_ids: Vec<Bytes32>) -> OfferBuilder<Make> {
-        Self::build_with_nonce(Self::nonce(coin_ids))
+    pub fn build(coins: &[Coin]) -> OfferBuilder<Make> {
+        Self::build_with_nonce(Self::nonce(coins))
     }
 
     pub fn build_with_nonce(nonce: Bytes32) -> OfferBuilder<Make> {
         OfferBuilder::new(nonce)
     }
 
-    pub fn nonce(mut coin_ids: Vec<Bytes32>) -> Bytes32 {
-        coin_ids.sort();
-        coin_ids.tree_hash().into()
+    pub fn nonce(coins: &[Coin]) -> Bytes32 {
+        offer_nonce(coins)
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>, OfferError> {
@@ -63,6 +63,16 @@ impl Offer {
         Ok(self.parse(allocator)?.take())
     }
 
+    /// Parses the offer and cancels it by spending the maker's coins back to the given synthetic key.
+    /// See [`ParsedOffer::cancel`] for more details.
+    pub fn cancel(
+        self,
+        ctx: &mut SpendContext,
+        synthetic_key: PublicKey,
+    ) -> Result<(), OfferError> {
+        self.parse(&mut ctx.allocator)?.cancel(ctx, synthetic_key)
+    }
+
     pub fn parse(self, allocator: &mut Allocator) -> Result<ParsedOffer, OfferError> {
         let mut parsed = ParsedOffer {
             aggregated_signature: self.spend_bundle.aggregated_signature,
@@ -105,6 +115,21 @@ impl Offer {
     }
 }
 
+/// Calculates the nonce of an offer from the coins being offered.
+/// Like the reference wallet, this is the tree hash of the list of coins sorted by coin id,
+/// with each coin in the form `(parent_coin_info puzzle_hash amount)`.
+pub fn offer_nonce(offered_coins: &[Coin]) -> Bytes32 {
+    let mut coins = offered_coins.to_vec();
+    coins.sort_by_cached_key(Coin::coin_id);
+
+    coins
+        .into_iter()
+        .map(|coin| (coin.parent_coin_info, (coin.puzzle_hash, (coin.amount, ()))))
+        .collect::<Vec<_>>()
+        .tree_hash()
+        .into()
+}
+
 impl From<SpendBundle> for Offer {
     fn from(spend_bundle: SpendBundle) -> Self {
         Self::new(spend_bundle)
@@ -116,3 +141,44 @@ impl From<Offer> for SpendBundle {
         offer.spend_bundle
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    const DECOMPRESSED_OFFER: &str = include_str!("../test_data/decompressed.offer");
+
+    #[test]
+    fn test_offer_nonce() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let offer = Offer::from_bytes(&hex::decode(DECOMPRESSED_OFFER.trim())?)?;
+        let parsed = offer.parse(&mut allocator)?;
+
+        // The sample offer was made by the reference wallet, and only offers a single NFT coin.
+        let [coin_spend] = parsed.coin_spends.as_slice() else {
+            panic!("expected a single offered coin");
+        };
+        let expected = Bytes32::new(hex!(
+            "d7a3b357ee3eb1d3857c2e164beea5cb8cf1d0d307c3b8c8463d84a15de2e3ea"
+        ));
+        assert!(parsed
+            .requested_payments
+            .values()
+            .flat_map(|(_, notarized_payments)| notarized_payments)
+            .all(|notarized_payment| notarized_payment.nonce == expected));
+
+        assert_eq!(offer_nonce(&[coin_spend.coin]), expected);
+        assert_eq!(Offer::nonce(&[coin_spend.coin]), expected);
+
+        // The nonce doesn't depend on the order of the coins.
+        let other = Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 3);
+        assert_eq!(
+            offer_nonce(&[coin_spend.coin, other]),
+            offer_nonce(&[other, coin_spend.coin])
+        );
+
+        Ok(())
+    }
+}
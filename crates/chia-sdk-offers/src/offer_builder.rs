@@ -1,12 +1,12 @@
 use chia_protocol::{Bytes32, Coin, CoinSpend, SpendBundle};
 use chia_puzzles::offer::{NotarizedPayment, Payment, SettlementPaymentsSolution};
 use chia_sdk_driver::{DriverError, Puzzle, SpendContext};
-use chia_sdk_types::{announcement_id, AssertPuzzleAnnouncement};
+use chia_sdk_types::{AssertPuzzleAnnouncement, Conditions};
 use clvm_traits::ToClvm;
 use clvmr::Allocator;
 use indexmap::IndexMap;
 
-use crate::{Offer, ParsedOffer};
+use crate::{settlement_announcement_id, Offer, ParsedOffer};
 
 #[derive(Debug, Clone)]
 pub struct OfferBuilder<T> {
@@ -18,6 +18,7 @@ pub struct Make {
     nonce: Bytes32,
     requested_payments: IndexMap<Bytes32, (Puzzle, Vec<NotarizedPayment>)>,
     announcements: Vec<AssertPuzzleAnnouncement>,
+    expires_at: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +38,7 @@ impl OfferBuilder<Make> {
                 nonce,
                 requested_payments: IndexMap::new(),
                 announcements: Vec::new(),
+                expires_at: None,
             },
         }
     }
@@ -60,8 +62,7 @@ impl OfferBuilder<Make> {
             nonce: self.data.nonce,
             payments,
         };
-        let notarized_payment_ptr = ctx.alloc(&notarized_payment)?;
-        let notarized_payment_hash = ctx.tree_hash(notarized_payment_ptr);
+        let announcement_id = settlement_announcement_id(puzzle_hash, &notarized_payment);
 
         self.data
             .requested_payments
@@ -72,16 +73,25 @@ impl OfferBuilder<Make> {
 
         self.data
             .announcements
-            .push(AssertPuzzleAnnouncement::new(announcement_id(
-                puzzle_hash,
-                notarized_payment_hash,
-            )));
+            .push(AssertPuzzleAnnouncement::new(announcement_id));
 
         Ok(self)
     }
 
+    /// Makes the offer expire at the given timestamp, by adding an `ASSERT_BEFORE_SECONDS_ABSOLUTE`
+    /// condition to the conditions returned by [`OfferBuilder::finish_with_conditions`].
+    /// Since the taker's spends are bundled with the maker's,
+    /// the offer can't be taken once a block with this timestamp or later has been farmed.
+    #[must_use]
+    pub fn expires_at(mut self, timestamp: u64) -> Self {
+        self.data.expires_at = Some(timestamp);
+        self
+    }
+
     /// This will create a new [`OfferBuilder`] with the requested payments frozen.
     /// It returns a list of announcements that can be asserted by the maker side.
+    ///
+    /// The expiration isn't included, so use [`OfferBuilder::finish_with_conditions`] if one was set.
     pub fn finish(self) -> (Vec<AssertPuzzleAnnouncement>, OfferBuilder<Partial>) {
         let partial = OfferBuilder {
             data: Partial {
@@ -90,6 +100,20 @@ impl OfferBuilder<Make> {
         };
         (self.data.announcements, partial)
     }
+
+    /// Like [`OfferBuilder::finish`], but returns the conditions that must be output by the maker side,
+    /// which assert the requested payments and the expiration, if any.
+    pub fn finish_with_conditions(self) -> (Conditions, OfferBuilder<Partial>) {
+        let expires_at = self.data.expires_at;
+        let (announcements, partial) = self.finish();
+
+        let mut conditions = Conditions::new().extend(announcements);
+        if let Some(timestamp) = expires_at {
+            conditions = conditions.assert_before_seconds_absolute(timestamp);
+        }
+
+        (conditions, partial)
+    }
 }
 
 impl OfferBuilder<Partial> {
@@ -0,0 +1,138 @@
+use chia_bls::aggregate_verify;
+use chia_sdk_signer::{AggSigConstants, RequiredSignature};
+use clvmr::Allocator;
+
+use crate::{Offer, OfferError, ParsedOffer};
+
+impl ParsedOffer {
+    /// Checks whether the aggregated signature is valid for every signature required by the maker's coin spends.
+    ///
+    /// The requested payments aren't real coin spends and are never signed, so they are excluded.
+    pub fn verify_signature(
+        &self,
+        allocator: &mut Allocator,
+        constants: &AggSigConstants,
+    ) -> Result<bool, OfferError> {
+        let required_signatures =
+            RequiredSignature::from_coin_spends(allocator, &self.coin_spends, constants)?;
+
+        Ok(aggregate_verify(
+            &self.aggregated_signature,
+            required_signatures
+                .iter()
+                .map(|required| (required.public_key(), required.final_message())),
+        ))
+    }
+}
+
+/// Parses the offer and checks its aggregated signature offline, without needing a full node.
+/// This can be used to screen out forged offers before they are displayed or taken.
+pub fn verify_offer_signature(
+    allocator: &mut Allocator,
+    offer: &Offer,
+    constants: &AggSigConstants,
+) -> Result<bool, OfferError> {
+    offer
+        .clone()
+        .parse(allocator)?
+        .verify_signature(allocator, constants)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::{sign, SecretKey, Signature};
+    use chia_protocol::{Bytes32, SpendBundle};
+    use chia_puzzles::offer::{Payment, SETTLEMENT_PAYMENTS_PUZZLE_HASH};
+    use chia_sdk_driver::{CatLayer, Layer, SpendContext, StandardLayer};
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::{Conditions, MAINNET_CONSTANTS};
+
+    use super::*;
+
+    /// Creates an offer of 1000 mojos for 100 CAT mojos, signed by the maker unless another key is given.
+    fn signed_offer(
+        sim: &mut Simulator,
+        ctx: &mut SpendContext,
+        constants: &AggSigConstants,
+        signer: Option<&SecretKey>,
+    ) -> anyhow::Result<Offer> {
+        let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1000)?;
+        let p2 = StandardLayer::new(pk);
+
+        let settlement_puzzle = ctx.settlement_payments_puzzle()?;
+        let cat_settlement_puzzle =
+            CatLayer::new(Bytes32::new([42; 32]), settlement_puzzle).construct_puzzle(ctx)?;
+
+        let (assertions, builder) = Offer::build(&[coin])
+            .request(
+                ctx,
+                &cat_settlement_puzzle,
+                vec![Payment::new(puzzle_hash, 100)],
+            )?
+            .finish();
+
+        p2.spend(
+            ctx,
+            coin,
+            Conditions::new()
+                .create_coin(settlement_hash, 1000, Vec::new())
+                .extend(assertions),
+        )?;
+
+        let coin_spends = ctx.take();
+        let signer = signer.unwrap_or(&sk);
+
+        let mut aggregated_signature = Signature::default();
+        for required in
+            RequiredSignature::from_coin_spends(&mut ctx.allocator, &coin_spends, constants)?
+        {
+            aggregated_signature += &sign(signer, required.final_message());
+        }
+
+        Ok(builder.bundle(ctx, SpendBundle::new(coin_spends, aggregated_signature))?)
+    }
+
+    #[test]
+    fn test_verify_valid_offer_signature() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+
+        let offer = signed_offer(&mut sim, ctx, &constants, None)?;
+        assert!(verify_offer_signature(
+            &mut ctx.allocator,
+            &offer,
+            &constants
+        )?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_forged_offer_signature() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+
+        let forger = SecretKey::from_seed(&[13; 32]);
+        let offer = signed_offer(&mut sim, ctx, &constants, Some(&forger))?;
+        assert!(!verify_offer_signature(
+            &mut ctx.allocator,
+            &offer,
+            &constants
+        )?);
+
+        // A signature for a different network isn't valid either.
+        let offer = signed_offer(&mut sim, ctx, &constants, None)?;
+        let other_network = AggSigConstants::new(Bytes32::new([1; 32]));
+        assert!(!verify_offer_signature(
+            &mut ctx.allocator,
+            &offer,
+            &other_network
+        )?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+use chia_protocol::{Bytes32, Coin, CoinSpend};
+use chia_puzzles::offer::{Payment, SETTLEMENT_PAYMENTS_PUZZLE_HASH};
+use chia_sdk_driver::{Cat, CatInfo, HashedPtr, Nft, NftInfo, Puzzle};
+use chia_sdk_types::{run_puzzle, Condition};
+use clvm_traits::{FromClvm, ToClvm};
+use clvmr::Allocator;
+
+use crate::{Offer, OfferError, ParsedOffer};
+
+/// The kind of asset being offered or requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OfferAsset {
+    Xch,
+    /// A CAT with the given asset id.
+    Cat(Bytes32),
+    /// An NFT with the given launcher id.
+    Nft(Bytes32),
+}
+
+/// A coin that the maker of the offer locked into the settlement payments puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OfferedCoin {
+    pub asset: OfferAsset,
+    pub coin: Coin,
+}
+
+/// A payment that the taker of the offer must make to the maker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestedPayment {
+    pub asset: OfferAsset,
+    pub nonce: Bytes32,
+    pub payment: Payment,
+}
+
+/// What an offer gives to the taker and what it asks for in return.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OfferSummary {
+    /// The coins spent by the maker of the offer, including any which aren't offered directly.
+    pub maker_coins: Vec<Coin>,
+    pub offered: Vec<OfferedCoin>,
+    pub requested: Vec<RequestedPayment>,
+    /// The earliest `ASSERT_BEFORE_SECONDS_ABSOLUTE` timestamp in the maker's coin spends, if any.
+    /// The offer can't be taken once a transaction block with this timestamp or later has been farmed.
+    pub expires_at: Option<u64>,
+}
+
+impl Offer {
+    /// Parses the offer and summarizes the offered coins and requested payments.
+    pub fn summary(&self, allocator: &mut Allocator) -> Result<OfferSummary, OfferError> {
+        self.clone().parse(allocator)?.summary(allocator)
+    }
+
+    /// Parses the offer and returns the timestamp at which it expires, if any.
+    /// See [`ParsedOffer::expiration`] for more details.
+    pub fn expiration(&self, allocator: &mut Allocator) -> Result<Option<u64>, OfferError> {
+        self.clone().parse(allocator)?.expiration(allocator)
+    }
+}
+
+impl ParsedOffer {
+    /// Summarizes the offered coins and requested payments.
+    ///
+    /// Offered coins are the children of the maker's coin spends which are locked into the
+    /// settlement payments puzzle, optionally wrapped as a CAT or NFT.
+    pub fn summary(&self, allocator: &mut Allocator) -> Result<OfferSummary, OfferError> {
+        let mut summary = OfferSummary {
+            expires_at: self.expiration(allocator)?,
+            ..Default::default()
+        };
+
+        for coin_spend in &self.coin_spends {
+            summary.maker_coins.push(coin_spend.coin);
+
+            for settlement_coin in settlement_coins(allocator, coin_spend)? {
+                summary.offered.push(match settlement_coin {
+                    SettlementCoin::Xch(coin) => OfferedCoin {
+                        asset: OfferAsset::Xch,
+                        coin,
+                    },
+                    SettlementCoin::Cat(cat) => OfferedCoin {
+                        asset: OfferAsset::Cat(cat.asset_id),
+                        coin: cat.coin,
+                    },
+                    SettlementCoin::Nft(nft) => OfferedCoin {
+                        asset: OfferAsset::Nft(nft.info.launcher_id),
+                        coin: nft.coin,
+                    },
+                });
+            }
+        }
+
+        for (puzzle_hash, (puzzle, notarized_payments)) in &self.requested_payments {
+            let asset = requested_asset(allocator, *puzzle_hash, *puzzle)?;
+
+            for notarized_payment in notarized_payments {
+                for payment in &notarized_payment.payments {
+                    summary.requested.push(RequestedPayment {
+                        asset,
+                        nonce: notarized_payment.nonce,
+                        payment: payment.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Returns the earliest `ASSERT_BEFORE_SECONDS_ABSOLUTE` timestamp in the maker's coin spends, if any.
+    /// The offer can't be taken once a transaction block with this timestamp or later has been farmed.
+    pub fn expiration(&self, allocator: &mut Allocator) -> Result<Option<u64>, OfferError> {
+        let mut expires_at: Option<u64> = None;
+
+        for coin_spend in &self.coin_spends {
+            let puzzle = coin_spend.puzzle_reveal.to_clvm(allocator)?;
+            let solution = coin_spend.solution.to_clvm(allocator)?;
+            let output = run_puzzle(allocator, puzzle, solution)?;
+            let conditions = Vec::<Condition>::from_clvm(allocator, output)?;
+
+            for condition in conditions {
+                if let Condition::AssertBeforeSecondsAbsolute(condition) = condition {
+                    expires_at = Some(
+                        expires_at
+                            .map_or(condition.seconds, |seconds| seconds.min(condition.seconds)),
+                    );
+                }
+            }
+        }
+
+        Ok(expires_at)
+    }
+}
+
+/// A child of a coin spend which is locked into the settlement payments puzzle.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SettlementCoin {
+    Xch(Coin),
+    Cat(Cat),
+    Nft(Nft<HashedPtr>),
+}
+
+/// Finds the children of a coin spend which are locked into the settlement payments puzzle,
+/// optionally wrapped as a CAT or NFT.
+pub(crate) fn settlement_coins(
+    allocator: &mut Allocator,
+    coin_spend: &CoinSpend,
+) -> Result<Vec<SettlementCoin>, OfferError> {
+    let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+    let puzzle_ptr = coin_spend.puzzle_reveal.to_clvm(allocator)?;
+    let solution = coin_spend.solution.to_clvm(allocator)?;
+    let puzzle = Puzzle::parse(allocator, puzzle_ptr);
+
+    if let Some(cats) = Cat::parse_children(allocator, coin_spend.coin, puzzle, solution)? {
+        return Ok(cats
+            .into_iter()
+            .filter(|cat| cat.p2_puzzle_hash == settlement_hash)
+            .map(SettlementCoin::Cat)
+            .collect());
+    }
+
+    if let Some(nft) = Nft::<HashedPtr>::parse_child(allocator, coin_spend.coin, puzzle, solution)?
+    {
+        if nft.info.p2_puzzle_hash == settlement_hash {
+            return Ok(vec![SettlementCoin::Nft(nft)]);
+        }
+        return Ok(Vec::new());
+    }
+
+    let output = run_puzzle(allocator, puzzle_ptr, solution)?;
+    let conditions = Vec::<Condition>::from_clvm(allocator, output)?;
+
+    Ok(conditions
+        .into_iter()
+        .filter_map(Condition::into_create_coin)
+        .filter(|create_coin| create_coin.puzzle_hash == settlement_hash)
+        .map(|create_coin| {
+            SettlementCoin::Xch(Coin::new(
+                coin_spend.coin.coin_id(),
+                create_coin.puzzle_hash,
+                create_coin.amount,
+            ))
+        })
+        .collect())
+}
+
+fn requested_asset(
+    allocator: &Allocator,
+    puzzle_hash: Bytes32,
+    puzzle: Puzzle,
+) -> Result<OfferAsset, OfferError> {
+    let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+    if puzzle_hash == settlement_hash {
+        return Ok(OfferAsset::Xch);
+    }
+
+    if let Some((cat, _p2_puzzle)) = CatInfo::parse(allocator, puzzle)? {
+        if cat.p2_puzzle_hash == settlement_hash {
+            return Ok(OfferAsset::Cat(cat.asset_id));
+        }
+    }
+
+    if let Some((nft, _p2_puzzle)) = NftInfo::<HashedPtr>::parse(allocator, puzzle)? {
+        if nft.p2_puzzle_hash == settlement_hash {
+            return Ok(OfferAsset::Nft(nft.launcher_id));
+        }
+    }
+
+    Err(OfferError::UnsupportedRequestedPuzzle(puzzle_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::Signature;
+    use chia_protocol::SpendBundle;
+    use chia_puzzles::{nft::NftMetadata, offer::SETTLEMENT_PAYMENTS_PUZZLE_HASH};
+    use chia_sdk_driver::{SpendContext, StandardLayer};
+    use chia_sdk_test::Simulator;
+    use hex_literal::hex;
+
+    use super::*;
+
+    const DECOMPRESSED_OFFER: &str = include_str!("../test_data/decompressed.offer");
+
+    #[test]
+    fn test_summarize_sample_offer() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let offer = Offer::from_bytes(&hex::decode(DECOMPRESSED_OFFER.trim())?)?;
+        let summary = offer.summary(&mut allocator)?;
+
+        assert_eq!(summary.offered.len(), 1);
+        assert!(matches!(summary.offered[0].asset, OfferAsset::Nft(..)));
+        assert_eq!(summary.offered[0].coin.amount, 1);
+
+        assert_eq!(summary.requested.len(), 1);
+        assert_eq!(
+            summary.requested[0].asset,
+            OfferAsset::Cat(Bytes32::new(hex!(
+                "02f42883fb3338310825c951efcca810ecb61772d9e5da6a2d4d0a6591b8897e"
+            )))
+        );
+        assert_eq!(
+            summary.requested[0].payment.puzzle_hash,
+            Bytes32::new(hex!(
+                "947c5be1522aff5736bd2bb91204fca385660e3fa59e3bb7a3ee709f52809f71"
+            ))
+        );
+        assert_eq!(summary.requested[0].payment.amount, 100_000_000_000);
+        assert_eq!(summary.expires_at, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_offer_expiration() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+        let (_sk, pk, puzzle_hash, coin) = sim.new_p2(1000)?;
+        let settlement_puzzle = ctx.settlement_payments_puzzle()?;
+
+        let (conditions, builder) = Offer::build(&[coin])
+            .request(
+                ctx,
+                &settlement_puzzle,
+                vec![Payment::new(puzzle_hash, 500)],
+            )?
+            .expires_at(1000)
+            .finish_with_conditions();
+
+        StandardLayer::new(pk).spend(
+            ctx,
+            coin,
+            conditions.create_coin(settlement_hash, 1000, Vec::new()),
+        )?;
+
+        let coin_spends = ctx.take();
+        let offer = builder.bundle(ctx, SpendBundle::new(coin_spends, Signature::default()))?;
+
+        assert_eq!(offer.expiration(&mut ctx.allocator)?, Some(1000));
+        assert_eq!(offer.summary(&mut ctx.allocator)?.expires_at, Some(1000));
+
+        // Offers without an expiration never expire.
+        let offer = Offer::from_bytes(&hex::decode(DECOMPRESSED_OFFER.trim())?)?;
+        assert_eq!(offer.expiration(&mut ctx.allocator)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_sample_offer_nft_metadata() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let offer = Offer::from_bytes(&hex::decode(DECOMPRESSED_OFFER.trim())?)?;
+        let parsed = offer.parse(&mut allocator)?;
+
+        let mut nfts = Vec::new();
+
+        for coin_spend in &parsed.coin_spends {
+            let ptr = coin_spend.puzzle_reveal.to_clvm(&mut allocator)?;
+            let puzzle = Puzzle::parse(&allocator, ptr);
+            if let Some((nft, _p2_puzzle)) = NftInfo::<NftMetadata>::parse(&allocator, puzzle)? {
+                nfts.push(nft);
+            }
+        }
+
+        assert_eq!(nfts.len(), 1);
+
+        let metadata = &nfts[0].metadata;
+        assert_eq!(
+            metadata.data_uris,
+            ["https://bafkreibhrxuryf2gwygsxeklhagtmdxtsocqvjjzdqy64rjdv3rdndqng4.ipfs.nftstorage.link/"]
+        );
+        assert_eq!(
+            metadata.data_hash,
+            Some(Bytes32::new(hex!(
+                "278de91c1746b60d2b914b380d360ef393850aa5391c31ee4523aee2368e0d37"
+            )))
+        );
+        assert_eq!(
+            metadata.metadata_uris,
+            ["https://pastebin.com/raw/T5LGpBe3"]
+        );
+        assert_eq!(
+            metadata.metadata_hash,
+            Some(Bytes32::new(hex!(
+                "5158025f5b241c6ec1848972395c383548945f66c1610bfac0dea907b65e8d60"
+            )))
+        );
+        assert!(metadata.license_uris.is_empty());
+        assert_eq!(metadata.license_hash, None);
+        assert_eq!(metadata.edition_number, 1);
+        assert_eq!(metadata.edition_total, 1);
+
+        // The typed metadata round trips through CLVM without losing any fields.
+        let ptr = metadata.to_clvm(&mut allocator)?;
+        assert_eq!(NftMetadata::from_clvm(&allocator, ptr)?, *metadata);
+
+        Ok(())
+    }
+}
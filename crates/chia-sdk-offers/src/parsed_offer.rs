@@ -1,10 +1,16 @@
-use chia_bls::Signature;
+use chia_bls::{PublicKey, Signature};
 use chia_protocol::{Bytes32, CoinSpend};
-use chia_puzzles::offer::NotarizedPayment;
-use chia_sdk_driver::Puzzle;
+use chia_puzzles::{offer::NotarizedPayment, standard::StandardArgs};
+use chia_sdk_driver::{
+    Cat, CatInfo, CatLayer, CatSpend, HashedPtr, Layer, Nft, NftInfo, Puzzle, SingletonLayer,
+    SpendContext, SpendWithConditions, StandardLayer,
+};
+use chia_sdk_types::Conditions;
+use clvm_traits::ToClvm;
+use clvmr::Allocator;
 use indexmap::IndexMap;
 
-use crate::{OfferBuilder, Take};
+use crate::{OfferBuilder, OfferError, Take};
 
 #[derive(Debug, Default, Clone)]
 pub struct ParsedOffer {
@@ -17,4 +23,143 @@ impl ParsedOffer {
     pub fn take(self) -> OfferBuilder<Take> {
         OfferBuilder::from_parsed_offer(self)
     }
+
+    /// Cancels the offer by spending the maker's coins back to the standard puzzle of the given synthetic key.
+    /// Once any of the coins have been spent, the offer can no longer be taken.
+    ///
+    /// XCH, CAT, and NFT coins are supported. Coin spends which aren't owned by the key are ignored.
+    pub fn cancel(
+        &self,
+        ctx: &mut SpendContext,
+        synthetic_key: PublicKey,
+    ) -> Result<(), OfferError> {
+        let p2 = StandardLayer::new(synthetic_key);
+        let p2_puzzle_hash: Bytes32 = StandardArgs::curry_tree_hash(synthetic_key).into();
+
+        let mut cat_spends: IndexMap<Bytes32, Vec<CatSpend>> = IndexMap::new();
+
+        for coin_spend in &self.coin_spends {
+            let coin = coin_spend.coin;
+            let puzzle = coin_spend.puzzle_reveal.to_clvm(&mut ctx.allocator)?;
+            let solution = coin_spend.solution.to_clvm(&mut ctx.allocator)?;
+            let puzzle = Puzzle::parse(&ctx.allocator, puzzle);
+
+            if let Some((info, p2_puzzle)) = NftInfo::<HashedPtr>::parse(&ctx.allocator, puzzle)? {
+                if !is_owned_by(&ctx.allocator, p2_puzzle, synthetic_key)? {
+                    continue;
+                }
+
+                let solution = SingletonLayer::<Puzzle>::parse_solution(&ctx.allocator, solution)?;
+                let nft = Nft::new(coin, solution.lineage_proof, info);
+                let _nft = nft.transfer(ctx, &p2, p2_puzzle_hash, Conditions::new())?;
+                continue;
+            }
+
+            if let Some((info, p2_puzzle)) = CatInfo::parse(&ctx.allocator, puzzle)? {
+                if !is_owned_by(&ctx.allocator, p2_puzzle, synthetic_key)? {
+                    continue;
+                }
+
+                let solution = CatLayer::<Puzzle>::parse_solution(&ctx.allocator, solution)?;
+                let cat = Cat::new(
+                    coin,
+                    solution.lineage_proof,
+                    info.asset_id,
+                    info.p2_puzzle_hash,
+                );
+
+                let inner_spend = p2.spend_with_conditions(
+                    ctx,
+                    Conditions::new().create_coin(
+                        p2_puzzle_hash,
+                        coin.amount,
+                        vec![p2_puzzle_hash.into()],
+                    ),
+                )?;
+
+                cat_spends
+                    .entry(info.asset_id)
+                    .or_default()
+                    .push(CatSpend::new(cat, inner_spend));
+                continue;
+            }
+
+            if is_owned_by(&ctx.allocator, puzzle, synthetic_key)? {
+                p2.spend(
+                    ctx,
+                    coin,
+                    Conditions::new().create_coin(p2_puzzle_hash, coin.amount, Vec::new()),
+                )?;
+            }
+        }
+
+        // Each asset's CATs must be spent together in a ring.
+        for cat_spends in cat_spends.values() {
+            Cat::spend_all(ctx, cat_spends)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_owned_by(
+    allocator: &Allocator,
+    puzzle: Puzzle,
+    synthetic_key: PublicKey,
+) -> Result<bool, OfferError> {
+    Ok(StandardLayer::parse_puzzle(allocator, puzzle)?
+        .is_some_and(|layer| layer.synthetic_key == synthetic_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::{Coin, SpendBundle};
+    use chia_puzzles::offer::SETTLEMENT_PAYMENTS_PUZZLE_HASH;
+    use chia_sdk_test::Simulator;
+
+    use crate::Offer;
+
+    use super::*;
+
+    #[test]
+    fn test_cancel_offer() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let (sk, pk, puzzle_hash, coin) = sim.new_p2(1)?;
+        let other_coin = sim.new_coin(puzzle_hash, 2);
+        let p2 = StandardLayer::new(pk);
+
+        let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+        for coin in [coin, other_coin] {
+            p2.spend(
+                ctx,
+                coin,
+                Conditions::new().create_coin(settlement_hash, coin.amount, Vec::new()),
+            )?;
+        }
+
+        let asset_id = Bytes32::new([42; 32]);
+        let (cat_coin, lineage_proof) = sim.new_cat_coin(asset_id, puzzle_hash, 3);
+        let cat = Cat::new(cat_coin, Some(lineage_proof), asset_id, puzzle_hash);
+        let inner_spend = p2.spend_with_conditions(
+            ctx,
+            Conditions::new().create_coin(settlement_hash, 3, vec![settlement_hash.into()]),
+        )?;
+        Cat::spend_all(ctx, &[CatSpend::new(cat, inner_spend)])?;
+
+        let offer = Offer::new(SpendBundle::new(ctx.take(), Signature::default()));
+        offer.cancel(ctx, pk)?;
+        sim.spend_coins(ctx.take(), &[sk])?;
+
+        for coin in [coin, other_coin] {
+            let child = Coin::new(coin.coin_id(), puzzle_hash, coin.amount);
+            assert!(sim.coin_state(child.coin_id()).is_some());
+        }
+
+        let child = cat.wrapped_child(puzzle_hash, 3);
+        assert!(sim.coin_state(child.coin.coin_id()).is_some());
+
+        Ok(())
+    }
 }
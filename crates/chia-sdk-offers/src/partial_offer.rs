@@ -0,0 +1,307 @@
+use chia_protocol::Bytes32;
+use chia_puzzles::offer::Payment;
+use chia_sdk_driver::{CatLayer, Layer, SpendContext};
+use indexmap::IndexMap;
+
+use crate::{Make, OfferAsset, OfferBuilder, OfferError, OfferSummary, RequestedPayment};
+
+/// The amounts exchanged when taking a percentage of a divisible offer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartialTake {
+    /// The total amount of each offered asset that the taker receives.
+    pub offered: IndexMap<OfferAsset, u64>,
+    /// The amount of each offered asset that isn't taken, and is returned to the maker.
+    pub remaining: IndexMap<OfferAsset, u64>,
+    /// The requested payments, scaled down proportionally.
+    pub requested: Vec<RequestedPayment>,
+}
+
+/// Calculates the amounts exchanged when taking the given percentage of an offer.
+///
+/// The total of each offered asset and every requested payment must divide evenly, so that both
+/// sides are scaled by exactly the same ratio. This means NFTs can only be taken in full.
+pub fn take_partial(summary: &OfferSummary, percentage: u8) -> Result<PartialTake, OfferError> {
+    if percentage == 0 || percentage > 100 {
+        return Err(OfferError::InvalidPercentage(percentage));
+    }
+
+    let mut totals: IndexMap<OfferAsset, u128> = IndexMap::new();
+
+    for offered in &summary.offered {
+        *totals.entry(offered.asset).or_default() += u128::from(offered.coin.amount);
+    }
+
+    let mut partial = PartialTake::default();
+
+    for (asset, total) in totals {
+        let total = u64::try_from(total)?;
+        let amount = scale_amount(total, percentage)?;
+        partial.offered.insert(asset, amount);
+
+        if amount < total {
+            partial.remaining.insert(asset, total - amount);
+        }
+    }
+
+    for requested in &summary.requested {
+        partial.requested.push(RequestedPayment {
+            payment: Payment {
+                amount: scale_amount(requested.payment.amount, percentage)?,
+                ..requested.payment.clone()
+            },
+            ..requested.clone()
+        });
+    }
+
+    Ok(partial)
+}
+
+impl PartialTake {
+    /// Adds the maker's side of the partial offer to an [`OfferBuilder`].
+    ///
+    /// The maker's spends assert the exact notarized payments, so the maker has to sign for the scaled
+    /// amounts. This requests the scaled payments from the taker, as well as the remaining offered amount
+    /// of each asset as a payment back to the maker, so that the taker can only claim their share of the
+    /// settlement coins. Only XCH and CATs can be partially offered or requested.
+    pub fn request(
+        &self,
+        ctx: &mut SpendContext,
+        builder: OfferBuilder<Make>,
+        maker_puzzle_hash: Bytes32,
+    ) -> Result<OfferBuilder<Make>, OfferError> {
+        let mut payments: IndexMap<OfferAsset, Vec<Payment>> = IndexMap::new();
+
+        for requested in &self.requested {
+            payments
+                .entry(requested.asset)
+                .or_default()
+                .push(requested.payment.clone());
+        }
+
+        for (asset, amount) in &self.remaining {
+            payments
+                .entry(*asset)
+                .or_default()
+                .push(Payment::new(maker_puzzle_hash, *amount));
+        }
+
+        let mut builder = builder;
+
+        for (asset, payments) in payments {
+            let settlement_puzzle = ctx.settlement_payments_puzzle()?;
+
+            let puzzle = match asset {
+                OfferAsset::Xch => settlement_puzzle,
+                OfferAsset::Cat(asset_id) => {
+                    CatLayer::new(asset_id, settlement_puzzle).construct_puzzle(ctx)?
+                }
+                OfferAsset::Nft(..) => return Err(OfferError::UnsupportedPartialAsset(asset)),
+            };
+
+            builder = builder.request(ctx, &puzzle, payments)?;
+        }
+
+        Ok(builder)
+    }
+}
+
+fn scale_amount(amount: u64, percentage: u8) -> Result<u64, OfferError> {
+    let scaled = u128::from(amount) * u128::from(percentage);
+
+    if scaled % 100 != 0 {
+        return Err(OfferError::IndivisibleAmount { amount, percentage });
+    }
+
+    Ok((scaled / 100).try_into()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::Signature;
+    use chia_protocol::{Coin, SpendBundle};
+    use chia_puzzles::offer::SETTLEMENT_PAYMENTS_PUZZLE_HASH;
+    use chia_sdk_driver::{Cat, CatSpend, SpendWithConditions, StandardLayer};
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::Conditions;
+
+    use crate::{take_offer, Offer, OfferedCoin};
+
+    use super::*;
+
+    fn offer_summary(asset: OfferAsset, coins: &[u64], requested_amount: u64) -> OfferSummary {
+        OfferSummary {
+            maker_coins: Vec::new(),
+            offered: (0..)
+                .zip(coins)
+                .map(|(i, amount)| OfferedCoin {
+                    asset,
+                    coin: Coin::new(Bytes32::new([i; 32]), Bytes32::default(), *amount),
+                })
+                .collect(),
+            requested: vec![RequestedPayment {
+                asset: OfferAsset::Xch,
+                nonce: Bytes32::default(),
+                payment: Payment::new(Bytes32::new([3; 32]), requested_amount),
+            }],
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_take_partial_cat_offer() -> anyhow::Result<()> {
+        let asset = OfferAsset::Cat(Bytes32::new([42; 32]));
+        let partial = take_partial(&offer_summary(asset, &[600, 400], 5000), 30)?;
+
+        assert_eq!(partial.offered.get(&asset), Some(&300));
+        assert_eq!(partial.remaining.get(&asset), Some(&700));
+        assert_eq!(partial.requested.len(), 1);
+        assert_eq!(partial.requested[0].payment.amount, 1500);
+        assert_eq!(
+            partial.requested[0].payment.puzzle_hash,
+            Bytes32::new([3; 32])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_take_partial_scales_total() -> anyhow::Result<()> {
+        // Neither coin divides evenly on its own, but the total does.
+        let asset = OfferAsset::Cat(Bytes32::new([42; 32]));
+        let partial = take_partial(&offer_summary(asset, &[333, 667], 5000), 30)?;
+
+        assert_eq!(partial.offered.get(&asset), Some(&300));
+        assert_eq!(partial.remaining.get(&asset), Some(&700));
+
+        let partial = take_partial(&offer_summary(asset, &[333, 667], 5000), 100)?;
+        assert_eq!(partial.offered.get(&asset), Some(&1000));
+        assert!(partial.remaining.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_take_partial_indivisible() {
+        let asset = OfferAsset::Cat(Bytes32::new([42; 32]));
+
+        assert!(matches!(
+            take_partial(&offer_summary(asset, &[600, 400], 333), 30),
+            Err(OfferError::IndivisibleAmount {
+                amount: 333,
+                percentage: 30
+            })
+        ));
+        assert!(matches!(
+            take_partial(&offer_summary(asset, &[333, 660], 5000), 30),
+            Err(OfferError::IndivisibleAmount {
+                amount: 993,
+                percentage: 30
+            })
+        ));
+        assert!(matches!(
+            take_partial(&offer_summary(asset, &[600, 400], 5000), 0),
+            Err(OfferError::InvalidPercentage(0))
+        ));
+        assert!(matches!(
+            take_partial(&offer_summary(asset, &[600, 400], 5000), 101),
+            Err(OfferError::InvalidPercentage(101))
+        ));
+    }
+
+    #[test]
+    fn test_take_partial_offer() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+        let (maker_secret_key, maker_public_key, maker_puzzle_hash, first_coin) =
+            sim.new_p2(333)?;
+        let second_coin = sim.new_coin(maker_puzzle_hash, 667);
+        let maker_p2 = StandardLayer::new(maker_public_key);
+
+        let (taker_secret_key, taker_public_key, taker_puzzle_hash, _coin) = sim.new_p2(0)?;
+        let taker_p2 = StandardLayer::new(taker_public_key);
+
+        let asset_id = Bytes32::new([42; 32]);
+        let (cat_coin, lineage_proof) = sim.new_cat_coin(asset_id, taker_puzzle_hash, 1500);
+        let cat = Cat::new(cat_coin, Some(lineage_proof), asset_id, taker_puzzle_hash);
+
+        // The maker offers 1000 mojos for 5000 CAT mojos, and the taker takes 30% of it.
+        let summary = OfferSummary {
+            maker_coins: Vec::new(),
+            offered: [first_coin, second_coin]
+                .into_iter()
+                .map(|coin| OfferedCoin {
+                    asset: OfferAsset::Xch,
+                    coin,
+                })
+                .collect(),
+            requested: vec![RequestedPayment {
+                asset: OfferAsset::Cat(asset_id),
+                nonce: Bytes32::default(),
+                payment: Payment::new(maker_puzzle_hash, 5000),
+            }],
+            expires_at: None,
+        };
+        let partial = take_partial(&summary, 30)?;
+
+        let builder = Offer::build(&[first_coin, second_coin]);
+        let (assertions, builder) = partial.request(ctx, builder, maker_puzzle_hash)?.finish();
+
+        maker_p2.spend(
+            ctx,
+            first_coin,
+            Conditions::new()
+                .create_coin(settlement_hash, 1000, Vec::new())
+                .extend(assertions),
+        )?;
+        maker_p2.spend(ctx, second_coin, Conditions::new())?;
+
+        let coin_spends = ctx.take();
+        let maker_offer =
+            builder.bundle(ctx, SpendBundle::new(coin_spends, Signature::default()))?;
+
+        // The taker pays 1500 CAT mojos for 300 of the offered mojos.
+        let settlement_puzzle = ctx.settlement_payments_puzzle()?;
+        let (assertions, builder) = Offer::build(&[cat.coin])
+            .request(
+                ctx,
+                &settlement_puzzle,
+                vec![Payment::new(taker_puzzle_hash, 300)],
+            )?
+            .finish();
+
+        let inner_spend = taker_p2.spend_with_conditions(
+            ctx,
+            Conditions::new()
+                .create_coin(settlement_hash, 1500, vec![settlement_hash.into()])
+                .extend(assertions),
+        )?;
+        Cat::spend_all(ctx, &[CatSpend::new(cat, inner_spend)])?;
+
+        let coin_spends = ctx.take();
+        let taker_spend: SpendBundle = builder
+            .bundle(ctx, SpendBundle::new(coin_spends, Signature::default()))?
+            .into();
+
+        let spend_bundle = take_offer(maker_offer, taker_spend)?;
+        sim.spend_coins(
+            spend_bundle.coin_spends,
+            &[maker_secret_key, taker_secret_key],
+        )?;
+
+        // The settlement coin is split between the taker and the maker's change.
+        let settlement_coin = Coin::new(first_coin.coin_id(), settlement_hash, 1000);
+        for (puzzle_hash, amount) in [(maker_puzzle_hash, 700), (taker_puzzle_hash, 300)] {
+            let coin = Coin::new(settlement_coin.coin_id(), puzzle_hash, amount);
+            assert!(sim.coin_state(coin.coin_id()).is_some());
+        }
+
+        let maker_cat = cat
+            .wrapped_child(settlement_hash, 1500)
+            .wrapped_child(maker_puzzle_hash, 1500);
+        assert!(sim.coin_state(maker_cat.coin.coin_id()).is_some());
+
+        Ok(())
+    }
+}
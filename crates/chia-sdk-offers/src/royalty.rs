@@ -0,0 +1,118 @@
+use chia_protocol::Bytes32;
+use chia_puzzles::offer::{NotarizedPayment, Payment};
+
+/// A payment of royalties to the creator of an NFT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoyaltyPayment {
+    pub puzzle_hash: Bytes32,
+    pub amount: u64,
+}
+
+/// The requested payments of an offer, after the royalties have been split off.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RoyaltySplit {
+    pub seller_payments: Vec<NotarizedPayment>,
+    pub royalty_payments: Vec<RoyaltyPayment>,
+}
+
+/// Calculates the royalty owed on a trade, rounded down.
+///
+/// The royalty is in ten thousandths, the same unit used by [`RoyaltyTransferLayer`](chia_sdk_driver::RoyaltyTransferLayer).
+/// For example, a royalty of 2.5% is represented as 250.
+pub fn royalty_amount(trade_price: u64, royalty_ten_thousandths: u16) -> u64 {
+    let amount = u128::from(trade_price) * u128::from(royalty_ten_thousandths) / 10_000;
+    u64::try_from(amount).unwrap_or(u64::MAX)
+}
+
+/// Splits each requested payment into the amount that goes to the seller and the royalty amount.
+/// Royalties which round down to zero are omitted.
+pub fn split_royalties(
+    notarized_payments: &[NotarizedPayment],
+    royalty_puzzle_hash: Bytes32,
+    royalty_ten_thousandths: u16,
+) -> RoyaltySplit {
+    let mut split = RoyaltySplit::default();
+
+    for notarized_payment in notarized_payments {
+        let mut payments = Vec::with_capacity(notarized_payment.payments.len());
+
+        for payment in &notarized_payment.payments {
+            let royalty =
+                royalty_amount(payment.amount, royalty_ten_thousandths).min(payment.amount);
+
+            if royalty > 0 {
+                split.royalty_payments.push(RoyaltyPayment {
+                    puzzle_hash: royalty_puzzle_hash,
+                    amount: royalty,
+                });
+            }
+
+            payments.push(Payment {
+                amount: payment.amount - royalty,
+                ..payment.clone()
+            });
+        }
+
+        split.seller_payments.push(NotarizedPayment {
+            nonce: notarized_payment.nonce,
+            payments,
+        });
+    }
+
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_royalty_amount() {
+        assert_eq!(royalty_amount(1000, 250), 25);
+        assert_eq!(royalty_amount(1_000_000_000_000, 250), 25_000_000_000);
+        assert_eq!(royalty_amount(999, 250), 24);
+        assert_eq!(royalty_amount(39, 250), 0);
+        assert_eq!(royalty_amount(40, 250), 1);
+        assert_eq!(royalty_amount(1000, 0), 0);
+        assert_eq!(royalty_amount(u64::MAX, 10_000), u64::MAX);
+    }
+
+    #[test]
+    fn test_split_royalties() {
+        let seller = Bytes32::new([1; 32]);
+        let creator = Bytes32::new([2; 32]);
+
+        let notarized_payments = vec![NotarizedPayment {
+            nonce: Bytes32::default(),
+            payments: vec![Payment::new(seller, 1001), Payment::new(seller, 10)],
+        }];
+
+        let split = split_royalties(&notarized_payments, creator, 250);
+
+        assert_eq!(
+            split.royalty_payments,
+            [RoyaltyPayment {
+                puzzle_hash: creator,
+                amount: 25
+            }]
+        );
+
+        let seller_payments = &split.seller_payments[0].payments;
+        assert_eq!(seller_payments[0].amount, 976);
+        assert_eq!(seller_payments[0].puzzle_hash, seller);
+        assert_eq!(seller_payments[1].amount, 10);
+    }
+
+    #[test]
+    fn test_split_no_royalties() {
+        let notarized_payments = vec![NotarizedPayment {
+            nonce: Bytes32::default(),
+            payments: vec![Payment::new(Bytes32::default(), 1000)],
+        }];
+
+        let split = split_royalties(&notarized_payments, Bytes32::default(), 0);
+
+        assert!(split.royalty_payments.is_empty());
+        assert_eq!(split.seller_payments, notarized_payments);
+    }
+}
@@ -0,0 +1,191 @@
+use chia_protocol::Bytes32;
+use chia_puzzles::offer::{NotarizedPayment, Payment, SettlementPaymentsSolution};
+use chia_sdk_driver::{DriverError, Layer, SettlementLayer, Spend, SpendContext};
+use chia_sdk_types::announcement_id;
+use clvm_utils::ToTreeHash;
+
+/// Builds the solution to the settlement payments puzzle, which is a list of `(nonce . payments)` pairs.
+/// Both versions of the puzzle take a solution with the same structure.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SettlementPayments {
+    notarized_payments: Vec<NotarizedPayment>,
+}
+
+impl SettlementPayments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a payment under a nonce. Payments which share a nonce are grouped into the same
+    /// notarized payment, in the order they were added.
+    #[must_use]
+    pub fn payment(mut self, nonce: Bytes32, payment: Payment) -> Self {
+        if let Some(notarized_payment) = self
+            .notarized_payments
+            .iter_mut()
+            .find(|notarized_payment| notarized_payment.nonce == nonce)
+        {
+            notarized_payment.payments.push(payment);
+        } else {
+            self.notarized_payments.push(NotarizedPayment {
+                nonce,
+                payments: vec![payment],
+            });
+        }
+        self
+    }
+
+    /// Adds every payment of a notarized payment, such as one requested by an offer.
+    #[must_use]
+    pub fn notarized_payment(self, notarized_payment: NotarizedPayment) -> Self {
+        let nonce = notarized_payment.nonce;
+        notarized_payment
+            .payments
+            .into_iter()
+            .fold(self, |builder, payment| builder.payment(nonce, payment))
+    }
+
+    pub fn notarized_payments(&self) -> &[NotarizedPayment] {
+        &self.notarized_payments
+    }
+
+    pub fn into_solution(self) -> SettlementPaymentsSolution {
+        SettlementPaymentsSolution {
+            notarized_payments: self.notarized_payments,
+        }
+    }
+
+    /// Creates a spend of the settlement payments puzzle which makes each of the payments.
+    pub fn spend(self, ctx: &mut SpendContext) -> Result<Spend, DriverError> {
+        SettlementLayer.construct_spend(ctx, self.into_solution())
+    }
+}
+
+impl From<SettlementPaymentsSolution> for SettlementPayments {
+    fn from(solution: SettlementPaymentsSolution) -> Self {
+        Self {
+            notarized_payments: solution.notarized_payments,
+        }
+    }
+}
+
+/// Calculates the id of the puzzle announcement that a settlement coin makes when it pays a notarized payment.
+/// The other side of the offer asserts this announcement, so that it can only be spent if the payment is made.
+///
+/// The puzzle hash is of the full settlement coin puzzle, including any outer layers such as a CAT.
+pub fn settlement_announcement_id(
+    settlement_puzzle_hash: Bytes32,
+    notarized_payment: &NotarizedPayment,
+) -> Bytes32 {
+    announcement_id(settlement_puzzle_hash, notarized_payment.tree_hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_sdk_types::{run_puzzle, Condition};
+    use clvm_traits::{FromClvm, ToClvm};
+    use clvmr::Allocator;
+    use hex_literal::hex;
+
+    use crate::Offer;
+
+    use super::*;
+
+    const DECOMPRESSED_OFFER: &str = include_str!("../test_data/decompressed.offer");
+
+    #[test]
+    fn test_settlement_payments_round_trip() -> anyhow::Result<()> {
+        let ctx = &mut SpendContext::new();
+
+        let first_nonce = Bytes32::new([1; 32]);
+        let second_nonce = Bytes32::new([2; 32]);
+
+        let payments = SettlementPayments::new()
+            .payment(first_nonce, Payment::new(Bytes32::new([3; 32]), 100))
+            .payment(second_nonce, Payment::new(Bytes32::new([4; 32]), 200))
+            .payment(first_nonce, Payment::new(Bytes32::new([5; 32]), 300));
+
+        assert_eq!(payments.notarized_payments().len(), 2);
+        assert_eq!(payments.notarized_payments()[0].payments.len(), 2);
+
+        let solution = ctx.alloc(&payments.clone().into_solution())?;
+        let parsed = SettlementPayments::from(SettlementPaymentsSolution::from_clvm(
+            &ctx.allocator,
+            solution,
+        )?);
+        assert_eq!(parsed, payments);
+
+        let rebuilt = parsed.notarized_payments().iter().cloned().fold(
+            SettlementPayments::new(),
+            SettlementPayments::notarized_payment,
+        );
+        assert_eq!(rebuilt, payments);
+
+        // The settlement payments puzzle creates a coin for each payment and announces each notarized payment.
+        let spend = payments.spend(ctx)?;
+        let output = run_puzzle(&mut ctx.allocator, spend.puzzle, spend.solution)?;
+        let conditions = Vec::<Condition>::from_clvm(&ctx.allocator, output)?;
+
+        let created: Vec<(Bytes32, u64)> = conditions
+            .iter()
+            .cloned()
+            .filter_map(Condition::into_create_coin)
+            .map(|create_coin| (create_coin.puzzle_hash, create_coin.amount))
+            .collect();
+        assert_eq!(
+            created,
+            [
+                (Bytes32::new([3; 32]), 100),
+                (Bytes32::new([5; 32]), 300),
+                (Bytes32::new([4; 32]), 200)
+            ]
+        );
+        assert_eq!(
+            conditions
+                .iter()
+                .filter(|condition| matches!(condition, Condition::CreatePuzzleAnnouncement(..)))
+                .count(),
+            2
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_offer_settlement_announcement() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let offer = Offer::from_bytes(&hex::decode(DECOMPRESSED_OFFER.trim())?)?;
+        let parsed = offer.parse(&mut allocator)?;
+
+        let mut asserted = Vec::new();
+
+        for coin_spend in &parsed.coin_spends {
+            let puzzle = coin_spend.puzzle_reveal.to_clvm(&mut allocator)?;
+            let solution = coin_spend.solution.to_clvm(&mut allocator)?;
+            let output = run_puzzle(&mut allocator, puzzle, solution)?;
+
+            for condition in Vec::<Condition>::from_clvm(&allocator, output)? {
+                if let Condition::AssertPuzzleAnnouncement(condition) = condition {
+                    asserted.push(condition.announcement_id);
+                }
+            }
+        }
+
+        let (puzzle_hash, (_puzzle, notarized_payments)) = parsed
+            .requested_payments
+            .first()
+            .expect("missing requested payment");
+        assert_eq!(notarized_payments.len(), 1);
+
+        let announcement_id = settlement_announcement_id(*puzzle_hash, &notarized_payments[0]);
+        assert_eq!(
+            announcement_id,
+            Bytes32::new(hex!(
+                "1a5f039491e578e7fe5bdfbcfbb8e9b4647958f2dfc5420ea833ad3ffa79856f"
+            ))
+        );
+        assert!(asserted.contains(&announcement_id));
+
+        Ok(())
+    }
+}
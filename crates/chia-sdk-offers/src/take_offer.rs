@@ -0,0 +1,183 @@
+use chia_bls::Signature;
+use chia_protocol::{Bytes32, SpendBundle};
+use chia_puzzles::offer::{NotarizedPayment, SettlementPaymentsSolution};
+use chia_sdk_driver::{Cat, CatSpend, Layer, SettlementLayer, SpendContext};
+use indexmap::IndexMap;
+
+use crate::{
+    offer_summary::{settlement_coins, SettlementCoin},
+    Offer, OfferError,
+};
+
+/// Takes an offer by combining it with the taker's side of the trade, which is structured like an offer itself.
+/// The settlement coins created by each side are spent to pay the notarized payments requested by the other,
+/// which creates the puzzle announcements that both sides assert.
+///
+/// The first settlement coin with a given puzzle hash pays every payment requested for that puzzle hash,
+/// and any other settlement coins with the same puzzle hash are spent without payments.
+pub fn take_offer(maker_offer: Offer, taker_spend: SpendBundle) -> Result<SpendBundle, OfferError> {
+    let mut ctx = SpendContext::new();
+
+    let maker = maker_offer.parse(&mut ctx.allocator)?;
+    let taker = Offer::from(taker_spend).parse(&mut ctx.allocator)?;
+
+    let mut requested_payments: IndexMap<Bytes32, Vec<NotarizedPayment>> = IndexMap::new();
+
+    for (puzzle_hash, (_puzzle, notarized_payments)) in maker
+        .requested_payments
+        .into_iter()
+        .chain(taker.requested_payments)
+    {
+        requested_payments
+            .entry(puzzle_hash)
+            .or_default()
+            .extend(notarized_payments);
+    }
+
+    let mut xch_coins = Vec::new();
+    let mut cats: IndexMap<Bytes32, Vec<Cat>> = IndexMap::new();
+    let mut nfts = Vec::new();
+
+    for coin_spend in maker.coin_spends.iter().chain(&taker.coin_spends) {
+        for settlement_coin in settlement_coins(&mut ctx.allocator, coin_spend)? {
+            match settlement_coin {
+                SettlementCoin::Xch(coin) => xch_coins.push(coin),
+                SettlementCoin::Cat(cat) => cats.entry(cat.asset_id).or_default().push(cat),
+                SettlementCoin::Nft(nft) => nfts.push(nft),
+            }
+        }
+    }
+
+    let mut settlement_solution = |puzzle_hash: Bytes32| SettlementPaymentsSolution {
+        notarized_payments: requested_payments
+            .shift_remove(&puzzle_hash)
+            .unwrap_or_default(),
+    };
+
+    for coin in xch_coins {
+        let spend =
+            SettlementLayer.construct_spend(&mut ctx, settlement_solution(coin.puzzle_hash))?;
+        ctx.spend(coin, spend)?;
+    }
+
+    for cats in cats.into_values() {
+        let mut cat_spends = Vec::new();
+
+        for cat in cats {
+            let inner_spend = SettlementLayer
+                .construct_spend(&mut ctx, settlement_solution(cat.coin.puzzle_hash))?;
+            cat_spends.push(CatSpend::new(cat, inner_spend));
+        }
+
+        Cat::spend_all(&mut ctx, &cat_spends)?;
+    }
+
+    for nft in nfts {
+        let inner_spend =
+            SettlementLayer.construct_spend(&mut ctx, settlement_solution(nft.coin.puzzle_hash))?;
+        nft.spend(&mut ctx, inner_spend)?;
+    }
+
+    if let Some(puzzle_hash) = requested_payments.keys().next() {
+        return Err(OfferError::MissingSettlementCoin(*puzzle_hash));
+    }
+
+    Ok(SpendBundle::aggregate(&[
+        SpendBundle::new(maker.coin_spends, maker.aggregated_signature),
+        SpendBundle::new(taker.coin_spends, taker.aggregated_signature),
+        SpendBundle::new(ctx.take(), Signature::default()),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Coin;
+    use chia_puzzles::offer::{Payment, SETTLEMENT_PAYMENTS_PUZZLE_HASH};
+    use chia_sdk_driver::{CatLayer, SpendWithConditions, StandardLayer};
+    use chia_sdk_test::Simulator;
+    use chia_sdk_types::Conditions;
+
+    use super::*;
+
+    #[test]
+    fn test_take_xch_for_cat_offer() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        let ctx = &mut SpendContext::new();
+        let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+        let (maker_secret_key, maker_public_key, maker_puzzle_hash, maker_coin) =
+            sim.new_p2(1000)?;
+        let maker_p2 = StandardLayer::new(maker_public_key);
+
+        let (taker_secret_key, taker_public_key, taker_puzzle_hash, _coin) = sim.new_p2(0)?;
+        let taker_p2 = StandardLayer::new(taker_public_key);
+
+        let asset_id = Bytes32::new([42; 32]);
+        let (cat_coin, lineage_proof) = sim.new_cat_coin(asset_id, taker_puzzle_hash, 100);
+        let cat = Cat::new(cat_coin, Some(lineage_proof), asset_id, taker_puzzle_hash);
+
+        let settlement_puzzle = ctx.settlement_payments_puzzle()?;
+        let cat_settlement_puzzle =
+            CatLayer::new(asset_id, settlement_puzzle).construct_puzzle(ctx)?;
+
+        // The maker offers 1000 mojos for 100 CAT mojos.
+        let (assertions, builder) = Offer::build(&[maker_coin])
+            .request(
+                ctx,
+                &cat_settlement_puzzle,
+                vec![Payment::new(maker_puzzle_hash, 100)],
+            )?
+            .finish();
+
+        maker_p2.spend(
+            ctx,
+            maker_coin,
+            Conditions::new()
+                .create_coin(settlement_hash, 1000, Vec::new())
+                .extend(assertions),
+        )?;
+
+        let coin_spends = ctx.take();
+        let maker_offer =
+            builder.bundle(ctx, SpendBundle::new(coin_spends, Signature::default()))?;
+
+        // The taker pays 100 CAT mojos for the 1000 mojos.
+        let (assertions, builder) = Offer::build(&[cat.coin])
+            .request(
+                ctx,
+                &settlement_puzzle,
+                vec![Payment::new(taker_puzzle_hash, 1000)],
+            )?
+            .finish();
+
+        let inner_spend = taker_p2.spend_with_conditions(
+            ctx,
+            Conditions::new()
+                .create_coin(settlement_hash, 100, vec![settlement_hash.into()])
+                .extend(assertions),
+        )?;
+        Cat::spend_all(ctx, &[CatSpend::new(cat, inner_spend)])?;
+
+        let coin_spends = ctx.take();
+        let taker_spend: SpendBundle = builder
+            .bundle(ctx, SpendBundle::new(coin_spends, Signature::default()))?
+            .into();
+
+        let spend_bundle = take_offer(maker_offer, taker_spend)?;
+        sim.spend_coins(
+            spend_bundle.coin_spends,
+            &[maker_secret_key, taker_secret_key],
+        )?;
+
+        let settlement_coin = Coin::new(maker_coin.coin_id(), settlement_hash, 1000);
+        let taker_coin = Coin::new(settlement_coin.coin_id(), taker_puzzle_hash, 1000);
+        assert!(sim.coin_state(taker_coin.coin_id()).is_some());
+
+        let maker_cat = cat
+            .wrapped_child(settlement_hash, 100)
+            .wrapped_child(maker_puzzle_hash, 100);
+        assert!(sim.coin_state(maker_cat.coin.coin_id()).is_some());
+
+        Ok(())
+    }
+}
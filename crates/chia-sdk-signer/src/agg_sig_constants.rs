@@ -81,3 +81,20 @@ fn hash(agg_sig_data: Bytes32, byte: u8) -> Bytes32 {
     hasher.update([byte]);
     hasher.finalize().into()
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_sdk_types::{MAINNET_CONSTANTS, TESTNET11_CONSTANTS};
+
+    use super::*;
+
+    #[test]
+    fn test_derived_constants() {
+        for constants in [&*MAINNET_CONSTANTS, &*TESTNET11_CONSTANTS] {
+            assert_eq!(
+                AggSigConstants::new(constants.agg_sig_me_additional_data),
+                AggSigConstants::from(constants)
+            );
+        }
+    }
+}
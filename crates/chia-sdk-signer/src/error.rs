@@ -13,6 +13,9 @@ pub enum SignerError {
     #[error("From CLVM error: {0}")]
     FromClvm(#[from] FromClvmError),
 
+    #[error("Streamable error: {0}")]
+    Streamable(#[from] chia_traits::Error),
+
     #[error("Infinity public key")]
     InfinityPublicKey,
 }
@@ -1,7 +1,11 @@
 mod agg_sig_constants;
 mod error;
 mod required_signature;
+mod spend_bundle;
+mod unsigned_transaction;
 
 pub use agg_sig_constants::*;
 pub use error::*;
 pub use required_signature::*;
+pub use spend_bundle::*;
+pub use unsigned_transaction::*;
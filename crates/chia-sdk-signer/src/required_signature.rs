@@ -1,12 +1,12 @@
-use chia_bls::PublicKey;
+use chia_bls::{verify, PublicKey, Signature};
 use chia_protocol::{Bytes, Bytes32, Coin, CoinSpend};
-use chia_sdk_types::{run_puzzle, AggSig, AggSigKind, Condition};
+use chia_sdk_types::{encode_clvm_int, run_puzzle, AggSig, AggSigKind, Condition};
 use clvm_traits::{FromClvm, ToClvm};
 use clvmr::Allocator;
 
 use crate::{AggSigConstants, SignerError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RequiredSignature {
     public_key: PublicKey,
     raw_message: Bytes,
@@ -15,6 +15,20 @@ pub struct RequiredSignature {
 }
 
 impl RequiredSignature {
+    pub fn new(
+        public_key: PublicKey,
+        raw_message: Bytes,
+        appended_info: Vec<u8>,
+        domain_string: Option<Bytes32>,
+    ) -> Self {
+        Self {
+            public_key,
+            raw_message,
+            appended_info,
+            domain_string,
+        }
+    }
+
     /// Converts a known [`AggSig`] condition to a `RequiredSignature` if possible.
     pub fn from_condition(coin: &Coin, condition: AggSig, constants: &AggSigConstants) -> Self {
         let domain_string;
@@ -33,17 +47,17 @@ impl RequiredSignature {
             }
             AggSigKind::Amount => {
                 domain_string = constants.amount();
-                u64_to_bytes(coin.amount)
+                encode_clvm_int(coin.amount)
             }
             AggSigKind::PuzzleAmount => {
                 domain_string = constants.puzzle_amount();
                 let puzzle = coin.puzzle_hash;
-                [puzzle.to_vec(), u64_to_bytes(coin.amount)].concat()
+                [puzzle.to_vec(), encode_clvm_int(coin.amount)].concat()
             }
             AggSigKind::ParentAmount => {
                 domain_string = constants.parent_amount();
                 let parent = coin.parent_coin_info;
-                [parent.to_vec(), u64_to_bytes(coin.amount)].concat()
+                [parent.to_vec(), encode_clvm_int(coin.amount)].concat()
             }
             AggSigKind::ParentPuzzle => {
                 domain_string = constants.parent_puzzle();
@@ -145,12 +159,11 @@ impl RequiredSignature {
         }
         message
     }
-}
 
-fn u64_to_bytes(value: u64) -> Vec<u8> {
-    let mut allocator = Allocator::new();
-    let atom = allocator.new_number(value.into()).unwrap();
-    allocator.atom(atom).as_ref().to_vec()
+    /// Checks whether the signature is valid for the public key and final message.
+    pub fn verify(&self, signature: &Signature) -> bool {
+        verify(signature, &self.public_key, self.final_message())
+    }
 }
 
 #[cfg(test)]
@@ -160,7 +173,8 @@ mod tests {
     use chia_bls::{master_to_wallet_unhardened, SecretKey};
     use chia_protocol::Bytes32;
     use chia_puzzles::DeriveSynthetic;
-    use chia_sdk_types::MAINNET_CONSTANTS;
+    use chia_sdk_driver::{SpendContext, StandardLayer};
+    use chia_sdk_types::{Conditions, MAINNET_CONSTANTS};
     use hex_literal::hex;
 
     #[test]
@@ -258,4 +272,33 @@ mod tests {
             assert_eq!(hex::encode(message), hex::encode(required.final_message()));
         }
     }
+
+    #[test]
+    fn test_standard_agg_sig_me_message() -> anyhow::Result<()> {
+        let mut ctx = SpendContext::new();
+        let public_key = SecretKey::from_seed(&[1; 32]).public_key();
+        let p2 = StandardLayer::new(public_key);
+        let coin = Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 1000);
+
+        let conditions = Conditions::new().create_coin(Bytes32::new([3; 32]), 900, Vec::new());
+        let message = StandardLayer::agg_sig_me_message(
+            &mut ctx,
+            coin.coin_id(),
+            &conditions,
+            MAINNET_CONSTANTS.agg_sig_me_additional_data,
+        )?;
+
+        p2.spend(&mut ctx, coin, conditions)?;
+        let coin_spends = ctx.take();
+
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+        let required =
+            RequiredSignature::from_coin_spends(&mut ctx.allocator, &coin_spends, &constants)?;
+
+        assert_eq!(required.len(), 1);
+        assert_eq!(required[0].public_key(), public_key);
+        assert_eq!(required[0].final_message(), message);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+
+use chia_bls::{aggregate_verify, Signature};
+use chia_protocol::{Bytes32, SpendBundle};
+use clvmr::Allocator;
+
+use crate::{AggSigConstants, RequiredSignature, SignerError};
+
+/// Combines spend bundles into one, by concatenating their coin spends and adding their signatures.
+///
+/// Coin spends are kept even if they are repeated, since the aggregated signature covers every one of them.
+/// Use [`check_no_duplicate_coins`] on the result to make sure that no coin is spent twice.
+pub fn aggregate_spend_bundles(bundles: Vec<SpendBundle>) -> SpendBundle {
+    let mut coin_spends = Vec::new();
+    let mut aggregated_signature = Signature::default();
+
+    for bundle in bundles {
+        coin_spends.extend(bundle.coin_spends);
+        aggregated_signature += &bundle.aggregated_signature;
+    }
+
+    SpendBundle::new(coin_spends, aggregated_signature)
+}
+
+/// Checks that no coin is spent more than once in the spend bundle, which would always be rejected.
+/// Returns the id of the first coin that is spent twice, even if the puzzle reveal or solution differs.
+pub fn check_no_duplicate_coins(spend_bundle: &SpendBundle) -> Result<(), Bytes32> {
+    let mut seen = HashSet::new();
+
+    for coin_spend in &spend_bundle.coin_spends {
+        let coin_id = coin_spend.coin.coin_id();
+
+        if !seen.insert(coin_id) {
+            return Err(coin_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks whether the aggregated signature of a spend bundle is valid for every signature required by its coin spends.
+pub fn verify_spend_bundle_signature(
+    allocator: &mut Allocator,
+    spend_bundle: &SpendBundle,
+    constants: &AggSigConstants,
+) -> Result<bool, SignerError> {
+    let required_signatures =
+        RequiredSignature::from_coin_spends(allocator, &spend_bundle.coin_spends, constants)?;
+
+    Ok(aggregate_verify(
+        &spend_bundle.aggregated_signature,
+        required_signatures
+            .iter()
+            .map(|required| (required.public_key(), required.final_message())),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::{sign, PublicKey, SecretKey};
+    use chia_protocol::{Bytes32, Coin, CoinSpend, Program};
+    use chia_sdk_types::{AggSigMe, AggSigParent, MAINNET_CONSTANTS};
+    use clvm_traits::{clvm_quote, FromClvm, ToClvm};
+    use clvmr::NodePtr;
+
+    use super::*;
+
+    fn coin_spend(amount: u64) -> CoinSpend {
+        CoinSpend::new(
+            Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), amount),
+            Program::from(vec![1]),
+            Program::from(vec![0x80]),
+        )
+    }
+
+    #[test]
+    fn test_aggregate_spend_bundles() {
+        let sk1 = SecretKey::from_seed(&[1; 32]);
+        let sk2 = SecretKey::from_seed(&[2; 32]);
+
+        let first = SpendBundle::new(vec![coin_spend(1), coin_spend(2)], sign(&sk1, b"first"));
+        let second = SpendBundle::new(vec![coin_spend(2), coin_spend(3)], sign(&sk2, b"second"));
+
+        let aggregated = aggregate_spend_bundles(vec![first, second]);
+
+        assert_eq!(
+            aggregated.coin_spends,
+            [coin_spend(1), coin_spend(2), coin_spend(2), coin_spend(3)]
+        );
+        assert!(aggregate_verify(
+            &aggregated.aggregated_signature,
+            [
+                (&sk1.public_key(), b"first".as_slice()),
+                (&sk2.public_key(), b"second".as_slice()),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_aggregate_no_spend_bundles() {
+        let aggregated = aggregate_spend_bundles(Vec::new());
+        assert!(aggregated.coin_spends.is_empty());
+        assert_eq!(aggregated.aggregated_signature, Signature::default());
+    }
+
+    #[test]
+    fn test_check_no_duplicate_coins() {
+        let spend_bundle = SpendBundle::new(
+            vec![coin_spend(1), coin_spend(2), coin_spend(3)],
+            Signature::default(),
+        );
+        assert_eq!(check_no_duplicate_coins(&spend_bundle), Ok(()));
+
+        let mut duplicate = coin_spend(2);
+        duplicate.solution = Program::from(vec![0xff, 0x80, 0x80]);
+
+        let spend_bundle = SpendBundle::new(
+            vec![coin_spend(1), coin_spend(2), duplicate.clone()],
+            Signature::default(),
+        );
+        assert_eq!(
+            check_no_duplicate_coins(&spend_bundle),
+            Err(duplicate.coin.coin_id())
+        );
+
+        // Aggregation keeps every coin spend, so this is still caught afterwards.
+        let aggregated = aggregate_spend_bundles(vec![spend_bundle]);
+        assert_eq!(
+            check_no_duplicate_coins(&aggregated),
+            Err(duplicate.coin.coin_id())
+        );
+    }
+
+    fn signed_coin_spend(
+        allocator: &mut Allocator,
+        public_key: PublicKey,
+        amount: u64,
+    ) -> anyhow::Result<CoinSpend> {
+        let puzzle = clvm_quote!((
+            AggSigMe::new(public_key, vec![1, 2, 3].into()),
+            (AggSigParent::new(public_key, vec![4, 5, 6].into()), ())
+        ))
+        .to_clvm(allocator)?;
+
+        Ok(CoinSpend::new(
+            Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), amount),
+            Program::from_clvm(allocator, puzzle)?,
+            Program::from_clvm(allocator, NodePtr::NIL)?,
+        ))
+    }
+
+    #[test]
+    fn test_verify_signature() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+        let sk = SecretKey::from_seed(&[42; 32]);
+
+        let coin_spend = signed_coin_spend(&mut allocator, sk.public_key(), 1)?;
+        let required_signatures =
+            RequiredSignature::from_coin_spend(&mut allocator, &coin_spend, &constants)?;
+
+        let mut aggregated_signature = Signature::default();
+
+        for required in &required_signatures {
+            let signature = sign(&sk, required.final_message());
+            assert!(required.verify(&signature));
+            assert!(!required.verify(&sign(&sk, required.raw_message())));
+            aggregated_signature += &signature;
+        }
+
+        let spend_bundle = SpendBundle::new(vec![coin_spend.clone()], aggregated_signature);
+        assert!(verify_spend_bundle_signature(
+            &mut allocator,
+            &spend_bundle,
+            &constants
+        )?);
+
+        let tampered = SpendBundle::new(
+            vec![coin_spend],
+            sign(&sk, required_signatures[0].final_message()),
+        );
+        assert!(!verify_spend_bundle_signature(
+            &mut allocator,
+            &tampered,
+            &constants
+        )?);
+
+        Ok(())
+    }
+
+    fn sign_coin_spend(
+        allocator: &mut Allocator,
+        sk: &SecretKey,
+        amount: u64,
+        constants: &AggSigConstants,
+    ) -> anyhow::Result<SpendBundle> {
+        let coin_spend = signed_coin_spend(allocator, sk.public_key(), amount)?;
+
+        let mut aggregated_signature = Signature::default();
+
+        for required in RequiredSignature::from_coin_spend(allocator, &coin_spend, constants)? {
+            aggregated_signature += &sign(sk, required.final_message());
+        }
+
+        Ok(SpendBundle::new(vec![coin_spend], aggregated_signature))
+    }
+
+    #[test]
+    fn test_aggregate_verify_signature() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+        let sk1 = SecretKey::from_seed(&[1; 32]);
+        let sk2 = SecretKey::from_seed(&[2; 32]);
+
+        let first = sign_coin_spend(&mut allocator, &sk1, 1, &constants)?;
+        let second = sign_coin_spend(&mut allocator, &sk2, 2, &constants)?;
+
+        let aggregated = aggregate_spend_bundles(vec![first.clone(), second]);
+        assert_eq!(check_no_duplicate_coins(&aggregated), Ok(()));
+        assert!(verify_spend_bundle_signature(
+            &mut allocator,
+            &aggregated,
+            &constants
+        )?);
+
+        // The same bundle twice still has a valid signature, but spends the coin twice.
+        let duplicated = aggregate_spend_bundles(vec![first.clone(), first.clone()]);
+        assert!(verify_spend_bundle_signature(
+            &mut allocator,
+            &duplicated,
+            &constants
+        )?);
+        assert_eq!(
+            check_no_duplicate_coins(&duplicated),
+            Err(first.coin_spends[0].coin.coin_id())
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use chia_bls::PublicKey;
+use chia_protocol::{Bytes, Bytes32, CoinSpend};
+use chia_traits::Streamable;
+use clvmr::Allocator;
+
+use crate::{AggSigConstants, RequiredSignature, SignerError};
+
+type SerializedSignature = (PublicKey, Bytes, Bytes, Option<Bytes32>);
+
+/// A list of coin spends along with the signatures they require, which can be passed from a
+/// watch-only wallet to an offline signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    pub coin_spends: Vec<CoinSpend>,
+    pub required_signatures: Vec<RequiredSignature>,
+}
+
+impl UnsignedTransaction {
+    /// Calculates the required signatures for the coin spends.
+    pub fn new(
+        allocator: &mut Allocator,
+        coin_spends: Vec<CoinSpend>,
+        constants: &AggSigConstants,
+    ) -> Result<Self, SignerError> {
+        let required_signatures =
+            RequiredSignature::from_coin_spends(allocator, &coin_spends, constants)?;
+
+        Ok(Self {
+            coin_spends,
+            required_signatures,
+        })
+    }
+
+    /// Recalculates the required signatures from the coin spends, and checks that they match.
+    /// This should be done by the signer, rather than trusting the list it was given.
+    pub fn verify_required_signatures(
+        &self,
+        allocator: &mut Allocator,
+        constants: &AggSigConstants,
+    ) -> Result<bool, SignerError> {
+        let expected =
+            RequiredSignature::from_coin_spends(allocator, &self.coin_spends, constants)?;
+        Ok(expected == self.required_signatures)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SignerError> {
+        let required_signatures: Vec<SerializedSignature> = self
+            .required_signatures
+            .iter()
+            .map(|required| {
+                (
+                    required.public_key(),
+                    required.raw_message().to_vec().into(),
+                    required.appended_info().to_vec().into(),
+                    required.domain_string(),
+                )
+            })
+            .collect();
+
+        Ok((self.coin_spends.clone(), required_signatures).to_bytes()?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SignerError> {
+        let (coin_spends, required_signatures) =
+            <(Vec<CoinSpend>, Vec<SerializedSignature>)>::from_bytes(bytes)?;
+
+        Ok(Self {
+            coin_spends,
+            required_signatures: required_signatures
+                .into_iter()
+                .map(|(public_key, raw_message, appended_info, domain_string)| {
+                    RequiredSignature::new(
+                        public_key,
+                        raw_message,
+                        appended_info.into(),
+                        domain_string,
+                    )
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::SecretKey;
+    use chia_protocol::{Coin, Program};
+    use chia_sdk_types::{AggSigMe, AggSigUnsafe, MAINNET_CONSTANTS};
+    use clvm_traits::{clvm_quote, FromClvm, ToClvm};
+    use clvmr::NodePtr;
+
+    use super::*;
+
+    fn coin_spend(allocator: &mut Allocator, public_key: PublicKey) -> anyhow::Result<CoinSpend> {
+        let puzzle = clvm_quote!((
+            AggSigMe::new(public_key, vec![1, 2, 3].into()),
+            (AggSigUnsafe::new(public_key, vec![4, 5, 6].into()), ())
+        ))
+        .to_clvm(allocator)?;
+        let puzzle_reveal = Program::from_clvm(allocator, puzzle)?;
+        let solution = Program::from_clvm(allocator, NodePtr::NIL)?;
+
+        let coin = Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 1);
+
+        Ok(CoinSpend::new(coin, puzzle_reveal, solution))
+    }
+
+    #[test]
+    fn test_unsigned_transaction_roundtrip() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+        let public_key = SecretKey::from_seed(&[42; 32]).public_key();
+
+        let coin_spends = vec![coin_spend(&mut allocator, public_key)?];
+        let transaction = UnsignedTransaction::new(&mut allocator, coin_spends, &constants)?;
+        assert_eq!(transaction.required_signatures.len(), 2);
+
+        let bytes = transaction.to_bytes()?;
+        let roundtrip = UnsignedTransaction::from_bytes(&bytes)?;
+
+        assert_eq!(roundtrip, transaction);
+        assert!(roundtrip.verify_required_signatures(&mut allocator, &constants)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tampered_required_signatures() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let constants = AggSigConstants::from(&*MAINNET_CONSTANTS);
+        let public_key = SecretKey::from_seed(&[42; 32]).public_key();
+
+        let coin_spends = vec![coin_spend(&mut allocator, public_key)?];
+        let mut transaction = UnsignedTransaction::new(&mut allocator, coin_spends, &constants)?;
+        transaction.required_signatures.pop();
+
+        assert!(!transaction.verify_required_signatures(&mut allocator, &constants)?);
+
+        Ok(())
+    }
+}
@@ -147,11 +147,16 @@ impl Drop for PeerSimulator {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use chia_bls::{DerivableKey, PublicKey, Signature};
     use chia_protocol::{
         Bytes, CoinSpend, CoinStateFilters, CoinStateUpdate, RespondCoinState, RespondPuzzleState,
         SpendBundle,
     };
+    use chia_sdk_client::{
+        wait_for_confirmation, ClientError, SubscriptionManager, MEMPOOL_RESPONSE_TIMEOUT,
+    };
     use chia_sdk_types::{AggSigMe, CreateCoin, Remark};
 
     use crate::{coin_state_updates, test_secret_key, test_transaction, to_program, to_puzzle};
@@ -882,4 +887,189 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let peer = sim.connect().await?;
+        let genesis_challenge = sim.config().constants.genesis_challenge;
+
+        let (puzzle_hash, puzzle_reveal) = to_puzzle(1)?;
+
+        let coin = sim.mint_coin(puzzle_hash, 0).await;
+        let unspent = sim.mint_coin(puzzle_hash, 1).await;
+
+        let spend_bundle = SpendBundle::new(
+            vec![CoinSpend::new(coin, puzzle_reveal.clone(), to_program(())?)],
+            Signature::default(),
+        );
+        let transaction_id = spend_bundle.name();
+
+        let ack = peer.send_transaction(spend_bundle).await?;
+        assert_eq!(ack.status, 1);
+
+        let height = wait_for_confirmation(
+            &peer,
+            transaction_id,
+            coin.coin_id(),
+            genesis_challenge,
+            Duration::from_secs(5),
+        )
+        .await?;
+        assert_eq!(height, 0);
+
+        // The transaction was never sent, so it can't be found in the mempool.
+        let unsent_id = SpendBundle::new(
+            vec![CoinSpend::new(unspent, puzzle_reveal, to_program(())?)],
+            Signature::default(),
+        )
+        .name();
+
+        let pending = wait_for_confirmation(
+            &peer,
+            unsent_id,
+            unspent.coin_id(),
+            genesis_challenge,
+            Duration::from_millis(50),
+        )
+        .await;
+        assert!(matches!(pending, Err(ClientError::ConfirmationTimeout(..))));
+
+        let evicted = wait_for_confirmation(
+            &peer,
+            unsent_id,
+            unspent.coin_id(),
+            genesis_challenge,
+            MEMPOOL_RESPONSE_TIMEOUT * 2,
+        )
+        .await;
+        assert!(matches!(evicted, Err(ClientError::TransactionDropped(..))));
+
+        let dropped = wait_for_confirmation(
+            &peer,
+            unsent_id,
+            Bytes32::new([42; 32]),
+            genesis_challenge,
+            Duration::from_millis(50),
+        )
+        .await;
+        assert!(matches!(dropped, Err(ClientError::TransactionDropped(..))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_coin_records() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let peer = sim.connect().await?;
+        let genesis_challenge = sim.config().constants.genesis_challenge;
+
+        let (puzzle_hash, puzzle_reveal) = to_puzzle(1)?;
+
+        let coin = sim.mint_coin(puzzle_hash, 2).await;
+        let unspent = sim.mint_coin(puzzle_hash, 3).await;
+
+        let spend_bundle = SpendBundle::new(
+            vec![CoinSpend::new(
+                coin,
+                puzzle_reveal,
+                to_program([CreateCoin::new(puzzle_hash, 1, Vec::new())])?,
+            )],
+            Signature::default(),
+        );
+
+        let ack = peer.send_transaction(spend_bundle).await?;
+        assert_eq!(ack.status, 1);
+
+        let spent_record = peer
+            .get_coin_record_by_name(coin.coin_id(), genesis_challenge)
+            .await?
+            .expect("missing coin record");
+        assert_eq!(spent_record.spent_height, Some(0));
+
+        let unspent_record = peer
+            .get_coin_record_by_name(unspent.coin_id(), genesis_challenge)
+            .await?
+            .expect("missing coin record");
+        assert_eq!(unspent_record.spent_height, None);
+
+        assert_eq!(
+            peer.get_coin_record_by_name(Bytes32::new([42; 32]), genesis_challenge)
+                .await?,
+            None
+        );
+
+        let child = Coin::new(coin.coin_id(), puzzle_hash, 1);
+
+        let unspent_records = peer
+            .get_coin_records_by_puzzle_hash(puzzle_hash, false, genesis_challenge)
+            .await?;
+        let mut unspent_coins: Vec<Coin> = unspent_records
+            .into_iter()
+            .map(|coin_state| coin_state.coin)
+            .collect();
+        unspent_coins.sort_by_key(|coin| coin.amount);
+        assert_eq!(unspent_coins, [child, unspent]);
+
+        let all_records = peer
+            .get_coin_records_by_puzzle_hash(puzzle_hash, true, genesis_challenge)
+            .await?;
+        assert_eq!(all_records.len(), 3);
+
+        let children = peer
+            .get_coin_records_by_parent_ids(vec![coin.coin_id(), unspent.coin_id()])
+            .await?;
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].coin, child);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscription_manager_reconnect() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let (peer, receiver) = sim.connect_split().await?;
+        let (mut manager, mut updates) = SubscriptionManager::new(peer, receiver);
+
+        let (puzzle_hash, puzzle_reveal) = to_puzzle(1)?;
+
+        let coin = sim.mint_coin(puzzle_hash, 0).await;
+        let coin_states = manager
+            .subscribe_puzzle_hashes(vec![puzzle_hash], 0)
+            .await?;
+        assert_eq!(coin_states.len(), 1);
+
+        let spend_bundle = SpendBundle::new(
+            vec![CoinSpend::new(coin, puzzle_reveal.clone(), to_program(())?)],
+            Signature::default(),
+        );
+        manager.peer().send_transaction(spend_bundle).await?;
+
+        let update = tokio::time::timeout(Duration::from_secs(5), updates.recv())
+            .await?
+            .expect("missing update");
+        assert_eq!(update.items.len(), 1);
+        assert_eq!(update.items[0].coin, coin);
+        assert_eq!(update.items[0].spent_height, Some(0));
+
+        let (peer, receiver) = sim.connect_split().await?;
+        let coin_states = manager.reconnect(peer, receiver, 0).await?;
+        assert_eq!(coin_states.len(), 1);
+        assert_eq!(coin_states[0].coin, coin);
+
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+        let spend_bundle = SpendBundle::new(
+            vec![CoinSpend::new(coin, puzzle_reveal, to_program(())?)],
+            Signature::default(),
+        );
+        manager.peer().send_transaction(spend_bundle).await?;
+
+        let update = tokio::time::timeout(Duration::from_secs(5), updates.recv())
+            .await?
+            .expect("missing update");
+        assert_eq!(update.items.len(), 1);
+        assert_eq!(update.items[0].coin, coin);
+
+        Ok(())
+    }
 }
@@ -135,6 +135,11 @@ async fn handle_message(
             let response = request_puzzle_state(addr, request, config, &simulator, subscriptions)?;
             (ProtocolMessageTypes::RespondPuzzleState, response)
         }
+        ProtocolMessageTypes::RequestTransaction => {
+            // Transactions are included in a block as soon as they are sent, so the mempool is
+            // always empty. Full nodes don't respond to requests for transactions they don't have.
+            return Ok(());
+        }
         message_type => {
             return Err(PeerSimulatorError::UnsupportedMessage(message_type));
         }
@@ -2,11 +2,12 @@ use std::collections::HashSet;
 
 use chia_bls::{PublicKey, SecretKey};
 use chia_consensus::{
-    consensus_constants::ConsensusConstants, gen::validation_error::ErrorCode,
+    consensus_constants::ConsensusConstants,
+    gen::{owned_conditions::OwnedSpendBundleConditions, validation_error::ErrorCode},
     spendbundle_validation::validate_clvm_and_signature,
 };
 use chia_protocol::{Bytes32, Coin, CoinSpend, CoinState, Program, SpendBundle};
-use chia_puzzles::standard::StandardArgs;
+use chia_puzzles::{cat::CatArgs, standard::StandardArgs, LineageProof};
 use chia_sdk_types::TESTNET11_CONSTANTS;
 use fastrand::Rng;
 use indexmap::{IndexMap, IndexSet};
@@ -18,6 +19,7 @@ pub struct Simulator {
     rng: Rng,
     height: u32,
     header_hashes: Vec<Bytes32>,
+    timestamps: Vec<u64>,
     coin_states: IndexMap<Bytes32, CoinState>,
     hinted_coins: IndexMap<Bytes32, IndexSet<Bytes32>>,
     puzzle_and_solutions: IndexMap<Bytes32, (Program, Program)>,
@@ -43,6 +45,7 @@ impl Simulator {
             rng,
             height: 0,
             header_hashes: vec![header_hash.into()],
+            timestamps: vec![0],
             coin_states: IndexMap::new(),
             hinted_coins: IndexMap::new(),
             puzzle_and_solutions: IndexMap::new(),
@@ -57,6 +60,38 @@ impl Simulator {
         self.header_hashes.last().copied().unwrap()
     }
 
+    /// The timestamp of the current block, in seconds.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamps.last().copied().unwrap()
+    }
+
+    /// Creates a new block the given number of seconds after the current one.
+    /// This allows spends with time based conditions to become valid or expire.
+    pub fn pass_time(&mut self, seconds: u64) {
+        self.create_block_at(self.timestamp() + seconds);
+    }
+
+    /// Creates the given number of empty blocks, with the same timestamp as the current one.
+    /// This allows spends with height based conditions to become valid or expire.
+    pub fn advance_blocks(&mut self, blocks: u32) {
+        for _ in 0..blocks {
+            self.create_block();
+        }
+    }
+
+    /// Creates a new block with the given timestamp, in seconds.
+    ///
+    /// # Panics
+    /// Will panic if the timestamp is earlier than that of the current block.
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        assert!(
+            timestamp >= self.timestamp(),
+            "timestamp {timestamp} is earlier than the current timestamp {}",
+            self.timestamp()
+        );
+        self.create_block_at(timestamp);
+    }
+
     pub fn header_hash_of(&self, height: u32) -> Option<Bytes32> {
         self.header_hashes.get(height as usize).copied()
     }
@@ -85,6 +120,36 @@ impl Simulator {
         Ok((sk, pk, p2, coin))
     }
 
+    /// Creates a CAT coin with the given asset id and inner puzzle hash, hinted by the inner puzzle hash.
+    /// Its parent is a spent CAT coin with the same asset id, so it can be spent with the returned
+    /// [`LineageProof`] without running the TAIL.
+    pub fn new_cat_coin(
+        &mut self,
+        asset_id: Bytes32,
+        inner_puzzle_hash: Bytes32,
+        amount: u64,
+    ) -> (Coin, LineageProof) {
+        let puzzle_hash = CatArgs::curry_tree_hash(asset_id, inner_puzzle_hash.into()).into();
+
+        let parent = self.new_coin(puzzle_hash, amount);
+        self.coin_states.insert(
+            parent.coin_id(),
+            CoinState::new(parent, Some(self.height), Some(self.height)),
+        );
+
+        let coin = Coin::new(parent.coin_id(), puzzle_hash, amount);
+        self.insert_coin(coin);
+        self.hint_coin(coin.coin_id(), inner_puzzle_hash);
+
+        let lineage_proof = LineageProof {
+            parent_parent_coin_info: parent.parent_coin_info,
+            parent_inner_puzzle_hash: inner_puzzle_hash,
+            parent_amount: amount,
+        };
+
+        (coin, lineage_proof)
+    }
+
     pub(crate) fn hint_coin(&mut self, coin_id: Bytes32, hint: Bytes32) {
         self.hinted_coins.entry(hint).or_default().insert(coin_id);
     }
@@ -150,6 +215,8 @@ impl Simulator {
             validate_clvm_and_signature(&spend_bundle, 7_700_000_000, constants, self.height)
                 .map_err(SimulatorError::Validation)?;
 
+        self.validate_timelocks(&conds)?;
+
         let puzzle_hashes: HashSet<Bytes32> =
             conds.spends.iter().map(|spend| spend.puzzle_hash).collect();
 
@@ -269,10 +336,245 @@ impl Simulator {
         coin_states.into_values().collect()
     }
 
+    fn validate_timelocks(&self, conds: &OwnedSpendBundleConditions) -> Result<(), SimulatorError> {
+        let height = self.height;
+        let timestamp = self.timestamp();
+
+        if conds.height_absolute > height {
+            return Err(SimulatorError::Validation(
+                ErrorCode::AssertHeightAbsoluteFailed,
+            ));
+        }
+
+        if conds
+            .before_height_absolute
+            .is_some_and(|before| height >= before)
+        {
+            return Err(SimulatorError::Validation(
+                ErrorCode::AssertBeforeHeightAbsoluteFailed,
+            ));
+        }
+
+        if conds.seconds_absolute > timestamp {
+            return Err(SimulatorError::Validation(
+                ErrorCode::AssertSecondsAbsoluteFailed,
+            ));
+        }
+
+        if conds
+            .before_seconds_absolute
+            .is_some_and(|before| timestamp >= before)
+        {
+            return Err(SimulatorError::Validation(
+                ErrorCode::AssertBeforeSecondsAbsoluteFailed,
+            ));
+        }
+
+        for spend in &conds.spends {
+            // Coins created in this transaction are born at the current height and timestamp.
+            let birth_height = self
+                .coin_states
+                .get(&spend.coin_id)
+                .and_then(|cs| cs.created_height)
+                .unwrap_or(height);
+            let birth_timestamp = self.timestamps[birth_height as usize];
+
+            if spend.height_relative.is_some_and(|blocks| {
+                birth_height
+                    .checked_add(blocks)
+                    .map_or(true, |valid_at| valid_at > height)
+            }) {
+                return Err(SimulatorError::Validation(
+                    ErrorCode::AssertHeightRelativeFailed,
+                ));
+            }
+
+            if spend.before_height_relative.is_some_and(|blocks| {
+                birth_height
+                    .checked_add(blocks)
+                    .is_some_and(|expires_at| height >= expires_at)
+            }) {
+                return Err(SimulatorError::Validation(
+                    ErrorCode::AssertBeforeHeightRelativeFailed,
+                ));
+            }
+
+            if spend.seconds_relative.is_some_and(|seconds| {
+                birth_timestamp
+                    .checked_add(seconds)
+                    .map_or(true, |valid_at| valid_at > timestamp)
+            }) {
+                return Err(SimulatorError::Validation(
+                    ErrorCode::AssertSecondsRelativeFailed,
+                ));
+            }
+
+            if spend.before_seconds_relative.is_some_and(|seconds| {
+                birth_timestamp
+                    .checked_add(seconds)
+                    .is_some_and(|expires_at| timestamp >= expires_at)
+            }) {
+                return Err(SimulatorError::Validation(
+                    ErrorCode::AssertBeforeSecondsRelativeFailed,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     fn create_block(&mut self) {
+        self.create_block_at(self.timestamp());
+    }
+
+    fn create_block_at(&mut self, timestamp: u64) {
         let mut header_hash = [0; 32];
         self.rng.fill(&mut header_hash);
         self.header_hashes.push(header_hash.into());
+        self.timestamps.push(timestamp);
         self.height += 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_sdk_types::Conditions;
+
+    use crate::{to_program, to_puzzle};
+
+    use super::*;
+
+    fn timelocked_spend(sim: &mut Simulator, conditions: Conditions) -> anyhow::Result<CoinSpend> {
+        let (puzzle_hash, puzzle_reveal) = to_puzzle(1)?;
+        let coin = sim.new_coin(puzzle_hash, 0);
+        Ok(CoinSpend::new(coin, puzzle_reveal, to_program(conditions)?))
+    }
+
+    #[test]
+    fn test_new_cat_coin() {
+        let mut sim = Simulator::new();
+        let asset_id = Bytes32::new([1; 32]);
+        let inner_puzzle_hash = Bytes32::new([2; 32]);
+
+        let (coin, lineage_proof) = sim.new_cat_coin(asset_id, inner_puzzle_hash, 100);
+        let puzzle_hash: Bytes32 =
+            CatArgs::curry_tree_hash(asset_id, inner_puzzle_hash.into()).into();
+        assert_eq!(coin.puzzle_hash, puzzle_hash);
+        assert_eq!(sim.hinted_coins(inner_puzzle_hash), [coin.coin_id()]);
+
+        let parent = Coin::new(
+            lineage_proof.parent_parent_coin_info,
+            puzzle_hash,
+            lineage_proof.parent_amount,
+        );
+        assert_eq!(parent.coin_id(), coin.parent_coin_info);
+
+        let parent_state = sim.coin_state(parent.coin_id()).expect("missing parent");
+        assert!(parent_state.spent_height.is_some());
+        assert!(sim.coin_state(coin.coin_id()).is_some());
+    }
+
+    #[test]
+    fn test_advance_blocks() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+
+        let relative = timelocked_spend(&mut sim, Conditions::new().assert_height_relative(5))?;
+        let absolute = timelocked_spend(&mut sim, Conditions::new().assert_height_absolute(10))?;
+
+        sim.advance_blocks(4);
+
+        assert!(matches!(
+            sim.spend_coins(vec![relative.clone()], &[]),
+            Err(SimulatorError::Validation(
+                ErrorCode::AssertHeightRelativeFailed
+            ))
+        ));
+
+        sim.advance_blocks(1);
+        sim.spend_coins(vec![relative], &[])?;
+        assert_eq!(sim.height(), 6);
+
+        assert!(matches!(
+            sim.spend_coins(vec![absolute.clone()], &[]),
+            Err(SimulatorError::Validation(
+                ErrorCode::AssertHeightAbsoluteFailed
+            ))
+        ));
+
+        sim.advance_blocks(4);
+        sim.spend_coins(vec![absolute], &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_timestamp() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+
+        let coin_spend =
+            timelocked_spend(&mut sim, Conditions::new().assert_seconds_absolute(1000))?;
+
+        sim.set_timestamp(999);
+
+        assert!(matches!(
+            sim.spend_coins(vec![coin_spend.clone()], &[]),
+            Err(SimulatorError::Validation(
+                ErrorCode::AssertSecondsAbsoluteFailed
+            ))
+        ));
+
+        sim.set_timestamp(1000);
+        assert_eq!(sim.timestamp(), 1000);
+        sim.spend_coins(vec![coin_spend], &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_height_relative_overflow() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        sim.advance_blocks(5);
+
+        let never_valid =
+            timelocked_spend(&mut sim, Conditions::new().assert_height_relative(u32::MAX))?;
+        let never_expires = timelocked_spend(
+            &mut sim,
+            Conditions::new().assert_before_height_relative(u32::MAX),
+        )?;
+
+        assert!(matches!(
+            sim.spend_coins(vec![never_valid], &[]),
+            Err(SimulatorError::Validation(
+                ErrorCode::AssertHeightRelativeFailed
+            ))
+        ));
+        sim.spend_coins(vec![never_expires], &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seconds_relative_overflow() -> anyhow::Result<()> {
+        let mut sim = Simulator::new();
+        sim.set_timestamp(1000);
+
+        let never_valid = timelocked_spend(
+            &mut sim,
+            Conditions::new().assert_seconds_relative(u64::MAX),
+        )?;
+        let never_expires = timelocked_spend(
+            &mut sim,
+            Conditions::new().assert_before_seconds_relative(u64::MAX),
+        )?;
+
+        assert!(matches!(
+            sim.spend_coins(vec![never_valid], &[]),
+            Err(SimulatorError::Validation(
+                ErrorCode::AssertSecondsRelativeFailed
+            ))
+        ));
+        sim.spend_coins(vec![never_expires], &[])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use clvmr::number::Number;
+
+/// Encodes an unsigned integer the same way as a CLVM atom, which is how amounts are hashed into coin ids
+/// and appended to `AGG_SIG_*` messages.
+///
+/// The encoding is big-endian two's complement with no redundant leading bytes, so zero is empty and
+/// a zero byte is only prepended when the high bit would otherwise make the value negative.
+pub fn encode_clvm_int(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len());
+
+    let mut encoded = Vec::with_capacity(bytes.len() - start + 1);
+    if bytes.get(start).is_some_and(|byte| byte & 0x80 != 0) {
+        encoded.push(0);
+    }
+    encoded.extend_from_slice(&bytes[start..]);
+    encoded
+}
+
+/// Encodes a signed integer of any size the same way as a CLVM atom.
+/// Negative values keep their leading `0xff` sign byte only when it's needed.
+pub fn encode_clvm_bigint(value: &Number) -> Vec<u8> {
+    let bytes = value.to_signed_bytes_be();
+    if bytes == [0] {
+        Vec::new()
+    } else {
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clvmr::Allocator;
+
+    use super::*;
+
+    fn allocator_encoding(value: Number) -> Vec<u8> {
+        let mut allocator = Allocator::new();
+        let atom = allocator.new_number(value).unwrap();
+        allocator.atom(atom).as_ref().to_vec()
+    }
+
+    #[test]
+    fn test_encode_clvm_int() {
+        assert_eq!(encode_clvm_int(0), Vec::<u8>::new());
+        assert_eq!(encode_clvm_int(127), [0x7f]);
+        assert_eq!(encode_clvm_int(128), [0x00, 0x80]);
+        assert_eq!(encode_clvm_int(255), [0x00, 0xff]);
+        assert_eq!(encode_clvm_int(256), [0x01, 0x00]);
+        assert_eq!(
+            encode_clvm_int(u64::MAX),
+            [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+        );
+
+        for value in [0, 127, 128, 255, 256, 0x8000, 1_000_000_000_000, u64::MAX] {
+            assert_eq!(encode_clvm_int(value), allocator_encoding(value.into()));
+            assert_eq!(
+                encode_clvm_bigint(&Number::from(value)),
+                encode_clvm_int(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_clvm_bigint() {
+        assert_eq!(encode_clvm_bigint(&Number::from(-1)), [0xff]);
+        assert_eq!(encode_clvm_bigint(&Number::from(-128)), [0x80]);
+        assert_eq!(encode_clvm_bigint(&Number::from(-129)), [0xff, 0x7f]);
+
+        let large = Number::from(u64::MAX) * Number::from(u64::MAX);
+        assert_eq!(
+            encode_clvm_bigint(&large),
+            allocator_encoding(large.clone())
+        );
+        assert_eq!(
+            encode_clvm_bigint(&-large.clone()),
+            allocator_encoding(-large)
+        );
+    }
+}
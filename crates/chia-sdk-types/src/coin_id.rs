@@ -0,0 +1,70 @@
+use chia_protocol::Bytes32;
+use clvmr::sha2::Sha256;
+
+use crate::encode_clvm_int;
+
+/// Calculates the id of a coin from its parent coin id, puzzle hash, and amount,
+/// which is the same as [`Coin::coin_id`](chia_protocol::Coin::coin_id).
+///
+/// The amount is hashed with the minimal CLVM integer encoding from [`encode_clvm_int`].
+pub fn coin_id(parent_coin_info: Bytes32, puzzle_hash: Bytes32, amount: u64) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update(parent_coin_info);
+    hasher.update(puzzle_hash);
+    hasher.update(encode_clvm_int(amount));
+    Bytes32::new(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Coin;
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_coin_id_vectors() {
+        let parent_coin_info = Bytes32::new([1; 32]);
+        let puzzle_hash = Bytes32::new([2; 32]);
+
+        assert_eq!(
+            coin_id(parent_coin_info, puzzle_hash, 0),
+            Bytes32::new(hex!(
+                "f818afd37a6dc3bc92fb44731011277006db4efa6e9023cd7468c02335d22a4d"
+            ))
+        );
+        assert_eq!(
+            coin_id(parent_coin_info, puzzle_hash, u64::MAX),
+            Bytes32::new(hex!(
+                "7a642bfb87dd7820f538e9a8c105a0e33be4d13bfdf9ef87ed95887e4f25e811"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_coin_id_matches_coin() {
+        let parent_coin_info = Bytes32::new([3; 32]);
+        let puzzle_hash = Bytes32::new([4; 32]);
+
+        for amount in [
+            0,
+            1,
+            0x7f,
+            0x80,
+            0xff,
+            0x100,
+            0x7fff,
+            0x8000,
+            1_000_000_000_000,
+            0x7fff_ffff_ffff_ffff,
+            0x8000_0000_0000_0000,
+            u64::MAX,
+        ] {
+            assert_eq!(
+                coin_id(parent_coin_info, puzzle_hash, amount),
+                Coin::new(parent_coin_info, puzzle_hash, amount).coin_id(),
+                "amount {amount}"
+            );
+        }
+    }
+}
@@ -4,8 +4,12 @@ use chia_sdk_derive::conditions;
 use clvm_traits::{FromClvm, ToClvm};
 
 mod agg_sig;
+mod announcement;
+mod create_coin;
+mod softfork;
 
 pub use agg_sig::*;
+pub use announcement::*;
 
 conditions! {
     pub enum Condition<T> {
@@ -0,0 +1,91 @@
+use chia_protocol::{Bytes, Bytes32};
+use clvmr::sha2::Sha256;
+
+use super::{
+    AssertCoinAnnouncement, AssertPuzzleAnnouncement, CreateCoinAnnouncement,
+    CreatePuzzleAnnouncement,
+};
+
+pub fn announcement_id(coin_info: Bytes32, message: impl AsRef<[u8]>) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update(coin_info.as_ref());
+    hasher.update(message.as_ref());
+    Bytes32::from(hasher.finalize())
+}
+
+/// The id asserted by [`AssertCoinAnnouncement`] for a message announced by the given coin.
+pub fn coin_announcement_id(coin_id: Bytes32, message: impl AsRef<[u8]>) -> Bytes32 {
+    announcement_id(coin_id, message)
+}
+
+/// The id asserted by [`AssertPuzzleAnnouncement`] for a message announced by a coin with the given puzzle hash.
+pub fn puzzle_announcement_id(puzzle_hash: Bytes32, message: impl AsRef<[u8]>) -> Bytes32 {
+    announcement_id(puzzle_hash, message)
+}
+
+/// Creates a coin announcement, along with the assertion that another coin spend can use to
+/// require it. The announcement must be output by the coin with the given id.
+pub fn coin_announcement_pair(
+    coin_id: Bytes32,
+    message: Bytes,
+) -> (CreateCoinAnnouncement, AssertCoinAnnouncement) {
+    let assertion = AssertCoinAnnouncement::new(coin_announcement_id(coin_id, &message));
+    (CreateCoinAnnouncement::new(message), assertion)
+}
+
+/// Creates a puzzle announcement, along with the assertion that another coin spend can use to
+/// require it. The announcement must be output by a coin with the given puzzle hash.
+pub fn puzzle_announcement_pair(
+    puzzle_hash: Bytes32,
+    message: Bytes,
+) -> (CreatePuzzleAnnouncement, AssertPuzzleAnnouncement) {
+    let assertion = AssertPuzzleAnnouncement::new(puzzle_announcement_id(puzzle_hash, &message));
+    (CreatePuzzleAnnouncement::new(message), assertion)
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_announcement_ids() {
+        assert_eq!(
+            coin_announcement_id(Bytes32::new([1; 32]), b"hello"),
+            Bytes32::new(hex!(
+                "2e997ef63bcfa3477ae8a0625220e717c407ba0fdfd0074cc79afdfd44073a76"
+            ))
+        );
+        assert_eq!(
+            puzzle_announcement_id(Bytes32::new([2; 32]), b""),
+            Bytes32::new(hex!(
+                "75877bb41d393b5fb8455ce60ecd8dda001d06316496b14dfa7f895656eeca4a"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_announcement_pairs() {
+        let coin_id = Bytes32::new([2; 32]);
+        let (create, assert) = coin_announcement_pair(coin_id, Bytes::from(b"$".to_vec()));
+
+        assert_eq!(create.message, Bytes::from(b"$".to_vec()));
+        assert_eq!(
+            assert.announcement_id,
+            Bytes32::new(hex!(
+                "e0e08f04b24da00c0b481ac265c062411608cdc627a76f4986e13ad347dffa8f"
+            ))
+        );
+
+        let puzzle_hash = Bytes32::new([1; 32]);
+        let (create, assert) =
+            puzzle_announcement_pair(puzzle_hash, Bytes::from(b"hello".to_vec()));
+
+        assert_eq!(create.message, Bytes::from(b"hello".to_vec()));
+        assert_eq!(
+            assert.announcement_id,
+            puzzle_announcement_id(puzzle_hash, b"hello")
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use chia_protocol::Bytes32;
+
+use super::CreateCoin;
+
+impl CreateCoin {
+    /// Creates a coin with a hint as its only memo, so that it can be looked up by the hint later.
+    /// For CATs and NFTs, the hint is typically the inner puzzle hash of the owner.
+    pub fn with_hint(puzzle_hash: Bytes32, amount: u64, hint: Bytes32) -> Self {
+        Self::new(puzzle_hash, amount, vec![hint.into()])
+    }
+
+    /// The first memo, if it's exactly 32 bytes long and can therefore be used as a hint.
+    pub fn hint(&self) -> Option<Bytes32> {
+        let memo = self.memos.first()?;
+        memo.as_ref().try_into().ok().map(Bytes32::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn test_create_coin_hint() {
+        let puzzle_hash = Bytes32::new([1; 32]);
+        let hint = Bytes32::new([2; 32]);
+
+        let create_coin = CreateCoin::with_hint(puzzle_hash, 1000, hint);
+        assert_eq!(create_coin.memos, [Bytes::from(hint)]);
+        assert_eq!(create_coin.hint(), Some(hint));
+
+        let create_coin =
+            CreateCoin::new(puzzle_hash, 1000, vec![hint.into(), Bytes::new(vec![3])]);
+        assert_eq!(create_coin.hint(), Some(hint));
+    }
+
+    #[test]
+    fn test_create_coin_without_hint() {
+        let puzzle_hash = Bytes32::new([1; 32]);
+
+        assert_eq!(CreateCoin::new(puzzle_hash, 1000, Vec::new()).hint(), None);
+
+        let create_coin = CreateCoin::new(puzzle_hash, 1000, vec![Bytes::new(vec![1, 2, 3])]);
+        assert_eq!(create_coin.hint(), None);
+    }
+}
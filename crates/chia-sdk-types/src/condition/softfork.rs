@@ -0,0 +1,74 @@
+use clvm_traits::{FromClvm, FromClvmError};
+use clvmr::{Allocator, NodePtr};
+
+use super::Softfork;
+
+impl Softfork<NodePtr> {
+    /// Parses the rest of the condition as the structure expected by a particular softfork.
+    /// Nodes must accept any softfork, so callers should fall back to the raw rest if this fails.
+    pub fn parse_rest<R>(&self, allocator: &Allocator) -> Result<R, FromClvmError>
+    where
+        R: FromClvm<Allocator>,
+    {
+        R::from_clvm(allocator, self.rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_protocol::Bytes32;
+    use clvm_traits::ToClvm;
+
+    use crate::{Condition, Conditions};
+
+    use super::*;
+
+    #[test]
+    fn test_softfork_payload() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let puzzle_hash = Bytes32::new([1; 32]);
+
+        let condition = (90, (1000, (puzzle_hash, 42))).to_clvm(&mut allocator)?;
+        let Condition::Softfork(softfork) = Condition::from_clvm(&allocator, condition)? else {
+            panic!("expected a softfork condition");
+        };
+
+        assert_eq!(
+            softfork.parse_rest::<(Bytes32, u64)>(&allocator)?,
+            (puzzle_hash, 42)
+        );
+        assert!(softfork.parse_rest::<Vec<u64>>(&allocator).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_softfork_conditions() -> anyhow::Result<()> {
+        let mut allocator = Allocator::new();
+        let puzzle_hash = Bytes32::new([1; 32]);
+
+        let inner = Conditions::new()
+            .create_coin(puzzle_hash, 1, Vec::new())
+            .reserve_fee(5)
+            .to_clvm(&mut allocator)?;
+        let program = [(90, (1000, (inner, ())))].to_clvm(&mut allocator)?;
+
+        let conditions = Vec::<Condition>::from_clvm(&allocator, program)?;
+        let [Condition::Softfork(softfork)] = conditions.as_slice() else {
+            panic!("expected a single softfork condition");
+        };
+        assert_eq!(softfork.cost, 1000);
+
+        let (inner, ()) = softfork.parse_rest::<(Vec<Condition>, ())>(&allocator)?;
+        let [Condition::CreateCoin(create_coin), Condition::ReserveFee(reserve_fee)] =
+            inner.as_slice()
+        else {
+            panic!("expected the wrapped conditions");
+        };
+        assert_eq!(create_coin.puzzle_hash, puzzle_hash);
+        assert_eq!(create_coin.amount, 1);
+        assert_eq!(reserve_fee.amount, 5);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 use clvm_traits::{FromClvm, ToClvm};
-use clvmr::NodePtr;
+use clvmr::{
+    serde::{node_from_bytes, node_to_bytes},
+    Allocator, NodePtr,
+};
 
-use crate::Condition;
+use chia_protocol::{Bytes32, Program};
+
+use crate::{Condition, ConditionsError, ReserveFee};
 
 #[must_use]
 #[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
@@ -45,6 +50,94 @@ impl<T> Conditions<T> {
     }
 }
 
+impl<T> Conditions<T> {
+    /// Replaces every [`ReserveFee`] condition with a single one for the total amount.
+    /// If the total is zero, no [`ReserveFee`] condition is included.
+    pub fn aggregate_reserve_fees(self) -> Self {
+        let mut total: u64 = 0;
+        let mut conditions = Vec::with_capacity(self.conditions.len());
+
+        for condition in self.conditions {
+            if let Condition::ReserveFee(reserve_fee) = condition {
+                total = total.saturating_add(reserve_fee.amount);
+            } else {
+                conditions.push(condition);
+            }
+        }
+
+        if total > 0 {
+            conditions.push(Condition::ReserveFee(ReserveFee::new(total)));
+        }
+
+        Self { conditions }
+    }
+
+    /// Pays a fee out of the change, so that no separate coin is needed to pay it.
+    /// The first [`CreateCoin`](crate::CreateCoin) to the change puzzle hash is reduced by the fee, and a [`ReserveFee`] is added.
+    ///
+    /// If the change is exactly the fee, the change output is removed entirely.
+    pub fn fee_from_change(
+        mut self,
+        change_puzzle_hash: Bytes32,
+        fee: u64,
+    ) -> Result<Self, ConditionsError> {
+        if fee == 0 {
+            return Ok(self);
+        }
+
+        let Some((index, change)) =
+            self.conditions
+                .iter_mut()
+                .enumerate()
+                .find_map(|(index, condition)| match condition {
+                    Condition::CreateCoin(create_coin)
+                        if create_coin.puzzle_hash == change_puzzle_hash =>
+                    {
+                        Some((index, create_coin))
+                    }
+                    _ => None,
+                })
+        else {
+            return Err(ConditionsError::MissingChange(change_puzzle_hash));
+        };
+
+        let remaining =
+            change
+                .amount
+                .checked_sub(fee)
+                .ok_or(ConditionsError::InsufficientChange {
+                    change: change.amount,
+                    fee,
+                })?;
+
+        if remaining > 0 {
+            change.amount = remaining;
+        } else {
+            self.conditions.remove(index);
+        }
+
+        Ok(self.reserve_fee(fee))
+    }
+}
+
+impl Conditions<Program> {
+    /// Serializes the conditions as a CLVM list, using a temporary [`Allocator`].
+    ///
+    /// This is only available for owned payloads, since a [`NodePtr`] would point into another allocator.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ConditionsError> {
+        let mut allocator = Allocator::new();
+        let ptr = self.to_clvm(&mut allocator)?;
+        Ok(node_to_bytes(&allocator, ptr)?)
+    }
+
+    /// Parses a serialized CLVM list of conditions, such as one created by [`Conditions::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConditionsError> {
+        let mut allocator = Allocator::new();
+        let ptr = node_from_bytes(&mut allocator, bytes)?;
+        Ok(Self::from_clvm(&allocator, ptr)?)
+    }
+}
+
 impl<T> AsRef<[Condition<T>]> for Conditions<T> {
     fn as_ref(&self) -> &[Condition<T>] {
         &self.conditions
@@ -59,3 +152,110 @@ impl<T> IntoIterator for Conditions<T> {
         self.conditions.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::PublicKey;
+
+    use super::*;
+    use crate::CreateCoin;
+
+    #[test]
+    fn test_aggregate_reserve_fees() {
+        let conditions = Conditions::new()
+            .reserve_fee(100)
+            .remark(NodePtr::NIL)
+            .reserve_fee(50)
+            .reserve_fee(25)
+            .aggregate_reserve_fees();
+
+        assert_eq!(
+            conditions.as_ref(),
+            [
+                Condition::Remark(crate::Remark::new(NodePtr::NIL)),
+                Condition::ReserveFee(ReserveFee::new(175)),
+            ]
+        );
+
+        let conditions = Conditions::new()
+            .remark(NodePtr::NIL)
+            .aggregate_reserve_fees();
+        assert_eq!(conditions.as_ref().len(), 1);
+    }
+
+    #[test]
+    fn test_fee_from_change() {
+        let payment = Bytes32::new([1; 32]);
+        let change = Bytes32::new([2; 32]);
+
+        let conditions = Conditions::new()
+            .create_coin(payment, 700, Vec::new())
+            .create_coin(change, 300, Vec::new())
+            .fee_from_change(change, 100)
+            .unwrap();
+
+        assert_eq!(
+            conditions.as_ref(),
+            [
+                Condition::CreateCoin(CreateCoin::new(payment, 700, Vec::new())),
+                Condition::CreateCoin(CreateCoin::new(change, 200, Vec::new())),
+                Condition::ReserveFee(ReserveFee::new(100)),
+            ]
+        );
+
+        // Spending all of the change on the fee removes the change output.
+        let conditions = Conditions::new()
+            .create_coin(change, 100, Vec::new())
+            .fee_from_change(change, 100)
+            .unwrap();
+        assert_eq!(
+            conditions.as_ref(),
+            [Condition::ReserveFee(ReserveFee::new(100))]
+        );
+
+        assert!(matches!(
+            Conditions::new()
+                .create_coin(change, 50, Vec::new())
+                .fee_from_change(change, 100),
+            Err(ConditionsError::InsufficientChange {
+                change: 50,
+                fee: 100
+            })
+        ));
+
+        assert!(matches!(
+            Conditions::new()
+                .create_coin(payment, 1000, Vec::new())
+                .fee_from_change(change, 100),
+            Err(ConditionsError::MissingChange(puzzle_hash)) if puzzle_hash == change
+        ));
+    }
+
+    #[test]
+    fn test_conditions_bytes_roundtrip() -> anyhow::Result<()> {
+        let conditions = Conditions::<Program>::default()
+            .create_coin(
+                Bytes32::new([1; 32]),
+                42,
+                vec![Bytes32::new([2; 32]).into()],
+            )
+            .agg_sig_me(PublicKey::default(), vec![1, 2, 3].into())
+            .remark(Program::from(vec![0x83, 0x61, 0x62, 0x63]))
+            .reserve_fee(100);
+
+        let bytes = conditions.to_bytes()?;
+        assert_eq!(Conditions::<Program>::from_bytes(&bytes)?, conditions);
+
+        let empty = Conditions::<Program>::default();
+        let bytes = empty.to_bytes()?;
+        assert_eq!(bytes, [0x80]);
+        assert_eq!(Conditions::<Program>::from_bytes(&bytes)?, empty);
+
+        assert!(matches!(
+            Conditions::<Program>::from_bytes(&[]),
+            Err(ConditionsError::Io(..))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use std::io;
+
+use chia_protocol::Bytes32;
+use clvm_traits::{FromClvmError, ToClvmError};
+use thiserror::Error;
+
+/// Errors that can occur when building [`Conditions`](crate::Conditions) or converting them to or from bytes.
+#[derive(Debug, Error)]
+pub enum ConditionsError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("failed to serialize conditions: {0}")]
+    ToClvm(#[from] ToClvmError),
+
+    #[error("failed to deserialize conditions: {0}")]
+    FromClvm(#[from] FromClvmError),
+
+    #[error("missing change output to puzzle hash {0}")]
+    MissingChange(Bytes32),
+
+    #[error("change of {change} is less than the fee of {fee}")]
+    InsufficientChange { change: u64, fee: u64 },
+}
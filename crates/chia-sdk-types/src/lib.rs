@@ -1,9 +1,17 @@
+mod clvm_int;
+mod coin_id;
 mod condition;
 mod conditions;
+mod conditions_error;
 mod constants;
+mod parsed_conditions;
 mod run_puzzle;
 
+pub use clvm_int::*;
+pub use coin_id::*;
 pub use condition::*;
 pub use conditions::*;
+pub use conditions_error::*;
 pub use constants::*;
+pub use parsed_conditions::*;
 pub use run_puzzle::*;
@@ -0,0 +1,174 @@
+use clvmr::NodePtr;
+
+use crate::{
+    AggSig, AssertCoinAnnouncement, AssertPuzzleAnnouncement, Condition, CreateCoin,
+    CreateCoinAnnouncement, CreatePuzzleAnnouncement,
+};
+
+/// A coin or puzzle announcement, either created or asserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Announcement {
+    CreateCoin(CreateCoinAnnouncement),
+    AssertCoin(AssertCoinAnnouncement),
+    CreatePuzzle(CreatePuzzleAnnouncement),
+    AssertPuzzle(AssertPuzzleAnnouncement),
+}
+
+/// The output conditions of a puzzle, grouped by kind.
+///
+/// Conditions which don't fall into one of the groups, including those with unknown opcodes,
+/// are kept in their original order and can be accessed with [`ParsedConditions::other`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedConditions<T = NodePtr> {
+    create_coins: Vec<CreateCoin>,
+    agg_sigs: Vec<AggSig>,
+    reserve_fee: Option<u64>,
+    announcements: Vec<Announcement>,
+    other: Vec<Condition<T>>,
+}
+
+impl<T> Default for ParsedConditions<T> {
+    fn default() -> Self {
+        Self {
+            create_coins: Vec::new(),
+            agg_sigs: Vec::new(),
+            reserve_fee: None,
+            announcements: Vec::new(),
+            other: Vec::new(),
+        }
+    }
+}
+
+impl<T> ParsedConditions<T> {
+    pub fn create_coins(&self) -> &[CreateCoin] {
+        &self.create_coins
+    }
+
+    pub fn agg_sigs(&self) -> &[AggSig] {
+        &self.agg_sigs
+    }
+
+    /// The total of every [`ReserveFee`](crate::ReserveFee) condition, or `None` if there were none.
+    pub fn reserve_fee(&self) -> Option<u64> {
+        self.reserve_fee
+    }
+
+    pub fn announcements(&self) -> &[Announcement] {
+        &self.announcements
+    }
+
+    pub fn other(&self) -> &[Condition<T>] {
+        &self.other
+    }
+
+    fn push(&mut self, condition: Condition<T>) {
+        match condition {
+            Condition::CreateCoin(create_coin) => self.create_coins.push(create_coin),
+            Condition::ReserveFee(reserve_fee) => {
+                let total = self.reserve_fee.unwrap_or(0);
+                self.reserve_fee = Some(total.saturating_add(reserve_fee.amount));
+            }
+            Condition::CreateCoinAnnouncement(inner) => {
+                self.announcements.push(Announcement::CreateCoin(inner));
+            }
+            Condition::AssertCoinAnnouncement(inner) => {
+                self.announcements.push(Announcement::AssertCoin(inner));
+            }
+            Condition::CreatePuzzleAnnouncement(inner) => {
+                self.announcements.push(Announcement::CreatePuzzle(inner));
+            }
+            Condition::AssertPuzzleAnnouncement(inner) => {
+                self.announcements.push(Announcement::AssertPuzzle(inner));
+            }
+            condition if condition.is_agg_sig() => self.agg_sigs.extend(condition.into_agg_sig()),
+            condition => self.other.push(condition),
+        }
+    }
+}
+
+impl<T> FromIterator<Condition<T>> for ParsedConditions<T> {
+    fn from_iter<I: IntoIterator<Item = Condition<T>>>(iter: I) -> Self {
+        let mut parsed = Self::default();
+
+        for condition in iter {
+            parsed.push(condition);
+        }
+
+        parsed
+    }
+}
+
+impl<T> From<Vec<Condition<T>>> for ParsedConditions<T> {
+    fn from(conditions: Vec<Condition<T>>) -> Self {
+        conditions.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::PublicKey;
+    use chia_protocol::{Bytes, Bytes32};
+
+    use crate::{AggSigKind, AggSigMe, AssertHeightAbsolute, Remark, ReserveFee};
+
+    use super::*;
+
+    #[test]
+    fn test_mixed_conditions() {
+        let puzzle_hash = Bytes32::new([1; 32]);
+        let public_key = PublicKey::default();
+        let unknown = NodePtr::NIL;
+
+        let parsed = ParsedConditions::from(vec![
+            Condition::CreateCoin(CreateCoin::new(puzzle_hash, 1000, Vec::new())),
+            Condition::ReserveFee(ReserveFee::new(25)),
+            Condition::AggSigMe(AggSigMe::new(public_key, Bytes::new(vec![1, 2, 3]))),
+            Condition::CreateCoinAnnouncement(CreateCoinAnnouncement::new(Bytes::default())),
+            Condition::AssertHeightAbsolute(AssertHeightAbsolute::new(100)),
+            Condition::CreateCoin(CreateCoin::new(puzzle_hash, 500, Vec::new())),
+            Condition::AssertPuzzleAnnouncement(AssertPuzzleAnnouncement::new(puzzle_hash)),
+            Condition::ReserveFee(ReserveFee::new(75)),
+            Condition::Other(unknown),
+            Condition::Remark(Remark::new(unknown)),
+        ]);
+
+        assert_eq!(
+            parsed.create_coins(),
+            [
+                CreateCoin::new(puzzle_hash, 1000, Vec::new()),
+                CreateCoin::new(puzzle_hash, 500, Vec::new())
+            ]
+        );
+        assert_eq!(
+            parsed.agg_sigs(),
+            [AggSig::new(
+                AggSigKind::Me,
+                public_key,
+                Bytes::new(vec![1, 2, 3])
+            )]
+        );
+        assert_eq!(parsed.reserve_fee(), Some(100));
+        assert_eq!(
+            parsed.announcements(),
+            [
+                Announcement::CreateCoin(CreateCoinAnnouncement::new(Bytes::default())),
+                Announcement::AssertPuzzle(AssertPuzzleAnnouncement::new(puzzle_hash)),
+            ]
+        );
+        assert_eq!(
+            parsed.other(),
+            [
+                Condition::AssertHeightAbsolute(AssertHeightAbsolute::new(100)),
+                Condition::Other(unknown),
+                Condition::Remark(Remark::new(unknown)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_reserve_fee() {
+        let parsed: ParsedConditions = Vec::new().into();
+        assert_eq!(parsed.reserve_fee(), None);
+        assert!(parsed.other().is_empty());
+    }
+}
@@ -6,6 +6,7 @@ import {
   curryTreeHash,
   fromHex,
   Simulator,
+  sumAmounts,
   toCoinId,
   toHex,
 } from "../index.js";
@@ -31,6 +32,29 @@ test("calculate coin id", (t) => {
   );
 });
 
+test("amounts above u64 are summed", (t) => {
+  const maxU64 = 2n ** 64n - 1n;
+
+  t.is(sumAmounts([maxU64, maxU64, maxU64]), maxU64 * 3n);
+  t.is(sumAmounts([2n ** 128n - 1n]), 2n ** 128n - 1n);
+  t.is(sumAmounts([]), 0n);
+});
+
+test("invalid amounts are rejected", (t) => {
+  t.throws(() => sumAmounts([-1n]));
+  t.throws(() => sumAmounts([2n ** 128n]));
+  t.throws(() => sumAmounts([2n ** 128n - 1n, 1n]));
+
+  // Coin amounts are still limited to u64.
+  t.throws(() =>
+    toCoinId({
+      parentCoinInfo: new Uint8Array(32),
+      puzzleHash: new Uint8Array(32),
+      amount: 2n ** 64n,
+    })
+  );
+});
+
 test("byte equality", (t) => {
   const a = Uint8Array.from([1, 2, 3]);
   const b = Uint8Array.from([1, 2, 3]);
@@ -281,3 +305,53 @@ test("mint and spend nft", (t) => {
     )
   );
 });
+
+test("conditions roundtrip", (t) => {
+  const clvm = new ClvmAllocator();
+  const simulator = new Simulator();
+  const p2 = simulator.newP2(1n);
+
+  const message = Uint8Array.from([1, 2, 3]);
+
+  const conditions = [
+    clvm.createCoin(p2.puzzleHash, 1000n, [p2.puzzleHash]),
+    clvm.reserveFee(50n),
+    clvm.aggSigMe(p2.publicKey, message),
+    clvm.remark(clvm.alloc("hello")),
+  ];
+
+  // The builders return programs, which serialize to CLVM bytes.
+  const serialized = clvm.alloc(conditions).serialize();
+  const parsed = clvm.parseConditions(clvm.deserialize(serialized));
+
+  t.deepEqual(
+    parsed.map((condition) => condition.kind),
+    ["createCoin", "reserveFee", "aggSigMe", "other"]
+  );
+
+  const [createCoin, reserveFee, aggSigMe, remark] = parsed;
+
+  t.true(compareBytes(createCoin.puzzleHash!, p2.puzzleHash));
+  t.is(createCoin.amount, 1000n);
+  t.is(createCoin.memos!.length, 1);
+  t.true(compareBytes(createCoin.memos![0], p2.puzzleHash));
+  t.is(reserveFee.amount, 50n);
+  t.true(compareBytes(aggSigMe.publicKey!, p2.publicKey));
+  t.true(compareBytes(aggSigMe.message!, message));
+  t.is(remark.puzzleHash, undefined);
+
+  const rebuilt = [
+    clvm.createCoin(
+      createCoin.puzzleHash!,
+      createCoin.amount!,
+      createCoin.memos!
+    ),
+    clvm.reserveFee(reserveFee.amount!),
+    clvm.aggSigMe(aggSigMe.publicKey!, aggSigMe.message!),
+    remark.program,
+  ];
+
+  for (const [i, condition] of rebuilt.entries()) {
+    t.true(compareBytes(condition.serialize(), conditions[i].serialize()));
+  }
+});
@@ -16,6 +16,20 @@ export interface Coin {
   amount: bigint
 }
 export declare function toCoinId(coin: Coin): Uint8Array
+/**
+ * A condition output by a puzzle, tagged by its `kind`.
+ * Only the fields which apply to that kind of condition are set.
+ */
+export interface ParsedCondition {
+  kind: string
+  program: Program
+  puzzleHash?: Uint8Array
+  amount?: bigint
+  memos?: Array<Uint8Array>
+  publicKey?: Uint8Array
+  message?: Uint8Array
+  announcementId?: Uint8Array
+}
 export interface CoinSpend {
   coin: Coin
   puzzleReveal: Uint8Array
@@ -84,6 +98,11 @@ export declare function sha256(bytes: Uint8Array): Uint8Array
 export declare function fromHexRaw(hex: string): Uint8Array
 export declare function fromHex(hex: string): Uint8Array
 export declare function toHex(bytes: Uint8Array): string
+/**
+ * Adds up coin amounts, such as to get the total balance of a wallet.
+ * Unlike a single amount, the total can exceed a u64, so it's checked against a u128 instead.
+ */
+export declare function sumAmounts(amounts: Array<bigint>): bigint
 export declare class ClvmAllocator {
   constructor()
   nil(): Program
@@ -138,6 +157,11 @@ export declare class ClvmAllocator {
   sendMessage(mode: number, message: Uint8Array, data: Array<Program>): Program
   receiveMessage(mode: number, message: Uint8Array, data: Array<Program>): Program
   softfork(cost: bigint, value: Program): Program
+  /**
+   * Parses a list of conditions. Conditions which aren't coin creations, fees, signatures,
+   * or announcements have the kind `other`, and can be inspected with their `program`.
+   */
+  parseConditions(conditions: Program): Array<ParsedCondition>
 }
 export declare class Program {
   isAtom(): boolean
@@ -310,7 +310,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { ClvmAllocator, curryTreeHash, intToSignedBytes, signedBytesToInt, toCoinId, Program, Simulator, compareBytes, sha256, fromHexRaw, fromHex, toHex } = nativeBinding
+const { ClvmAllocator, curryTreeHash, intToSignedBytes, signedBytesToInt, toCoinId, Program, Simulator, compareBytes, sha256, fromHexRaw, fromHex, toHex, sumAmounts } = nativeBinding
 
 module.exports.ClvmAllocator = ClvmAllocator
 module.exports.curryTreeHash = curryTreeHash
@@ -324,3 +324,4 @@ module.exports.sha256 = sha256
 module.exports.fromHexRaw = fromHexRaw
 module.exports.fromHex = fromHex
 module.exports.toHex = toHex
+module.exports.sumAmounts = sumAmounts
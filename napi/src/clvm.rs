@@ -29,7 +29,7 @@ use crate::{
     Coin, CoinSpend, MintedNfts, Nft, NftMetadata, NftMint, ParsedNft, Program, Spend,
 };
 
-type Clvm = Reference<ClvmAllocator>;
+pub(crate) type Clvm = Reference<ClvmAllocator>;
 
 #[napi]
 pub struct ClvmAllocator(pub(crate) SpendContext);
@@ -0,0 +1,116 @@
+use chia::clvm_traits::FromClvm;
+use chia_wallet_sdk::{AggSigKind, Condition};
+use clvmr::NodePtr;
+use napi::bindgen_prelude::*;
+
+use crate::{clvm::Clvm, traits::IntoJs, ClvmAllocator, Program};
+
+/// A condition output by a puzzle, tagged by its `kind`.
+/// Only the fields which apply to that kind of condition are set.
+#[napi(object)]
+pub struct ParsedCondition {
+    pub kind: String,
+    pub program: ClassInstance<Program>,
+    pub puzzle_hash: Option<Uint8Array>,
+    pub amount: Option<BigInt>,
+    pub memos: Option<Vec<Uint8Array>>,
+    pub public_key: Option<Uint8Array>,
+    pub message: Option<Uint8Array>,
+    pub announcement_id: Option<Uint8Array>,
+}
+
+impl ParsedCondition {
+    fn new(kind: &str, program: ClassInstance<Program>) -> Self {
+        Self {
+            kind: kind.to_string(),
+            program,
+            puzzle_hash: None,
+            amount: None,
+            memos: None,
+            public_key: None,
+            message: None,
+            announcement_id: None,
+        }
+    }
+}
+
+fn agg_sig_kind(kind: AggSigKind) -> &'static str {
+    match kind {
+        AggSigKind::Parent => "aggSigParent",
+        AggSigKind::Puzzle => "aggSigPuzzle",
+        AggSigKind::Amount => "aggSigAmount",
+        AggSigKind::PuzzleAmount => "aggSigPuzzleAmount",
+        AggSigKind::ParentAmount => "aggSigParentAmount",
+        AggSigKind::ParentPuzzle => "aggSigParentPuzzle",
+        AggSigKind::Unsafe => "aggSigUnsafe",
+        AggSigKind::Me => "aggSigMe",
+    }
+}
+
+#[napi]
+impl ClvmAllocator {
+    /// Parses a list of conditions. Conditions which aren't coin creations, fees, signatures,
+    /// or announcements have the kind `other`, and can be inspected with their `program`.
+    #[napi(ts_args_type = "conditions: Program")]
+    pub fn parse_conditions(
+        &self,
+        env: Env,
+        this: This<Clvm>,
+        conditions: &Program,
+    ) -> Result<Vec<ParsedCondition>> {
+        let allocator = &self.0.allocator;
+
+        let ptrs = Vec::<NodePtr>::from_clvm(allocator, conditions.ptr)
+            .map_err(|error| Error::from_reason(error.to_string()))?;
+
+        let mut parsed = Vec::with_capacity(ptrs.len());
+
+        for ptr in ptrs {
+            let condition = Condition::<NodePtr>::from_clvm(allocator, ptr)
+                .map_err(|error| Error::from_reason(error.to_string()))?;
+
+            let program = Program::new(this.clone(env)?, ptr).into_instance(env)?;
+
+            let item = match condition {
+                Condition::CreateCoin(inner) => ParsedCondition {
+                    puzzle_hash: Some(inner.puzzle_hash.into_js()?),
+                    amount: Some(inner.amount.into_js()?),
+                    memos: Some(inner.memos.into_js()?),
+                    ..ParsedCondition::new("createCoin", program)
+                },
+                Condition::ReserveFee(inner) => ParsedCondition {
+                    amount: Some(inner.amount.into_js()?),
+                    ..ParsedCondition::new("reserveFee", program)
+                },
+                Condition::CreateCoinAnnouncement(inner) => ParsedCondition {
+                    message: Some(inner.message.into_js()?),
+                    ..ParsedCondition::new("createCoinAnnouncement", program)
+                },
+                Condition::CreatePuzzleAnnouncement(inner) => ParsedCondition {
+                    message: Some(inner.message.into_js()?),
+                    ..ParsedCondition::new("createPuzzleAnnouncement", program)
+                },
+                Condition::AssertCoinAnnouncement(inner) => ParsedCondition {
+                    announcement_id: Some(inner.announcement_id.into_js()?),
+                    ..ParsedCondition::new("assertCoinAnnouncement", program)
+                },
+                Condition::AssertPuzzleAnnouncement(inner) => ParsedCondition {
+                    announcement_id: Some(inner.announcement_id.into_js()?),
+                    ..ParsedCondition::new("assertPuzzleAnnouncement", program)
+                },
+                condition => match condition.into_agg_sig() {
+                    Some(agg_sig) => ParsedCondition {
+                        public_key: Some(agg_sig.public_key.into_js()?),
+                        message: Some(agg_sig.message.into_js()?),
+                        ..ParsedCondition::new(agg_sig_kind(agg_sig.kind), program)
+                    },
+                    None => ParsedCondition::new("other", program),
+                },
+            };
+
+            parsed.push(item);
+        }
+
+        Ok(parsed)
+    }
+}
@@ -12,6 +12,7 @@ mod clvm;
 mod clvm_value;
 mod coin;
 mod coin_spend;
+mod condition;
 mod lineage_proof;
 mod nft;
 mod program;
@@ -22,6 +23,7 @@ mod utils;
 pub use clvm::*;
 pub use coin::*;
 pub use coin_spend::*;
+pub use condition::*;
 pub use lineage_proof::*;
 pub use nft::*;
 pub use program::*;
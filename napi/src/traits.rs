@@ -167,6 +167,24 @@ impl FromJs<BigInt> for u64 {
     }
 }
 
+impl IntoJs<BigInt> for u128 {
+    fn into_js(self) -> Result<BigInt> {
+        Ok(BigInt::from(self))
+    }
+}
+
+impl FromJs<BigInt> for u128 {
+    fn from_js(js_value: BigInt) -> Result<Self> {
+        let (signed, value, lossless) = js_value.get_u128();
+
+        if signed || !lossless {
+            return Err(Error::from_reason("Expected u128"));
+        }
+
+        Ok(value)
+    }
+}
+
 impl FromJs<BigInt> for num_bigint::BigInt {
     fn from_js(num: BigInt) -> Result<Self> {
         if num.words.is_empty() {
@@ -1,7 +1,7 @@
 use clvmr::sha2::Sha256;
 use napi::bindgen_prelude::*;
 
-use crate::traits::IntoJs;
+use crate::traits::{IntoJs, IntoRust};
 
 #[napi]
 pub fn compare_bytes(a: Uint8Array, b: Uint8Array) -> bool {
@@ -37,3 +37,19 @@ pub fn from_hex(hex: String) -> Result<Uint8Array> {
 pub fn to_hex(bytes: Uint8Array) -> String {
     hex::encode(bytes.as_ref())
 }
+
+/// Adds up coin amounts, such as to get the total balance of a wallet.
+/// Unlike a single amount, the total can exceed a u64, so it's checked against a u128 instead.
+#[napi]
+pub fn sum_amounts(amounts: Vec<BigInt>) -> Result<BigInt> {
+    let mut total: u128 = 0;
+
+    for amount in amounts {
+        let amount: u128 = amount.into_rust()?;
+        total = total
+            .checked_add(amount)
+            .ok_or_else(|| Error::from_reason("Sum of amounts doesn't fit in a u128"))?;
+    }
+
+    total.into_js()
+}
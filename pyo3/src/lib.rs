@@ -0,0 +1,20 @@
+#![allow(clippy::needless_pass_by_value)]
+
+use pyo3::prelude::*;
+
+mod offer;
+mod utils;
+
+#[pymodule]
+fn chia_wallet_sdk(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(utils::compare_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::sha256, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::from_hex_raw, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::from_hex, m)?)?;
+    m.add_function(wrap_pyfunction!(utils::to_hex, m)?)?;
+    m.add_function(wrap_pyfunction!(offer::compress_offer_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(offer::decompress_offer_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(offer::compress_offer, m)?)?;
+    m.add_function(wrap_pyfunction!(offer::decompress_offer, m)?)?;
+    Ok(())
+}
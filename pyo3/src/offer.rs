@@ -0,0 +1,38 @@
+use chia_wallet_sdk::{self as sdk, OfferError};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+fn offer_error(error: OfferError) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+#[pyfunction]
+pub(crate) fn compress_offer_bytes<'py>(
+    py: Python<'py>,
+    bytes: &[u8],
+) -> PyResult<Bound<'py, PyBytes>> {
+    let compressed = sdk::compress_offer_bytes(bytes).map_err(offer_error)?;
+    Ok(PyBytes::new_bound(py, &compressed))
+}
+
+#[pyfunction]
+pub(crate) fn decompress_offer_bytes<'py>(
+    py: Python<'py>,
+    bytes: &[u8],
+) -> PyResult<Bound<'py, PyBytes>> {
+    let decompressed = sdk::decompress_offer_bytes(bytes).map_err(offer_error)?;
+    Ok(PyBytes::new_bound(py, &decompressed))
+}
+
+/// Compresses a serialized spend bundle and encodes it as a bech32m `offer1...` string.
+#[pyfunction]
+pub(crate) fn compress_offer(spend_bundle: &[u8]) -> PyResult<String> {
+    let compressed = sdk::compress_offer_bytes(spend_bundle).map_err(offer_error)?;
+    sdk::encode_offer_data(&compressed).map_err(offer_error)
+}
+
+/// Decodes an `offer1...` string and decompresses it into a serialized spend bundle.
+#[pyfunction]
+pub(crate) fn decompress_offer<'py>(py: Python<'py>, offer: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let compressed = sdk::decode_offer_data(offer).map_err(offer_error)?;
+    decompress_offer_bytes(py, &compressed)
+}
@@ -0,0 +1,30 @@
+use clvmr::sha2::Sha256;
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+#[pyfunction]
+pub(crate) fn compare_bytes(a: &[u8], b: &[u8]) -> bool {
+    a == b
+}
+
+#[pyfunction]
+pub(crate) fn sha256<'py>(py: Python<'py>, bytes: &[u8]) -> Bound<'py, PyBytes> {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    PyBytes::new_bound(py, &hasher.finalize())
+}
+
+#[pyfunction]
+pub(crate) fn from_hex_raw<'py>(py: Python<'py>, hex: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let bytes = hex::decode(hex).map_err(|error| PyValueError::new_err(error.to_string()))?;
+    Ok(PyBytes::new_bound(py, &bytes))
+}
+
+#[pyfunction]
+pub(crate) fn from_hex<'py>(py: Python<'py>, hex: &str) -> PyResult<Bound<'py, PyBytes>> {
+    from_hex_raw(py, hex.strip_prefix("0x").unwrap_or(hex))
+}
+
+#[pyfunction]
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    hex::encode(bytes)
+}
@@ -0,0 +1,35 @@
+import chia_wallet_sdk as sdk
+
+
+def test_sha256():
+    assert sdk.sha256(b"") == sdk.from_hex(
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    )
+
+
+def test_hex_roundtrip():
+    data = bytes([1, 2, 3, 255])
+    assert sdk.to_hex(data) == "010203ff"
+    assert sdk.from_hex("0x010203ff") == data
+    assert sdk.from_hex_raw("010203ff") == data
+    assert sdk.compare_bytes(sdk.from_hex(sdk.to_hex(data)), data)
+
+
+def test_invalid_hex():
+    try:
+        sdk.from_hex("xyz")
+    except ValueError:
+        return
+    raise AssertionError("expected a ValueError")
+
+
+def test_offer_compression_roundtrip():
+    # A spend bundle with no coin spends and the infinity signature.
+    spend_bundle = bytes(4) + bytes([0xC0]) + bytes(95)
+
+    compressed = sdk.compress_offer_bytes(spend_bundle)
+    assert sdk.decompress_offer_bytes(compressed) == spend_bundle
+
+    offer = sdk.compress_offer(spend_bundle)
+    assert offer.startswith("offer1")
+    assert sdk.decompress_offer(offer) == spend_bundle
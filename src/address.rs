@@ -13,6 +13,10 @@ pub enum AddressError {
     #[error("wrong length, expected 32 bytes but found {0}")]
     WrongLength(usize),
 
+    /// The address was for a different network than expected.
+    #[error("wrong prefix, expected {expected} but found {found}")]
+    WrongPrefix { expected: String, found: String },
+
     /// An error occured while trying to decode the address.
     #[error("error when decoding address: {0}")]
     Decode(#[from] bech32::Error),
@@ -64,6 +68,41 @@ pub fn decode_address(address: &str) -> Result<([u8; 32], String), AddressError>
     Ok((puzzle_hash, hrp))
 }
 
+/// Decodes each address independently, so that an invalid address doesn't prevent the rest from being decoded.
+pub fn decode_addresses(
+    addresses: &[impl AsRef<str>],
+) -> Vec<Result<([u8; 32], String), AddressError>> {
+    addresses
+        .iter()
+        .map(|address| decode_address(address.as_ref()))
+        .collect()
+}
+
+/// Decodes every address, stopping at the first one which is invalid.
+pub fn decode_addresses_strict(
+    addresses: &[impl AsRef<str>],
+) -> Result<Vec<([u8; 32], String)>, AddressError> {
+    addresses
+        .iter()
+        .map(|address| decode_address(address.as_ref()))
+        .collect()
+}
+
+/// Decodes an address into a puzzle hash, checking that it has the expected HRP prefix.
+/// For example, mainnet addresses use `xch` and testnet addresses use `txch`.
+pub fn decode_address_with_prefix(address: &str, prefix: &str) -> Result<[u8; 32], AddressError> {
+    let (puzzle_hash, found) = decode_address(address)?;
+
+    if found != prefix {
+        return Err(AddressError::WrongPrefix {
+            expected: prefix.to_string(),
+            found,
+        });
+    }
+
+    Ok(puzzle_hash)
+}
+
 /// Encodes an address with a given HRP prefix.
 pub fn encode_address(puzzle_hash: [u8; 32], prefix: &str) -> Result<String, bech32::Error> {
     let data = bech32::convert_bits(&puzzle_hash, 8, 5, true)
@@ -74,6 +113,32 @@ pub fn encode_address(puzzle_hash: [u8; 32], prefix: &str) -> Result<String, bec
     bech32::encode(prefix, data, Variant::Bech32m)
 }
 
+/// The HRP prefix of an NFT id, such as `nft1...`.
+pub const NFT_ID_PREFIX: &str = "nft";
+
+/// The HRP prefix of a DID id, such as `did:chia:1...`.
+pub const DID_ID_PREFIX: &str = "did:chia:";
+
+/// Encodes an NFT launcher id as an `nft1...` id.
+pub fn encode_nft_id(launcher_id: [u8; 32]) -> Result<String, bech32::Error> {
+    encode_address(launcher_id, NFT_ID_PREFIX)
+}
+
+/// Decodes an `nft1...` id into the NFT launcher id.
+pub fn decode_nft_id(nft_id: &str) -> Result<[u8; 32], AddressError> {
+    decode_address_with_prefix(nft_id, NFT_ID_PREFIX)
+}
+
+/// Encodes a DID launcher id as a `did:chia:1...` id.
+pub fn encode_did_id(launcher_id: [u8; 32]) -> Result<String, bech32::Error> {
+    encode_address(launcher_id, DID_ID_PREFIX)
+}
+
+/// Decodes a `did:chia:1...` id into the DID launcher id.
+pub fn decode_did_id(did_id: &str) -> Result<[u8; 32], AddressError> {
+    decode_address_with_prefix(did_id, DID_ID_PREFIX)
+}
+
 /// Removes the `0x` prefix from a puzzle hash in hex format.
 pub fn strip_prefix(puzzle_hash: &str) -> &str {
     if let Some(puzzle_hash) = puzzle_hash.strip_prefix("0x") {
@@ -167,4 +232,107 @@ mod tests {
             Err(AddressError::InvalidFormat)
         );
     }
+
+    #[test]
+    fn test_address_prefix() {
+        let address = "xch1a0t57qn6uhe7tzjlxlhwy2qgmuxvvft8gnfzmg5detg0q9f3yc3s2apz0h";
+        let (puzzle_hash, _) = decode_address(address).unwrap();
+
+        assert_eq!(decode_address_with_prefix(address, "xch"), Ok(puzzle_hash));
+        assert_eq!(
+            decode_address_with_prefix(address, "txch"),
+            Err(AddressError::WrongPrefix {
+                expected: "txch".to_string(),
+                found: "xch".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_bad_checksum() {
+        assert_eq!(
+            decode_address("xch1a0t57qn6uhe7tzjlxlhwy2qgmuxvvft8gnfzmg5detg0q9f3yc3s2apz0j"),
+            Err(AddressError::Decode(bech32::Error::InvalidChecksum))
+        );
+    }
+
+    #[test]
+    fn test_wrong_length_address() {
+        let data = bech32::convert_bits(&[42; 20], 8, 5, true)
+            .unwrap()
+            .into_iter()
+            .map(u5::try_from_u8)
+            .collect::<Result<Vec<_>, bech32::Error>>()
+            .unwrap();
+        let address = bech32::encode("xch", data, Variant::Bech32m).unwrap();
+
+        assert_eq!(decode_address(&address), Err(AddressError::WrongLength(20)));
+    }
+
+    #[test]
+    fn test_decode_addresses() {
+        let valid = "xch1ftxk2v033kv94ueucp0a34sgt9398vle7l7g3q9k4leedjmmdysqvv6q96";
+        let addresses = vec![
+            valid.to_string(),
+            "hello there!".to_string(),
+            "xch1ay273ctc9c6nxmzmzsup28scrce8ney84j4nlewdlaxqs22v53ksxgf38f".to_string(),
+        ];
+
+        let results = decode_addresses(&addresses);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], decode_address(valid));
+        assert_eq!(
+            results[1],
+            Err(AddressError::Decode(bech32::Error::MissingSeparator))
+        );
+        assert!(results[2].is_ok());
+
+        assert_eq!(
+            decode_addresses_strict(&addresses),
+            Err(AddressError::Decode(bech32::Error::MissingSeparator))
+        );
+        assert_eq!(
+            decode_addresses_strict(&[valid]).map(|decoded| decoded.len()),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_nft_id() {
+        let launcher_id = hex!("e9943cae428345e36f0e4d2d3ecdcf734ee6c6858e365c7feccc2a9ee94dbf3b");
+        let nft_id = "nft1ax2retjzsdz7xmcwf5knanw0wd8wd3593cm9cllves4fa62dhuas6nekfh";
+
+        assert_eq!(encode_nft_id(launcher_id).unwrap(), nft_id);
+        assert_eq!(decode_nft_id(nft_id), Ok(launcher_id));
+        assert_eq!(
+            decode_did_id(nft_id),
+            Err(AddressError::WrongPrefix {
+                expected: "did:chia:".to_string(),
+                found: "nft".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_did_id() {
+        let launcher_id = hex!("f5c4c1d6d9a4f6e7a6ec9f8c8b1d9b2d4a8b1e0f7c6d5e4f3a2b1c0d9e8f7a6b");
+        let did_id = "did:chia:17hzvr4ke5nmw0fhvn7xgk8vm949gk8s003k4une69vwqm8500f4s5jtj6u";
+
+        assert_eq!(encode_did_id(launcher_id).unwrap(), did_id);
+        assert_eq!(decode_did_id(did_id), Ok(launcher_id));
+        assert_eq!(
+            decode_nft_id(did_id),
+            Err(AddressError::WrongPrefix {
+                expected: "nft".to_string(),
+                found: "did:chia:".to_string()
+            })
+        );
+        assert_eq!(
+            decode_did_id("xch1a0t57qn6uhe7tzjlxlhwy2qgmuxvvft8gnfzmg5detg0q9f3yc3s2apz0h"),
+            Err(AddressError::WrongPrefix {
+                expected: "did:chia:".to_string(),
+                found: "xch".to_string()
+            })
+        );
+    }
 }
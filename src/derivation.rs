@@ -0,0 +1,233 @@
+use std::ops::Range;
+
+use chia_bls::{DerivableKey, PublicKey, SecretKey};
+use chia_puzzles::{standard::StandardArgs, DeriveSynthetic};
+use thiserror::Error;
+
+/// An error that occurs when deriving puzzle hashes.
+#[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
+pub enum DerivationError {
+    /// The next batch of indices would go past the highest unhardened index.
+    #[error("index overflow, batch starting at {0} exceeds the maximum index")]
+    IndexOverflow(u32),
+}
+
+/// Derives the synthetic public key for an unhardened index of an intermediate public key.
+/// The default hidden puzzle hash is [`DEFAULT_HIDDEN_PUZZLE_HASH`](chia_puzzles::standard::DEFAULT_HIDDEN_PUZZLE_HASH).
+pub fn synthetic_public_key(
+    intermediate_pk: &PublicKey,
+    index: u32,
+    hidden_puzzle_hash: &[u8; 32],
+) -> PublicKey {
+    intermediate_pk
+        .derive_unhardened(index)
+        .derive_synthetic_hidden(hidden_puzzle_hash)
+}
+
+/// Derives the synthetic secret key for an unhardened index of an intermediate secret key.
+/// This is the key which signs for the puzzle hash of the matching [`synthetic_public_key`].
+pub fn synthetic_secret_key(
+    intermediate_sk: &SecretKey,
+    index: u32,
+    hidden_puzzle_hash: &[u8; 32],
+) -> SecretKey {
+    intermediate_sk
+        .derive_unhardened(index)
+        .derive_synthetic_hidden(hidden_puzzle_hash)
+}
+
+/// Derives the standard puzzle hash for an unhardened index of an intermediate public key.
+pub fn standard_puzzle_hash_at(intermediate_pk: &PublicKey, index: u32) -> [u8; 32] {
+    let pk = intermediate_pk.derive_unhardened(index).derive_synthetic();
+    StandardArgs::curry_tree_hash(pk).into()
+}
+
+/// Lazily derives the unhardened standard puzzle hashes of an intermediate public key, starting at an index.
+/// This allows a large number of puzzle hashes to be processed without collecting them all at once.
+pub fn standard_puzzle_hashes(
+    intermediate_pk: &PublicKey,
+    start: u32,
+) -> impl Iterator<Item = [u8; 32]> + '_ {
+    (start..=u32::MAX).map(move |index| standard_puzzle_hash_at(intermediate_pk, index))
+}
+
+/// Options for [`derive_until_gap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GapScan {
+    /// The number of consecutive unused puzzle hashes after which scanning stops.
+    pub gap: u32,
+    /// The number of puzzle hashes derived at a time before they are checked.
+    pub batch_size: u32,
+}
+
+impl GapScan {
+    pub fn new(gap: u32) -> Self {
+        Self {
+            gap,
+            batch_size: 100,
+        }
+    }
+
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: u32) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+/// Derives unhardened standard puzzle hashes in batches until `gap` consecutive ones are unused,
+/// according to the `lookup` closure. Returns the highest used index, or [`None`] if none were used.
+///
+/// Fails if the scan would need indices past [`u32::MAX`] before reaching the gap.
+pub fn derive_until_gap(
+    intermediate_pk: &PublicKey,
+    lookup: impl Fn(&[u8; 32]) -> bool,
+    options: GapScan,
+) -> Result<Option<u32>, DerivationError> {
+    let batch_size = options.batch_size.max(1);
+
+    let mut highest_used = None;
+    let mut unused = 0;
+    let mut start = 0;
+
+    while unused < options.gap {
+        let range = batch_range(start, batch_size)?;
+        start = range.end;
+
+        let batch: Vec<[u8; 32]> = range
+            .clone()
+            .map(|index| standard_puzzle_hash_at(intermediate_pk, index))
+            .collect();
+
+        for (index, puzzle_hash) in range.zip(batch.iter()) {
+            if lookup(puzzle_hash) {
+                highest_used = Some(index);
+                unused = 0;
+            } else {
+                unused += 1;
+            }
+
+            if unused >= options.gap {
+                break;
+            }
+        }
+    }
+
+    Ok(highest_used)
+}
+
+fn batch_range(start: u32, batch_size: u32) -> Result<Range<u32>, DerivationError> {
+    let end = start
+        .checked_add(batch_size)
+        .ok_or(DerivationError::IndexOverflow(start))?;
+    Ok(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use chia_puzzles::standard::DEFAULT_HIDDEN_PUZZLE_HASH;
+
+    use super::*;
+
+    fn intermediate_pk() -> PublicKey {
+        SecretKey::from_seed(&[7; 32]).public_key()
+    }
+
+    #[test]
+    fn test_standard_puzzle_hashes() {
+        let pk = intermediate_pk();
+
+        assert_eq!(standard_puzzle_hashes(&pk, 0).take(1000).count(), 1000);
+
+        let puzzle_hashes: Vec<[u8; 32]> = standard_puzzle_hashes(&pk, 10).take(3).collect();
+        assert_eq!(
+            puzzle_hashes,
+            [10, 11, 12].map(|index| standard_puzzle_hash_at(&pk, index))
+        );
+    }
+
+    #[test]
+    fn test_gap_scan() -> anyhow::Result<()> {
+        let pk = intermediate_pk();
+        let used: Vec<[u8; 32]> = [0, 5, 7]
+            .into_iter()
+            .map(|index| standard_puzzle_hash_at(&pk, index))
+            .collect();
+
+        for batch_size in [1, 8, 100] {
+            let calls = Cell::new(0);
+            let highest = derive_until_gap(
+                &pk,
+                |puzzle_hash| {
+                    calls.set(calls.get() + 1);
+                    used.contains(puzzle_hash)
+                },
+                GapScan::new(20).with_batch_size(batch_size),
+            )?;
+
+            assert_eq!(highest, Some(7));
+            assert_eq!(calls.get(), 28);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gap_scan_first_used() -> anyhow::Result<()> {
+        let pk = intermediate_pk();
+        let first = standard_puzzle_hash_at(&pk, 0);
+
+        let highest = derive_until_gap(&pk, |puzzle_hash| *puzzle_hash == first, GapScan::new(5))?;
+        assert_eq!(highest, Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gap_scan_all_unused() -> anyhow::Result<()> {
+        let highest = derive_until_gap(&intermediate_pk(), |_| false, GapScan::new(5))?;
+        assert_eq!(highest, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_range_overflow() {
+        assert_eq!(batch_range(0, 100), Ok(0..100));
+        assert_eq!(
+            batch_range(u32::MAX - 100, 100),
+            Ok(u32::MAX - 100..u32::MAX)
+        );
+        assert_eq!(
+            batch_range(u32::MAX - 99, 100),
+            Err(DerivationError::IndexOverflow(u32::MAX - 99))
+        );
+    }
+
+    #[test]
+    fn test_synthetic_keys() {
+        let sk = SecretKey::from_seed(&[7; 32]);
+        let pk = intermediate_pk();
+        let hidden_puzzle_hash: [u8; 32] = DEFAULT_HIDDEN_PUZZLE_HASH.into();
+
+        for index in [0, 1, 42] {
+            let synthetic_pk = synthetic_public_key(&pk, index, &hidden_puzzle_hash);
+            let synthetic_sk = synthetic_secret_key(&sk, index, &hidden_puzzle_hash);
+
+            assert_eq!(synthetic_sk.public_key(), synthetic_pk);
+            assert_eq!(synthetic_pk, pk.derive_unhardened(index).derive_synthetic());
+            assert_eq!(
+                <[u8; 32]>::from(StandardArgs::curry_tree_hash(synthetic_pk)),
+                standard_puzzle_hash_at(&pk, index)
+            );
+        }
+
+        // A different hidden puzzle results in a different key.
+        assert_ne!(
+            synthetic_public_key(&pk, 0, &[1; 32]),
+            synthetic_public_key(&pk, 0, &hidden_puzzle_hash)
+        );
+    }
+}
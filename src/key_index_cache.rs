@@ -0,0 +1,214 @@
+use std::collections::{HashMap, HashSet};
+
+use chia_bls::{PublicKey, SecretKey};
+use chia_puzzles::standard::{StandardArgs, DEFAULT_HIDDEN_PUZZLE_HASH};
+
+use crate::{synthetic_public_key, synthetic_secret_key};
+
+/// Maps synthetic public keys and standard puzzle hashes back to their unhardened derivation index.
+///
+/// Keys are derived lazily from the intermediate public key. When a lookup misses, derivation is
+/// extended up to `gap` indices past the highest index that has been found so far, and no further.
+/// Everything derived along the way is remembered for later lookups, as are the misses, until a
+/// higher index is found.
+#[derive(Debug, Clone)]
+pub struct KeyIndexCache {
+    intermediate_pk: PublicKey,
+    hidden_puzzle_hash: [u8; 32],
+    gap: u32,
+    derived: u32,
+    highest_found: Option<u32>,
+    public_keys: HashMap<PublicKey, u32>,
+    puzzle_hashes: HashMap<[u8; 32], PublicKey>,
+    missed_public_keys: HashSet<PublicKey>,
+    missed_puzzle_hashes: HashSet<[u8; 32]>,
+}
+
+impl KeyIndexCache {
+    /// Creates an empty cache for the default hidden puzzle hash.
+    pub fn new(intermediate_pk: PublicKey, gap: u32) -> Self {
+        Self {
+            intermediate_pk,
+            hidden_puzzle_hash: DEFAULT_HIDDEN_PUZZLE_HASH.into(),
+            gap,
+            derived: 0,
+            highest_found: None,
+            public_keys: HashMap::new(),
+            puzzle_hashes: HashMap::new(),
+            missed_public_keys: HashSet::new(),
+            missed_puzzle_hashes: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_hidden_puzzle_hash(mut self, hidden_puzzle_hash: [u8; 32]) -> Self {
+        self.hidden_puzzle_hash = hidden_puzzle_hash;
+        self.public_keys.clear();
+        self.puzzle_hashes.clear();
+        self.missed_public_keys.clear();
+        self.missed_puzzle_hashes.clear();
+        self.highest_found = None;
+        self.derived = 0;
+        self
+    }
+
+    /// The number of indices that have been derived so far.
+    pub fn derived(&self) -> u32 {
+        self.derived
+    }
+
+    /// The highest index which has been found by a lookup, if any.
+    pub fn highest_found(&self) -> Option<u32> {
+        self.highest_found
+    }
+
+    /// Derives and caches every index below `count`.
+    pub fn derive_to(&mut self, count: u32) {
+        while self.derived < count {
+            let index = self.derived;
+            let public_key =
+                synthetic_public_key(&self.intermediate_pk, index, &self.hidden_puzzle_hash);
+            let puzzle_hash = StandardArgs::curry_tree_hash(public_key).into();
+
+            self.public_keys.insert(public_key, index);
+            self.puzzle_hashes.insert(puzzle_hash, public_key);
+            self.derived += 1;
+        }
+    }
+
+    /// Finds the derivation index of a synthetic public key, extending derivation if needed.
+    pub fn index_of(&mut self, public_key: &PublicKey) -> Option<u32> {
+        if self.missed_public_keys.contains(public_key) {
+            return None;
+        }
+
+        if !self.public_keys.contains_key(public_key) {
+            self.extend();
+        }
+
+        let Some(index) = self.public_keys.get(public_key).copied() else {
+            self.missed_public_keys.insert(*public_key);
+            return None;
+        };
+
+        self.found(index);
+        Some(index)
+    }
+
+    /// Finds the synthetic public key of a standard puzzle hash, extending derivation if needed.
+    pub fn public_key_of(&mut self, puzzle_hash: &[u8; 32]) -> Option<PublicKey> {
+        if self.missed_puzzle_hashes.contains(puzzle_hash) {
+            return None;
+        }
+
+        if !self.puzzle_hashes.contains_key(puzzle_hash) {
+            self.extend();
+        }
+
+        let Some(public_key) = self.puzzle_hashes.get(puzzle_hash).copied() else {
+            self.missed_puzzle_hashes.insert(*puzzle_hash);
+            return None;
+        };
+
+        self.found(self.public_keys[&public_key]);
+        Some(public_key)
+    }
+
+    /// Finds the synthetic secret key which signs for a public key, such as the one in a
+    /// [`RequiredSignature`](crate::RequiredSignature). The intermediate secret key must match
+    /// the intermediate public key of the cache.
+    pub fn secret_key(
+        &mut self,
+        intermediate_sk: &SecretKey,
+        public_key: &PublicKey,
+    ) -> Option<SecretKey> {
+        let index = self.index_of(public_key)?;
+        Some(synthetic_secret_key(
+            intermediate_sk,
+            index,
+            &self.hidden_puzzle_hash,
+        ))
+    }
+
+    /// Derives every index up to `gap` past the highest index found so far.
+    fn extend(&mut self) {
+        let count = self.highest_found.map_or(self.gap, |index| {
+            index.saturating_add(self.gap).saturating_add(1)
+        });
+        self.derive_to(count);
+    }
+
+    /// Raises the highest found index. Any previous miss may now be within the gap, so misses are forgotten.
+    fn found(&mut self, index: u32) {
+        if self.highest_found.is_some_and(|highest| highest >= index) {
+            return;
+        }
+
+        self.highest_found = Some(index);
+        self.missed_public_keys.clear();
+        self.missed_puzzle_hashes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::standard_puzzle_hash_at;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_key_within_gap() {
+        let intermediate_sk = SecretKey::from_seed(&[3; 32]);
+        let intermediate_pk = intermediate_sk.public_key();
+        let hidden_puzzle_hash: [u8; 32] = DEFAULT_HIDDEN_PUZZLE_HASH.into();
+        let public_key_at =
+            |index| synthetic_public_key(&intermediate_pk, index, &hidden_puzzle_hash);
+
+        let mut cache = KeyIndexCache::new(intermediate_pk, 10);
+
+        // Nothing has been found yet, so only the first gap is derived.
+        assert_eq!(cache.index_of(&public_key_at(5)), Some(5));
+        assert_eq!(cache.derived(), 10);
+        assert_eq!(cache.highest_found(), Some(5));
+
+        // The key at index 15 is within the gap of the highest index found.
+        let public_key = public_key_at(15);
+        assert_eq!(cache.index_of(&public_key), Some(15));
+        assert_eq!(cache.derived(), 16);
+
+        let secret_key = cache
+            .secret_key(&intermediate_sk, &public_key)
+            .expect("missing secret key");
+        assert_eq!(secret_key.public_key(), public_key);
+
+        let puzzle_hash = standard_puzzle_hash_at(&intermediate_pk, 15);
+        assert_eq!(cache.public_key_of(&puzzle_hash), Some(public_key));
+        assert_eq!(cache.derived(), 16);
+    }
+
+    #[test]
+    fn test_misses_stop_at_gap() {
+        let intermediate_pk = SecretKey::from_seed(&[3; 32]).public_key();
+        let hidden_puzzle_hash: [u8; 32] = DEFAULT_HIDDEN_PUZZLE_HASH.into();
+        let public_key_at =
+            |index| synthetic_public_key(&intermediate_pk, index, &hidden_puzzle_hash);
+
+        let mut cache = KeyIndexCache::new(intermediate_pk, 10);
+        assert_eq!(cache.index_of(&public_key_at(3)), Some(3));
+
+        // Keys past the gap aren't found, and repeated misses don't derive any further.
+        for _ in 0..3 {
+            assert_eq!(cache.index_of(&public_key_at(45)), None);
+            assert_eq!(
+                cache.public_key_of(&standard_puzzle_hash_at(&intermediate_pk, 45)),
+                None
+            );
+            assert_eq!(cache.derived(), 14);
+        }
+
+        // Finding a higher index extends the gap, so earlier misses are retried.
+        assert_eq!(cache.index_of(&public_key_at(13)), Some(13));
+        assert_eq!(cache.index_of(&public_key_at(20)), Some(20));
+        assert_eq!(cache.derived(), 24);
+    }
+}
@@ -2,9 +2,19 @@
 
 mod address;
 mod coin_selection;
+mod derivation;
+mod key_index_cache;
+mod offer_status;
+mod spend_bundle_json;
+mod wif;
 
 pub use address::*;
 pub use coin_selection::*;
+pub use derivation::*;
+pub use key_index_cache::*;
+pub use offer_status::*;
+pub use spend_bundle_json::*;
+pub use wif::*;
 
 pub use chia_sdk_client::*;
 pub use chia_sdk_driver::*;
@@ -0,0 +1,239 @@
+use chia_protocol::{Bytes32, Coin};
+use chia_sdk_client::{ClientError, Peer};
+use chia_sdk_offers::OfferSummary;
+use indexmap::IndexSet;
+
+/// Whether an offer can still be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfferStatus {
+    /// None of the maker's coins have been spent, and the offer hasn't expired.
+    Valid,
+    /// The offer's expiration timestamp has been reached.
+    Expired,
+    /// The maker's coins were spent without locking the offered coins into the settlement puzzle.
+    Cancelled,
+    /// The offer was taken, and the offered coins were created.
+    Completed,
+}
+
+/// Checks the status of an offer against the current coin state of the peer.
+///
+/// The timestamp is compared against the offer's expiration, and should be the current time
+/// (or the timestamp of the latest transaction block) in seconds since the unix epoch.
+///
+/// An offer which doesn't spend any of the maker's coins can never be taken,
+/// so it's reported as [`OfferStatus::Cancelled`]. Likewise, an offer with no offered coins
+/// can't be shown to have completed, so it's cancelled once a maker coin is spent.
+pub async fn offer_status(
+    peer: &Peer,
+    summary: &OfferSummary,
+    genesis_challenge: Bytes32,
+    timestamp: u64,
+) -> Result<OfferStatus, ClientError> {
+    if summary.maker_coins.is_empty() {
+        return Ok(OfferStatus::Cancelled);
+    }
+
+    let maker_coin_ids: IndexSet<Bytes32> = summary.maker_coins.iter().map(Coin::coin_id).collect();
+
+    let offered_coin_ids: Vec<Bytes32> = summary
+        .offered
+        .iter()
+        .map(|offered| offered.coin.coin_id())
+        .collect();
+
+    let coin_states = peer
+        .request_coin_state(
+            maker_coin_ids
+                .iter()
+                .copied()
+                .chain(offered_coin_ids.iter().copied())
+                .collect(),
+            None,
+            genesis_challenge,
+            false,
+        )
+        .await?
+        .map_err(|rejection| ClientError::CoinStateRejected(rejection.reason))?
+        .coin_states;
+
+    let is_unspent = |coin_id: &Bytes32| {
+        coin_states.iter().any(|coin_state| {
+            coin_state.coin.coin_id() == *coin_id && coin_state.spent_height.is_none()
+        })
+    };
+
+    let exists = |coin_id: &Bytes32| {
+        coin_states
+            .iter()
+            .any(|coin_state| coin_state.coin.coin_id() == *coin_id)
+    };
+
+    if maker_coin_ids.iter().all(is_unspent) {
+        if summary
+            .expires_at
+            .is_some_and(|expires_at| timestamp >= expires_at)
+        {
+            return Ok(OfferStatus::Expired);
+        }
+
+        return Ok(OfferStatus::Valid);
+    }
+
+    if !offered_coin_ids.is_empty() && offered_coin_ids.iter().all(exists) {
+        Ok(OfferStatus::Completed)
+    } else {
+        Ok(OfferStatus::Cancelled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chia_bls::Signature;
+    use chia_protocol::{CoinSpend, SpendBundle};
+    use chia_puzzles::offer::SETTLEMENT_PAYMENTS_PUZZLE_HASH;
+    use chia_sdk_offers::Offer;
+    use chia_sdk_test::{to_program, to_puzzle, PeerSimulator};
+    use chia_sdk_types::Conditions;
+    use clvmr::Allocator;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_offer_status() -> anyhow::Result<()> {
+        let sim = PeerSimulator::new().await?;
+        let peer = sim.connect().await?;
+        let genesis_challenge = sim.config().constants.genesis_challenge;
+
+        let mut allocator = Allocator::new();
+        let (puzzle_hash, puzzle_reveal) = to_puzzle(1)?;
+        let settlement_hash: Bytes32 = SETTLEMENT_PAYMENTS_PUZZLE_HASH.into();
+
+        let spend = |coin: Coin, conditions: Conditions| -> anyhow::Result<SpendBundle> {
+            Ok(SpendBundle::new(
+                vec![CoinSpend::new(
+                    coin,
+                    puzzle_reveal.clone(),
+                    to_program(conditions)?,
+                )],
+                Signature::default(),
+            ))
+        };
+
+        // The offer is taken, so the settlement coin is created.
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+        let offer = spend(
+            coin,
+            Conditions::new().create_coin(settlement_hash, 1, Vec::new()),
+        )?;
+        let summary = Offer::new(offer.clone()).summary(&mut allocator)?;
+        assert_eq!(summary.offered.len(), 1);
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 0).await?,
+            OfferStatus::Valid
+        );
+
+        assert_eq!(peer.send_transaction(offer).await?.status, 1);
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 0).await?,
+            OfferStatus::Completed
+        );
+
+        // The maker's coin is spent elsewhere, which cancels the offer.
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+        let offer = spend(
+            coin,
+            Conditions::new().create_coin(settlement_hash, 1, Vec::new()),
+        )?;
+        let summary = Offer::new(offer).summary(&mut allocator)?;
+
+        let cancel = spend(
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+        assert_eq!(peer.send_transaction(cancel).await?.status, 1);
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 0).await?,
+            OfferStatus::Cancelled
+        );
+
+        // The offer expires once the timestamp is reached.
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+        let offer = spend(
+            coin,
+            Conditions::new()
+                .create_coin(settlement_hash, 1, Vec::new())
+                .assert_before_seconds_absolute(100),
+        )?;
+        let summary = Offer::new(offer).summary(&mut allocator)?;
+        assert_eq!(summary.expires_at, Some(100));
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 99).await?,
+            OfferStatus::Valid
+        );
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 100).await?,
+            OfferStatus::Expired
+        );
+
+        // Spending a maker coin which doesn't create an offered coin also cancels the offer.
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+        let fee_coin = sim.mint_coin(puzzle_hash, 1).await;
+        let mut offer = spend(
+            coin,
+            Conditions::new().create_coin(settlement_hash, 1, Vec::new()),
+        )?;
+        offer
+            .coin_spends
+            .extend(spend(fee_coin, Conditions::new().reserve_fee(1))?.coin_spends);
+        let summary = Offer::new(offer).summary(&mut allocator)?;
+        assert_eq!(summary.maker_coins, [coin, fee_coin]);
+        assert_eq!(summary.offered.len(), 1);
+
+        let cancel = spend(
+            fee_coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+        assert_eq!(peer.send_transaction(cancel).await?.status, 1);
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 0).await?,
+            OfferStatus::Cancelled
+        );
+
+        // Without any offered coins, spending a maker coin can't complete the offer.
+        let coin = sim.mint_coin(puzzle_hash, 1).await;
+        let offer = spend(coin, Conditions::new().reserve_fee(1))?;
+        let summary = Offer::new(offer).summary(&mut allocator)?;
+        assert_eq!(summary.maker_coins, [coin]);
+        assert!(summary.offered.is_empty());
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 0).await?,
+            OfferStatus::Valid
+        );
+
+        let cancel = spend(
+            coin,
+            Conditions::new().create_coin(puzzle_hash, 1, Vec::new()),
+        )?;
+        assert_eq!(peer.send_transaction(cancel).await?.status, 1);
+
+        assert_eq!(
+            offer_status(&peer, &summary, genesis_challenge, 0).await?,
+            OfferStatus::Cancelled
+        );
+
+        // There is nothing to check if the offer doesn't spend any coins.
+        assert_eq!(
+            offer_status(&peer, &OfferSummary::default(), genesis_challenge, 0).await?,
+            OfferStatus::Cancelled
+        );
+
+        Ok(())
+    }
+}
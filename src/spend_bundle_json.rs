@@ -0,0 +1,159 @@
+use chia_bls::Signature;
+use chia_protocol::{Bytes32, Coin, CoinSpend, Program, SpendBundle};
+use hex::FromHexError;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// Errors you can get while trying to read a spend bundle from JSON.
+#[derive(Error, Debug)]
+pub enum SpendBundleJsonError {
+    /// A required field wasn't present.
+    #[error("missing field {0:?}")]
+    MissingField(&'static str),
+
+    /// A field had the wrong type or length.
+    #[error("invalid field {0:?}")]
+    InvalidField(&'static str),
+
+    /// A field wasn't valid hex.
+    #[error("hex error: {0}")]
+    Hex(#[from] FromHexError),
+
+    /// The aggregated signature wasn't a valid G2 element.
+    #[error("bls error: {0}")]
+    Bls(#[from] chia_bls::Error),
+}
+
+/// Converts a spend bundle to the JSON format used by the full node RPC, such as `push_tx`.
+/// Bytes are hex encoded with a `0x` prefix, and coin amounts are numbers.
+pub fn spend_bundle_to_json(spend_bundle: &SpendBundle) -> Value {
+    json!({
+        "coin_spends": spend_bundle
+            .coin_spends
+            .iter()
+            .map(|coin_spend| json!({
+                "coin": {
+                    "parent_coin_info": encode_hex(coin_spend.coin.parent_coin_info.as_ref()),
+                    "puzzle_hash": encode_hex(coin_spend.coin.puzzle_hash.as_ref()),
+                    "amount": coin_spend.coin.amount,
+                },
+                "puzzle_reveal": encode_hex(coin_spend.puzzle_reveal.as_ref()),
+                "solution": encode_hex(coin_spend.solution.as_ref()),
+            }))
+            .collect::<Vec<Value>>(),
+        "aggregated_signature": encode_hex(&spend_bundle.aggregated_signature.to_bytes()),
+    })
+}
+
+/// Reads a spend bundle from the JSON format used by the full node RPC.
+/// The `0x` prefix is optional, since the full node accepts hex without it.
+pub fn spend_bundle_from_json(value: &Value) -> Result<SpendBundle, SpendBundleJsonError> {
+    let coin_spends = field(value, "coin_spends")?
+        .as_array()
+        .ok_or(SpendBundleJsonError::InvalidField("coin_spends"))?
+        .iter()
+        .map(coin_spend_from_json)
+        .collect::<Result<Vec<CoinSpend>, SpendBundleJsonError>>()?;
+
+    let signature: [u8; 96] = hex_field(value, "aggregated_signature")?
+        .try_into()
+        .map_err(|_| SpendBundleJsonError::InvalidField("aggregated_signature"))?;
+
+    Ok(SpendBundle::new(
+        coin_spends,
+        Signature::from_bytes(&signature)?,
+    ))
+}
+
+fn coin_spend_from_json(value: &Value) -> Result<CoinSpend, SpendBundleJsonError> {
+    let coin = field(value, "coin")?;
+
+    let amount = field(coin, "amount")?
+        .as_u64()
+        .ok_or(SpendBundleJsonError::InvalidField("amount"))?;
+
+    Ok(CoinSpend::new(
+        Coin::new(
+            bytes32_field(coin, "parent_coin_info")?,
+            bytes32_field(coin, "puzzle_hash")?,
+            amount,
+        ),
+        Program::from(hex_field(value, "puzzle_reveal")?),
+        Program::from(hex_field(value, "solution")?),
+    ))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn field<'a>(value: &'a Value, name: &'static str) -> Result<&'a Value, SpendBundleJsonError> {
+    value
+        .get(name)
+        .ok_or(SpendBundleJsonError::MissingField(name))
+}
+
+fn hex_field(value: &Value, name: &'static str) -> Result<Vec<u8>, SpendBundleJsonError> {
+    let text = field(value, name)?
+        .as_str()
+        .ok_or(SpendBundleJsonError::InvalidField(name))?;
+    Ok(hex::decode(text.strip_prefix("0x").unwrap_or(text))?)
+}
+
+fn bytes32_field(value: &Value, name: &'static str) -> Result<Bytes32, SpendBundleJsonError> {
+    let bytes: [u8; 32] = hex_field(value, name)?
+        .try_into()
+        .map_err(|_| SpendBundleJsonError::InvalidField(name))?;
+    Ok(bytes.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spend_bundle_json_roundtrip() -> anyhow::Result<()> {
+        let spend_bundle = SpendBundle::new(
+            vec![CoinSpend::new(
+                Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 1000),
+                Program::from(vec![0x01]),
+                Program::from(vec![0xff, 0x80, 0x80]),
+            )],
+            Signature::default(),
+        );
+
+        let fixture = json!({
+            "coin_spends": [
+                {
+                    "coin": {
+                        "parent_coin_info": "0x0101010101010101010101010101010101010101010101010101010101010101",
+                        "puzzle_hash": "0x0202020202020202020202020202020202020202020202020202020202020202",
+                        "amount": 1000
+                    },
+                    "puzzle_reveal": "0x01",
+                    "solution": "0xff8080"
+                }
+            ],
+            "aggregated_signature": format!("0xc0{}", "00".repeat(95))
+        });
+
+        assert_eq!(spend_bundle_to_json(&spend_bundle), fixture);
+        assert_eq!(spend_bundle_from_json(&fixture)?, spend_bundle);
+
+        let mut unprefixed = fixture.clone();
+        unprefixed["coin_spends"][0]["solution"] = json!("ff8080");
+        assert_eq!(spend_bundle_from_json(&unprefixed)?, spend_bundle);
+
+        let mut missing = fixture;
+        missing["coin_spends"][0]["coin"]
+            .as_object_mut()
+            .expect("coin is an object")
+            .remove("amount");
+        assert!(matches!(
+            spend_bundle_from_json(&missing),
+            Err(SpendBundleJsonError::MissingField("amount"))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use chia_bls::SecretKey;
+use clvmr::sha2::Sha256;
+use thiserror::Error;
+
+/// The version byte prepended to the secret key before encoding.
+///
+/// Bitcoin uses `0x80` for mainnet WIF keys, but BLS secret keys aren't interchangeable with
+/// secp256k1 keys, so a distinct byte is used to avoid a key being mistakenly imported by
+/// a Bitcoin wallet (or the other way around).
+pub const WIF_VERSION: u8 = 0xb1;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const CHECKSUM_LENGTH: usize = 4;
+const PAYLOAD_LENGTH: usize = 1 + 32 + CHECKSUM_LENGTH;
+
+/// Errors you can get while trying to import a secret key.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum KeyError {
+    /// The string contained a character outside of the base58 alphabet.
+    #[error("invalid base58 character {0:?}")]
+    InvalidCharacter(char),
+
+    /// The decoded data was not the expected length.
+    #[error("wrong length, expected {PAYLOAD_LENGTH} bytes but found {0}")]
+    WrongLength(usize),
+
+    /// The version byte didn't match [`WIF_VERSION`].
+    #[error("unknown version byte {0:#04x}")]
+    InvalidVersion(u8),
+
+    /// The checksum didn't match the payload.
+    #[error("invalid checksum")]
+    InvalidChecksum,
+
+    /// The payload wasn't a valid secret key.
+    #[error("invalid secret key")]
+    InvalidSecretKey,
+}
+
+/// Encodes a secret key as a base58check string, prefixed with [`WIF_VERSION`].
+pub fn export_secret_key_wif(sk: &SecretKey) -> String {
+    let mut payload = Vec::with_capacity(PAYLOAD_LENGTH);
+    payload.push(WIF_VERSION);
+    payload.extend_from_slice(&sk.to_bytes());
+    let checksum = checksum(&payload);
+    payload.extend_from_slice(&checksum);
+    base58_encode(&payload)
+}
+
+/// Decodes a secret key which was encoded with [`export_secret_key_wif`].
+pub fn import_secret_key_wif(s: &str) -> Result<SecretKey, KeyError> {
+    let payload = base58_decode(s)?;
+
+    if payload.len() != PAYLOAD_LENGTH {
+        return Err(KeyError::WrongLength(payload.len()));
+    }
+
+    let (data, expected) = payload.split_at(PAYLOAD_LENGTH - CHECKSUM_LENGTH);
+
+    if checksum(data) != expected {
+        return Err(KeyError::InvalidChecksum);
+    }
+
+    if data[0] != WIF_VERSION {
+        return Err(KeyError::InvalidVersion(data[0]));
+    }
+
+    let bytes: [u8; 32] = data[1..].try_into().expect("length was checked");
+    SecretKey::from_bytes(&bytes).map_err(|_| KeyError::InvalidSecretKey)
+}
+
+fn checksum(data: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    let first = hasher.finalize();
+
+    let mut hasher = Sha256::new();
+    hasher.update(first);
+    let second = hasher.finalize();
+
+    [second[0], second[1], second[2], second[3]]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn base58_encode(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&byte| byte == 0).count();
+
+    // Digits are stored in little-endian order, in base 58.
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+
+    for &byte in &data[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut result = String::with_capacity(zeros + digits.len());
+    result.extend(std::iter::repeat('1').take(zeros));
+    result.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| char::from(ALPHABET[usize::from(digit)])),
+    );
+    result
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn base58_decode(s: &str) -> Result<Vec<u8>, KeyError> {
+    let zeros = s.chars().take_while(|&c| c == '1').count();
+
+    // Bytes are stored in little-endian order, in base 256.
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+
+    for c in s.chars().skip(zeros) {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&a| char::from(a) == c)
+            .ok_or(KeyError::InvalidCharacter(c))? as u32;
+
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut result = vec![0; zeros];
+    result.extend(bytes.iter().rev());
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_base58() {
+        assert_eq!(base58_encode(&[]), "");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(base58_decode("StV1DL6CwTryKyV").unwrap(), b"hello world");
+        assert_eq!(base58_decode("112").unwrap(), [0, 0, 1]);
+        assert_eq!(base58_decode("0"), Err(KeyError::InvalidCharacter('0')));
+    }
+
+    #[test]
+    fn test_wif_roundtrip() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1337);
+
+        for _ in 0..20 {
+            let sk = SecretKey::from_seed(&rng.gen::<[u8; 32]>());
+            let wif = export_secret_key_wif(&sk);
+            assert_eq!(import_secret_key_wif(&wif).unwrap(), sk);
+        }
+    }
+
+    #[test]
+    fn test_wif_bad_checksum() {
+        let sk = SecretKey::from_seed(&[42; 32]);
+        let mut payload = base58_decode(&export_secret_key_wif(&sk)).unwrap();
+        let last = payload.len() - 1;
+        payload[last] ^= 1;
+
+        assert_eq!(
+            import_secret_key_wif(&base58_encode(&payload)),
+            Err(KeyError::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn test_wif_wrong_length() {
+        let sk = SecretKey::from_seed(&[42; 32]);
+        let mut payload = base58_decode(&export_secret_key_wif(&sk)).unwrap();
+        payload.pop();
+
+        assert_eq!(
+            import_secret_key_wif(&base58_encode(&payload)),
+            Err(KeyError::WrongLength(PAYLOAD_LENGTH - 1))
+        );
+    }
+}
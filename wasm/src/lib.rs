@@ -0,0 +1,5 @@
+#![allow(clippy::needless_pass_by_value)]
+
+mod offer;
+
+pub use offer::*;
@@ -0,0 +1,47 @@
+use chia_sdk_offers::{self as offers, OfferError};
+use wasm_bindgen::prelude::*;
+
+fn offer_error(error: OfferError) -> JsError {
+    let message = match error {
+        OfferError::MissingVersionPrefix => {
+            "Offer is missing its compression version prefix".to_string()
+        }
+        OfferError::UnsupportedVersion => {
+            "Offer was compressed with an unsupported version".to_string()
+        }
+        OfferError::NotCompressed => "Offer is not compressed".to_string(),
+        OfferError::Io(error) => format!("Offer data is corrupt: {error}"),
+        OfferError::Flate2(error) => format!("Offer data is corrupt: {error}"),
+        OfferError::InvalidPrefix(prefix) => {
+            format!("Expected an offer1 string, but found prefix {prefix}")
+        }
+        OfferError::InvalidFormat => "Offer is not encoded as bech32m".to_string(),
+        OfferError::Decode(error) => format!("Offer is not valid bech32m: {error}"),
+        error => error.to_string(),
+    };
+    JsError::new(&message)
+}
+
+#[wasm_bindgen(js_name = compressOfferBytes)]
+pub fn compress_offer_bytes(bytes: &[u8]) -> Result<Vec<u8>, JsError> {
+    offers::compress_offer_bytes(bytes).map_err(offer_error)
+}
+
+#[wasm_bindgen(js_name = decompressOfferBytes)]
+pub fn decompress_offer_bytes(bytes: &[u8]) -> Result<Vec<u8>, JsError> {
+    offers::decompress_offer_bytes(bytes).map_err(offer_error)
+}
+
+/// Compresses a serialized spend bundle and encodes it as a bech32m `offer1...` string.
+#[wasm_bindgen(js_name = compressOffer)]
+pub fn compress_offer(spend_bundle: &[u8]) -> Result<String, JsError> {
+    let compressed = compress_offer_bytes(spend_bundle)?;
+    offers::encode_offer_data(&compressed).map_err(offer_error)
+}
+
+/// Decodes an `offer1...` string and decompresses it into a serialized spend bundle.
+#[wasm_bindgen(js_name = decompressOffer)]
+pub fn decompress_offer(offer: &str) -> Result<Vec<u8>, JsError> {
+    let compressed = offers::decode_offer_data(offer).map_err(offer_error)?;
+    decompress_offer_bytes(&compressed)
+}
@@ -0,0 +1,51 @@
+#![cfg(target_arch = "wasm32")]
+
+use chia_wallet_sdk_wasm::{
+    compress_offer, compress_offer_bytes, decompress_offer, decompress_offer_bytes,
+};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const COMPRESSED_OFFER: &str =
+    include_str!("../../crates/chia-sdk-offers/test_data/compressed.offer");
+const DECOMPRESSED_OFFER: &str =
+    include_str!("../../crates/chia-sdk-offers/test_data/decompressed.offer");
+
+/// A spend bundle with no coin spends and the infinity signature.
+fn empty_spend_bundle() -> Vec<u8> {
+    let mut bytes = vec![0; 4];
+    bytes.push(0xc0);
+    bytes.extend([0; 95]);
+    bytes
+}
+
+#[wasm_bindgen_test]
+fn test_offer_roundtrip() {
+    let spend_bundle = empty_spend_bundle();
+
+    let offer = compress_offer(&spend_bundle).unwrap();
+    assert!(offer.starts_with("offer1"));
+    assert_eq!(decompress_offer(&offer).unwrap(), spend_bundle);
+
+    let compressed = compress_offer_bytes(&spend_bundle).unwrap();
+    assert_eq!(&compressed[..2], [0, 6]);
+    assert_eq!(decompress_offer_bytes(&compressed).unwrap(), spend_bundle);
+}
+
+#[wasm_bindgen_test]
+fn test_decompress_sample_offer() {
+    let compressed = hex::decode(COMPRESSED_OFFER.trim()).unwrap();
+    let decompressed = hex::decode(DECOMPRESSED_OFFER.trim()).unwrap();
+
+    assert_eq!(decompress_offer_bytes(&compressed).unwrap(), decompressed);
+    assert_eq!(compress_offer_bytes(&decompressed).unwrap(), compressed);
+
+    let offer = chia_sdk_offers::encode_offer_data(&compressed).unwrap();
+    assert_eq!(decompress_offer(&offer).unwrap(), decompressed);
+}
+
+#[wasm_bindgen_test]
+fn test_decompression_errors() {
+    assert!(decompress_offer_bytes(&[]).is_err());
+    assert!(decompress_offer_bytes(&[0, 7, 1, 2, 3]).is_err());
+    assert!(decompress_offer("not an offer").is_err());
+}